@@ -1,12 +1,20 @@
-use std::fmt::{self, Display};
+use std::{
+    fmt::{self, Display},
+    path::PathBuf,
+};
 
 use common::{
     commands::ApiCommand,
+    fuzzy::SearchMatch,
     item::{Item, Name},
     items::Items,
-    list::List,
-    recipes::{Ingredients, Recipe},
-    section::Section,
+    layout::{StoreLayout, StoreName},
+    list::{List, ListName},
+    pantry::{ExpiryReport, PantryItem},
+    plan::{DateRange, MealPlan, MealSlot, PlannedMeal},
+    recipes::{Ingredients, Recipe, RecipeCoverage},
+    section::{Section, UNSORTED},
+    unit::UnitSystem,
 };
 use persistence::store::{Store, StoreDispatch, StoreError, StoreResponse, StoreType};
 
@@ -112,23 +120,144 @@ impl ApiDispatch {
 pub enum ApiResponse {
     AddedChecklistItem(Name),
     AddedItem(Name),
+    AddedItemAlias {
+        item: Name,
+        alias: Name,
+    },
+    AddedList(ListName),
     AddedListItem(Name),
     AddedListRecipe(Recipe),
+    AddedListFromPlan {
+        list: ListName,
+        range: DateRange,
+        recipes: Vec<Recipe>,
+    },
+    AddedPantryItem(PantryItem),
     AddedRecipe(Recipe),
+    AddedSection(Section),
+    AddedStore(StoreName),
+    ArchivedList(ListName),
+    AuditedPantryItem(PantryItem),
     Checklist(Vec<Item>),
-    DeletedRecipe(Recipe),
+    CheckedListItem(Name),
+    ClearedCheckedListItems(Vec<Name>),
+    ClearedChecklist,
+    ClearedList(ListName),
+    ConsumedPantryItem {
+        item: Name,
+        left: Option<PantryItem>,
+    },
+    DeletedRecipe {
+        recipe: Recipe,
+        kept: Vec<Name>,
+        removed: Vec<Name>,
+        unplanned: Vec<MealSlot>,
+    },
     DeletedChecklistItem(Name),
+    DeletedItem(Name),
+    DeletedItemAlias(Name),
+    DeletedList(ListName),
+    DeletedListItem(Name),
+    DeletedPantryItem(Name),
+    DeletedPlannedMeal(PlannedMeal),
+    DeletedSection(Section),
+    DeletedStore(StoreName),
+    DuplicateItems(Vec<Vec<Name>>),
+    Expiring(ExpiryReport),
     Exported(Vec<Item>, List),
+    ExportedMealPlan {
+        plan: MealPlan,
+        path: PathBuf,
+    },
     FetchedRecipe((Recipe, Ingredients)),
+    Item(Item),
+    ItemAliases(Vec<(Name, Name)>),
     ItemAlreadyAdded(Name),
     Items(Items),
     ImportToSqlite,
+    ImportedMealPlan(MealPlan),
     List(List),
+    MergedDuplicates(Vec<(Name, Name)>),
+    MergedItems {
+        item: Name,
+        into: Name,
+    },
+    MergedSection {
+        section: Section,
+        into: Section,
+    },
+    Lists {
+        active: Vec<ListName>,
+        archived: Vec<ListName>,
+    },
+    MealPlan(MealPlan),
+    MovedMeal {
+        meal: PlannedMeal,
+        from: MealSlot,
+    },
     NothingReturned(ApiCommand),
+    Pantry(Vec<PantryItem>),
+    PlannedMeal(PlannedMeal),
     Recipes(Vec<Recipe>),
+    RecipeCoverage(Vec<RecipeCoverage>),
     RecipeIngredients(Option<Ingredients>),
+    RecipeNotFound {
+        recipe: Recipe,
+        suggestions: Vec<Recipe>,
+    },
     RefreshList,
+    RenamedList {
+        list: ListName,
+        new_name: ListName,
+    },
+    RenamedSection {
+        section: Section,
+        new_name: Section,
+    },
+    ReviewedSuggestions(Vec<(Name, Section)>),
+    SearchResults(Vec<SearchMatch>),
+    SectionSuggestions(Vec<(Name, Section)>),
     Sections(Vec<Section>),
+    StoreLayout(StoreLayout),
+    Stores(Vec<StoreName>),
+    SwappedMeals(Vec<PlannedMeal>),
+    UncheckedListItem(Name),
+    UpdatedItem(Item),
+    UpdatedRecipe(Recipe, Ingredients),
+}
+
+impl ApiResponse {
+    /// Shows any amounts in the response in the units of `system`.
+    pub fn to_system(self, system: UnitSystem) -> Self {
+        match self {
+            Self::FetchedRecipe((recipe, ingredients)) => {
+                Self::FetchedRecipe((recipe, ingredients.to_system(system)))
+            }
+            Self::List(list) => Self::List(list.to_system(system)),
+            Self::Pantry(pantry) => Self::Pantry(
+                pantry
+                    .into_iter()
+                    .map(|item| item.to_system(system))
+                    .collect(),
+            ),
+            Self::RecipeIngredients(ingredients) => {
+                Self::RecipeIngredients(ingredients.map(|i| i.to_system(system)))
+            }
+            Self::UpdatedRecipe(recipe, ingredients) => {
+                Self::UpdatedRecipe(recipe, ingredients.to_system(system))
+            }
+            other => other,
+        }
+    }
+
+    /// The response as JSON, for the responses that can be shown that way.
+    pub fn to_json(&self) -> Result<Option<String>, serde_json::Error> {
+        let json = match self {
+            Self::Expiring(report) => serde_json::to_string_pretty(report)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(json))
+    }
 }
 
 impl Display for ApiResponse {
@@ -136,12 +265,35 @@ impl Display for ApiResponse {
         match self {
             Self::AddedChecklistItem(name) => writeln!(f, "\nchecklist item added: {name}"),
             Self::AddedItem(name) => writeln!(f, "\nitem added: {name}"),
+            Self::AddedItemAlias { item, alias } => {
+                writeln!(f, "\nalias added: {alias} -> {item}")
+            }
+            Self::AddedList(list) => writeln!(f, "\nlist added: {list}"),
             Self::AddedListItem(name) => writeln!(f, "\nitem added to list: {name}"),
             Self::AddedListRecipe(recipe) => {
                 writeln!(f, "\nrecipe added:\n{recipe}")?;
                 Ok(())
             }
+            Self::AddedListFromPlan {
+                list,
+                range,
+                recipes,
+            } => {
+                writeln!(
+                    f,
+                    "\nadded to list '{list}' from the meal plan for {range}:"
+                )?;
+                for recipe in recipes {
+                    writeln!(f, "  {recipe}")?;
+                }
+                Ok(())
+            }
+            Self::AddedPantryItem(item) => writeln!(f, "\nin the pantry: {item}"),
             Self::AddedRecipe(name) => writeln!(f, "\nrecipe added: {name}"),
+            Self::AddedSection(section) => writeln!(f, "\nsection added: {section}"),
+            Self::AddedStore(store) => writeln!(f, "\nstore added: {store}"),
+            Self::ArchivedList(list) => writeln!(f, "\nlist archived: {list}"),
+            Self::AuditedPantryItem(item) => writeln!(f, "\nin the pantry: {item}"),
             Self::Checklist(items) => {
                 writeln!(f, "\nchecklist:")?;
                 for item in items {
@@ -149,8 +301,71 @@ impl Display for ApiResponse {
                 }
                 Ok(())
             }
+            Self::CheckedListItem(name) => writeln!(f, "\nchecked off: {name}"),
+            Self::ClearedCheckedListItems(items) => {
+                writeln!(f, "\ncleared checked items:")?;
+                for item in items {
+                    writeln!(f, "{item}")?;
+                }
+                Ok(())
+            }
+            Self::ClearedChecklist => writeln!(f, "\nChecklist is now empty"),
+            Self::ClearedList(list) => writeln!(f, "\nList '{list}' is now empty"),
+            Self::ConsumedPantryItem { item, left } => {
+                writeln!(f, "\nused: {item}")?;
+                match left {
+                    Some(left) => writeln!(f, "left in the pantry: {left}"),
+                    None => writeln!(f, "none left in the pantry"),
+                }
+            }
             Self::DeletedChecklistItem(name) => writeln!(f, "\ndeleted from checklist: \n{name}"),
-            Self::DeletedRecipe(recipe) => writeln!(f, "\ndeleted recipe: \n{recipe}"),
+            Self::DeletedItem(name) => writeln!(f, "\ndeleted item: \n{name}"),
+            Self::DeletedItemAlias(alias) => writeln!(f, "\ndeleted alias: {alias}"),
+            Self::DeletedList(list) => writeln!(f, "\ndeleted list: {list}"),
+            Self::DeletedSection(section) => writeln!(f, "\ndeleted section: {section}"),
+            Self::DeletedStore(store) => writeln!(f, "\ndeleted store: {store}"),
+            Self::DuplicateItems(groups) if groups.is_empty() => {
+                writeln!(f, "\nno duplicate items")
+            }
+            Self::DuplicateItems(groups) => {
+                writeln!(f, "\npossible duplicates, each merged into the first:")?;
+                for group in groups {
+                    let names = group.iter().map(Name::as_str).collect::<Vec<_>>();
+                    writeln!(f, "  {}", names.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::DeletedListItem(name) => writeln!(f, "\ndeleted from list: \n{name}"),
+            Self::Expiring(report) => write!(f, "\n{report}"),
+            Self::DeletedPantryItem(name) => writeln!(f, "\ndeleted from pantry: {name}"),
+            Self::DeletedPlannedMeal(meal) => writeln!(f, "\ndeleted from the plan: {meal}"),
+            Self::DeletedRecipe {
+                recipe,
+                kept,
+                removed,
+                unplanned,
+            } => {
+                writeln!(f, "\ndeleted recipe: \n{recipe}")?;
+                if !removed.is_empty() {
+                    writeln!(f, "\nremoved items:")?;
+                    for item in removed {
+                        writeln!(f, "{item}")?;
+                    }
+                }
+                if !kept.is_empty() {
+                    writeln!(f, "\nkept items still in use:")?;
+                    for item in kept {
+                        writeln!(f, "{item}")?;
+                    }
+                }
+                if !unplanned.is_empty() {
+                    writeln!(f, "\nremoved from the meal plan:")?;
+                    for meal in unplanned {
+                        writeln!(f, "{meal}")?;
+                    }
+                }
+                Ok(())
+            }
             Self::Exported(items, list) => {
                 writeln!(f, "\nexported items:")?;
                 for item in items {
@@ -162,6 +377,13 @@ impl Display for ApiResponse {
                 }
                 Ok(())
             }
+            Self::ExportedMealPlan { plan, path } => writeln!(
+                f,
+                "\nexported {} meals from {} to {}",
+                plan.meals().len(),
+                plan.range(),
+                path.display()
+            ),
             Self::FetchedRecipe((recipe, ingredients)) => {
                 writeln!(f, "\n{recipe}:")?;
                 for ingredient in ingredients.iter() {
@@ -169,6 +391,17 @@ impl Display for ApiResponse {
                 }
                 Ok(())
             }
+            Self::Item(item) => {
+                writeln!(f)?;
+                write_item_details(f, item)
+            }
+            Self::ItemAliases(aliases) => {
+                writeln!(f)?;
+                for (alias, item) in aliases {
+                    writeln!(f, "{alias} -> {item}")?;
+                }
+                Ok(())
+            }
             Self::ItemAlreadyAdded(item) => writeln!(f, "\nitem already added: {item}"),
             Self::Items(items) => {
                 writeln!(f)?;
@@ -178,14 +411,47 @@ impl Display for ApiResponse {
                 Ok(())
             }
             Self::ImportToSqlite => writeln!(f, "\nImport successful"),
+            Self::ImportedMealPlan(plan) => write!(f, "\nimported {plan}"),
             Self::List(list) => {
+                if let Some(layout) = list.layout() {
+                    writeln!(f, "\nat {}:", layout.store())?;
+                }
+                if let Some(plan) = list.plan() {
+                    writeln!(f, "\nfrom the meal plan for {plan}")?;
+                }
+                for (heading, items) in list.groups() {
+                    writeln!(f, "\n{}:", heading.unwrap_or(UNSORTED))?;
+                    for item in items {
+                        writeln!(f, "  {item}")?;
+                    }
+                }
+                Ok(())
+            }
+            Self::Lists { active, archived } => {
                 writeln!(f)?;
-                for item in list.items() {
-                    writeln!(f, "{item}")?;
+                for list in active {
+                    writeln!(f, "{list}")?;
+                }
+                if !archived.is_empty() {
+                    writeln!(f, "\narchived:")?;
+                    for list in archived {
+                        writeln!(f, "{list}")?;
+                    }
                 }
                 Ok(())
             }
+            Self::MealPlan(plan) => write!(f, "\n{plan}"),
+            Self::MovedMeal { meal, from } => writeln!(f, "\nmoved from {from} to {meal}"),
             Self::NothingReturned(cmd) => writeln!(f, "\nnothing returned for command: {cmd:?}."),
+            Self::Pantry(pantry) if pantry.is_empty() => writeln!(f, "\nthe pantry is empty"),
+            Self::Pantry(pantry) => {
+                writeln!(f)?;
+                for item in pantry {
+                    writeln!(f, "{item}")?;
+                }
+                Ok(())
+            }
+            Self::PlannedMeal(meal) => writeln!(f, "\nplanned: {meal}"),
             Self::Recipes(recipes) => {
                 writeln!(f)?;
                 for recipe in recipes {
@@ -193,6 +459,16 @@ impl Display for ApiResponse {
                 }
                 Ok(())
             }
+            Self::RecipeCoverage(recipes) if recipes.is_empty() => {
+                writeln!(f, "\nno recipes use what you have")
+            }
+            Self::RecipeCoverage(recipes) => {
+                writeln!(f)?;
+                for recipe in recipes {
+                    writeln!(f, "{recipe}")?;
+                }
+                Ok(())
+            }
             Self::RecipeIngredients(ingredients) => {
                 if let Some(ingredients) = ingredients {
                     writeln!(f)?;
@@ -203,7 +479,63 @@ impl Display for ApiResponse {
                 Ok(())
             }
 
+            Self::MergedDuplicates(merged) => {
+                writeln!(f)?;
+                for (item, into) in merged {
+                    writeln!(f, "item merged: {item} -> {into}")?;
+                }
+                Ok(())
+            }
+            Self::MergedItems { item, into } => writeln!(f, "\nitem merged: {item} -> {into}"),
+            Self::MergedSection { section, into } => {
+                writeln!(f, "\nsection merged: {section} -> {into}")
+            }
             Self::RefreshList => writeln!(f, "\nList is now empty"),
+            Self::RenamedList { list, new_name } => {
+                writeln!(f, "\nlist renamed: {list} -> {new_name}")
+            }
+            Self::RenamedSection { section, new_name } => {
+                writeln!(f, "\nsection renamed: {section} -> {new_name}")
+            }
+            Self::ReviewedSuggestions(items) => {
+                writeln!(f)?;
+                for (item, section) in items {
+                    writeln!(f, "{item} is now in {section}")?;
+                }
+                Ok(())
+            }
+            Self::SectionSuggestions(suggestions) if suggestions.is_empty() => {
+                writeln!(f, "\nno sections waiting to be reviewed")
+            }
+            Self::SectionSuggestions(suggestions) => {
+                writeln!(f, "\nsuggested sections:")?;
+                for (item, section) in suggestions {
+                    writeln!(f, "  {item}: {section}")?;
+                }
+                Ok(())
+            }
+            Self::RecipeNotFound {
+                recipe,
+                suggestions,
+            } => {
+                write!(f, "\nrecipe not found: {recipe}")?;
+                if !suggestions.is_empty() {
+                    let suggestions = suggestions
+                        .iter()
+                        .map(|recipe| format!("'{recipe}'"))
+                        .collect::<Vec<_>>();
+                    write!(f, ", did you mean {}?", suggestions.join(" or "))?;
+                }
+                writeln!(f)
+            }
+            Self::SearchResults(matches) if matches.is_empty() => writeln!(f, "\nno matches"),
+            Self::SearchResults(matches) => {
+                writeln!(f)?;
+                for found in matches {
+                    writeln!(f, "{}: {}", found.kind(), found.name())?;
+                }
+                Ok(())
+            }
             Self::Sections(sections) => {
                 writeln!(f)?;
                 for section in sections {
@@ -211,8 +543,49 @@ impl Display for ApiResponse {
                 }
                 Ok(())
             }
+            Self::StoreLayout(layout) => write!(f, "\n{layout}"),
+            Self::Stores(stores) => {
+                writeln!(f)?;
+                for store in stores {
+                    writeln!(f, "{store}")?;
+                }
+                Ok(())
+            }
+            Self::SwappedMeals(meals) => {
+                writeln!(f, "\nswapped:")?;
+                for meal in meals {
+                    writeln!(f, "  {meal}")?;
+                }
+                Ok(())
+            }
+            Self::UncheckedListItem(name) => writeln!(f, "\nback on the list: {name}"),
+            Self::UpdatedItem(item) => {
+                writeln!(f, "\nitem updated:")?;
+                write_item_details(f, item)
+            }
+            Self::UpdatedRecipe(recipe, ingredients) => {
+                writeln!(f, "\nrecipe updated: {recipe}")?;
+                for ingredient in ingredients.iter() {
+                    writeln!(f, "{ingredient}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_item_details(f: &mut fmt::Formatter<'_>, item: &Item) -> fmt::Result {
+    writeln!(f, "{item}")?;
+    if let Some(section) = item.section() {
+        writeln!(f, "section: {section}")?;
+    }
+    if let Some(recipes) = item.recipes().filter(|recipes| !recipes.is_empty()) {
+        writeln!(f, "recipes:")?;
+        for recipe in recipes {
+            writeln!(f, "  {recipe}")?;
         }
     }
+    Ok(())
 }
 
 impl From<StoreResponse> for ApiResponse {
@@ -220,23 +593,101 @@ impl From<StoreResponse> for ApiResponse {
         match res {
             StoreResponse::AddedChecklistItem(item) => Self::AddedChecklistItem(item),
             StoreResponse::AddedItem(item) => Self::AddedItem(item),
+            StoreResponse::AddedItemAlias { item, alias } => Self::AddedItemAlias { item, alias },
+            StoreResponse::AddedList(list) => Self::AddedList(list),
             StoreResponse::AddedListItem(item) => Self::AddedListItem(item),
             StoreResponse::AddedListRecipe(item) => Self::AddedListRecipe(item),
+            StoreResponse::AddedListFromPlan {
+                list,
+                range,
+                recipes,
+            } => Self::AddedListFromPlan {
+                list,
+                range,
+                recipes,
+            },
+            StoreResponse::AddedPantryItem(item) => Self::AddedPantryItem(item),
             StoreResponse::AddedRecipe(item) => Self::AddedRecipe(item),
+            StoreResponse::AddedSection(section) => Self::AddedSection(section),
+            StoreResponse::AddedStore(store) => Self::AddedStore(store),
+            StoreResponse::ArchivedList(list) => Self::ArchivedList(list),
+            StoreResponse::AuditedPantryItem(item) => Self::AuditedPantryItem(item),
             StoreResponse::Checklist(item) => Self::Checklist(item),
-            StoreResponse::DeletedRecipe(item) => Self::DeletedRecipe(item),
+            StoreResponse::DeletedRecipe {
+                recipe,
+                kept,
+                removed,
+                unplanned,
+            } => Self::DeletedRecipe {
+                recipe,
+                kept,
+                removed,
+                unplanned,
+            },
+            StoreResponse::CheckedListItem(item) => Self::CheckedListItem(item),
+            StoreResponse::ClearedCheckedListItems(items) => Self::ClearedCheckedListItems(items),
+            StoreResponse::ClearedChecklist => Self::ClearedChecklist,
+            StoreResponse::ClearedList(list) => Self::ClearedList(list),
+            StoreResponse::ConsumedPantryItem { item, left } => {
+                Self::ConsumedPantryItem { item, left }
+            }
             StoreResponse::DeletedChecklistItem(item) => Self::DeletedChecklistItem(item),
+            StoreResponse::DeletedItem(item) => Self::DeletedItem(item),
+            StoreResponse::DeletedItemAlias(alias) => Self::DeletedItemAlias(alias),
+            StoreResponse::DeletedList(list) => Self::DeletedList(list),
+            StoreResponse::DeletedSection(section) => Self::DeletedSection(section),
+            StoreResponse::DeletedStore(store) => Self::DeletedStore(store),
+            StoreResponse::DuplicateItems(groups) => Self::DuplicateItems(groups),
+            StoreResponse::Expiring(report) => Self::Expiring(report),
+            StoreResponse::DeletedListItem(item) => Self::DeletedListItem(item),
+            StoreResponse::DeletedPantryItem(item) => Self::DeletedPantryItem(item),
+            StoreResponse::DeletedPlannedMeal(meal) => Self::DeletedPlannedMeal(meal),
             StoreResponse::Exported(items, list) => Self::Exported(items, list),
+            StoreResponse::ExportedMealPlan { plan, path } => Self::ExportedMealPlan { plan, path },
             StoreResponse::FetchedRecipe(item) => Self::FetchedRecipe(item),
+            StoreResponse::Item(item) => Self::Item(item),
+            StoreResponse::ItemAliases(aliases) => Self::ItemAliases(aliases),
             StoreResponse::ItemAlreadyAdded(item) => Self::ItemAlreadyAdded(item),
             StoreResponse::Items(item) => Self::Items(item),
             StoreResponse::ImportToSqlite => Self::ImportToSqlite,
+            StoreResponse::ImportedMealPlan(plan) => Self::ImportedMealPlan(plan),
             StoreResponse::List(item) => Self::List(item),
+            StoreResponse::Lists { active, archived } => Self::Lists { active, archived },
+            StoreResponse::MergedDuplicates(merged) => Self::MergedDuplicates(merged),
+            StoreResponse::MergedItems { item, into } => Self::MergedItems { item, into },
+            StoreResponse::MergedSection { section, into } => Self::MergedSection { section, into },
+            StoreResponse::MealPlan(plan) => Self::MealPlan(plan),
+            StoreResponse::MovedMeal { meal, from } => Self::MovedMeal { meal, from },
             StoreResponse::NothingReturned(item) => Self::NothingReturned(item),
+            StoreResponse::Pantry(pantry) => Self::Pantry(pantry),
+            StoreResponse::PlannedMeal(meal) => Self::PlannedMeal(meal),
             StoreResponse::Recipes(item) => Self::Recipes(item),
+            StoreResponse::RecipeCoverage(recipes) => Self::RecipeCoverage(recipes),
             StoreResponse::RecipeIngredients(item) => Self::RecipeIngredients(item),
+            StoreResponse::RecipeNotFound {
+                recipe,
+                suggestions,
+            } => Self::RecipeNotFound {
+                recipe,
+                suggestions,
+            },
             StoreResponse::RefreshList => Self::RefreshList,
+            StoreResponse::RenamedList { list, new_name } => Self::RenamedList { list, new_name },
+            StoreResponse::RenamedSection { section, new_name } => {
+                Self::RenamedSection { section, new_name }
+            }
+            StoreResponse::ReviewedSuggestions(items) => Self::ReviewedSuggestions(items),
+            StoreResponse::SectionSuggestions(suggestions) => Self::SectionSuggestions(suggestions),
+            StoreResponse::SearchResults(matches) => Self::SearchResults(matches),
             StoreResponse::Sections(item) => Self::Sections(item),
+            StoreResponse::StoreLayout(layout) => Self::StoreLayout(layout),
+            StoreResponse::Stores(stores) => Self::Stores(stores),
+            StoreResponse::SwappedMeals(meals) => Self::SwappedMeals(meals),
+            StoreResponse::UncheckedListItem(item) => Self::UncheckedListItem(item),
+            StoreResponse::UpdatedItem(item) => Self::UpdatedItem(item),
+            StoreResponse::UpdatedRecipe(recipe, ingredients) => {
+                Self::UpdatedRecipe(recipe, ingredients)
+            }
         }
     }
 }
@@ -252,7 +703,7 @@ mod tests {
         let api = Api::init(StoreType::SqliteInMem).await.unwrap();
 
         let response = api
-            .dispatch(ApiCommand::Add(Add::Recipe { recipe: Recipe::new("fluffy american pancakes"), ingredients: Ingredients::from_input_string("135g/4¾oz plain flour, 1 tsp baking powder, ½ tsp salt, 2 tbsp caster sugar, 130ml/4½fl oz milk, 1 large egg, lightly beaten, 2 tbsp melted butter (allowed to cool slightly), plus extra for cooking") }))
+            .dispatch(ApiCommand::Add(Add::Recipe { recipe: Recipe::new("fluffy american pancakes"), servings: None, ingredients: Ingredients::from_input_string("135g/4¾oz plain flour, 1 tsp baking powder, ½ tsp salt, 2 tbsp caster sugar, 130ml/4½fl oz milk, 1 large egg, lightly beaten, 2 tbsp melted butter (allowed to cool slightly), plus extra for cooking") }))
             .await
             .unwrap();
 
@@ -266,7 +717,7 @@ mod tests {
         insta::assert_display_snapshot!(response.to_string().trim(), @"fluffy american pancakes");
 
         let response = api
-            .dispatch(ApiCommand::Read(Read::Recipe(Recipe::new(
+            .dispatch(ApiCommand::Read(Read::recipe_from_name(Recipe::new(
                 "fluffy american pancakes",
             ))))
             .await
@@ -274,29 +725,25 @@ mod tests {
 
         insta::assert_display_snapshot!(response.to_string().trim(), @r###"
 
-        135g/4¾oz plain flour
+        135 g plain flour
         1 tsp baking powder
-        ½ tsp salt
+        0.5 tsp salt
         2 tbsp caster sugar
-        130ml/4½fl oz milk
-        1 large egg
-        lightly beaten
-        2 tbsp melted butter (allowed to cool slightly)
-        plus extra for cooking
+        130 ml milk
+        1 egg, large, lightly beaten
+        2 tbsp butter, melted, allowed to cool slightly, plus extra for cooking
         "###);
 
         let response = api.dispatch(ApiCommand::Read(Read::All)).await.unwrap();
 
         insta::assert_display_snapshot!(response.to_string().trim(), @r###"
-        135g/4¾oz plain flour
-        1 tsp baking powder
-        ½ tsp salt
-        2 tbsp caster sugar
-        130ml/4½fl oz milk
-        1 large egg
-        lightly beaten
-        2 tbsp melted butter (allowed to cool slightly)
-        plus extra for cooking
+        plain flour
+        baking powder
+        salt
+        caster sugar
+        milk
+        egg
+        butter
         "###);
 
         let response = api
@@ -309,6 +756,15 @@ mod tests {
         insta::assert_display_snapshot!(response.to_string().trim(), @r###"
         deleted recipe: 
         fluffy american pancakes
+
+        removed items:
+        plain flour
+        baking powder
+        salt
+        caster sugar
+        milk
+        egg
+        butter
         "###);
 
         let response = api.dispatch(ApiCommand::Read(Read::Recipes)).await.unwrap();
@@ -0,0 +1,450 @@
+//! Meal plans as [RFC 5545](https://www.rfc-editor.org/rfc/rfc5545) iCalendar
+//! files, so that planned meals can be shared with a household calendar and
+//! read back after they've been moved around there.
+
+use thiserror::Error;
+use time::{Month, OffsetDateTime};
+
+use crate::{
+    date::Date,
+    export::ICalendarSerializable,
+    plan::{DateRange, Meal, MealPlan, MealSlot, PlannedMeal},
+    recipes::{Ingredients, Recipe},
+};
+
+// Lines longer than this many bytes are folded onto the next line.
+const LINE_LIMIT: usize = 75;
+
+// The days a calendar was exported for, so that meals taken off the calendar
+// can be taken off the plan when it's read back.
+const RANGE_PROPERTY: &str = "X-GUST-RANGE";
+
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    #[error("invalid date in calendar: {0}")]
+    InvalidDate(String),
+
+    #[error("event without a {0}")]
+    MissingProperty(&'static str),
+
+    #[error("no meals in calendar")]
+    NoMeals,
+
+    #[error("not an iCalendar file")]
+    NotACalendar,
+}
+
+/// The meals planned over a range of days, each with the ingredients of its
+/// recipe, ready to be written as an iCalendar file.
+#[derive(Debug, Clone)]
+pub struct MealCalendar {
+    range: DateRange,
+    meals: Vec<(PlannedMeal, Ingredients)>,
+    stamp: OffsetDateTime,
+}
+
+impl MealCalendar {
+    pub fn new(
+        range: DateRange,
+        meals: impl IntoIterator<Item = (PlannedMeal, Ingredients)>,
+    ) -> Self {
+        Self {
+            range,
+            meals: meals.into_iter().collect(),
+            stamp: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Sets when the calendar was made, which is otherwise now.
+    pub fn with_stamp(mut self, stamp: OffsetDateTime) -> Self {
+        self.stamp = stamp;
+        self
+    }
+}
+
+impl ICalendarSerializable for MealCalendar {
+    /// Writes the calendar with an event for each meal.
+    ///
+    /// Meals are timed events in the calendar's own time zone, with the
+    /// recipe's ingredients in the description. Each event's UID is that of
+    /// the meal it was exported from, so calendars can tell a re-exported
+    /// meal from a new one.
+    fn to_icalendar(&self) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//gust//meal plan//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            format!(
+                "{RANGE_PROPERTY}:{}/{}",
+                format_date(self.range.start()),
+                format_date(self.range.end())
+            ),
+        ];
+        for (meal, ingredients) in &self.meals {
+            let slot = meal.slot();
+            let (start, end) = meal_hours(slot.meal());
+            let date = format_date(slot.date());
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{date}-{}@gust", slot.meal()),
+                format!("DTSTAMP:{}", format_stamp(self.stamp)),
+                format!("DTSTART:{date}T{start:02}0000"),
+                format!("DTEND:{date}T{end:02}0000"),
+                format!(
+                    "SUMMARY:{}",
+                    escape(&format!("{}: {}", capitalize(slot.meal()), meal.recipe()))
+                ),
+            ]);
+            let ingredients = ingredients
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            if !ingredients.is_empty() {
+                lines.push(format!("DESCRIPTION:{}", escape(&ingredients.join("\n"))));
+            }
+            lines.push("END:VEVENT".to_string());
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        lines.iter().map(|line| fold(line) + "\r\n").collect()
+    }
+}
+
+/// Reads the meals out of an iCalendar file.
+///
+/// Each event's day is the meal's day. The meal is read from the time the
+/// event starts, so an event moved to midday becomes lunch, or from the start
+/// of its summary for an all-day event, and the recipe from the rest of the
+/// summary. Descriptions are ignored, since ingredients belong to recipes.
+///
+/// The plan covers the days the calendar was exported for, along with any
+/// days its events have since been moved to.
+pub fn parse_meal_plan(calendar: &str) -> Result<MealPlan, CalendarError> {
+    let lines = unfold(calendar);
+    if lines.first().map(|line| line.trim()) != Some("BEGIN:VCALENDAR") {
+        return Err(CalendarError::NotACalendar);
+    }
+
+    let mut range = None;
+    let mut meals = Vec::new();
+    let mut event: Option<Vec<(String, String)>> = None;
+    for line in &lines {
+        let Some((name, value)) = split_property(line) else {
+            continue;
+        };
+        match (name.as_str(), value.as_str(), event.as_mut()) {
+            ("BEGIN", "VEVENT", _) => event = Some(Vec::new()),
+            ("END", "VEVENT", Some(_)) => {
+                meals.push(read_event(&event.take().unwrap_or_default())?);
+            }
+            (_, _, Some(properties)) => properties.push((name, value)),
+            (RANGE_PROPERTY, value, None) => {
+                let (start, end) = value
+                    .split_once('/')
+                    .ok_or_else(|| CalendarError::InvalidDate(value.to_string()))?;
+                range = Some(DateRange::new(parse_date(start)?, parse_date(end)?));
+            }
+            _ => {}
+        }
+    }
+
+    let dates = meals.iter().map(|meal: &PlannedMeal| meal.slot().date());
+    let (first, last) = match (dates.clone().min(), dates.max(), range) {
+        (Some(first), Some(last), Some(range)) => (first.min(range.start()), last.max(range.end())),
+        (Some(first), Some(last), None) => (first, last),
+        (None, None, Some(range)) => (range.start(), range.end()),
+        _ => return Err(CalendarError::NoMeals),
+    };
+    Ok(MealPlan::new(DateRange::new(first, last), meals))
+}
+
+fn read_event(properties: &[(String, String)]) -> Result<PlannedMeal, CalendarError> {
+    let property = |name: &'static str| {
+        properties
+            .iter()
+            .find(|(property, _)| property == name)
+            .map(|(_, value)| value.as_str())
+            .ok_or(CalendarError::MissingProperty(name))
+    };
+
+    let start = property("DTSTART")?;
+    let date = parse_date(start)?;
+    let summary = unescape(property("SUMMARY")?);
+    let (named_meal, recipe) = match summary.split_once(':') {
+        Some((meal, recipe)) => match meal.parse::<Meal>() {
+            Ok(meal) => (Some(meal), recipe.trim()),
+            Err(_) => (None, summary.trim()),
+        },
+        None => (None, summary.trim()),
+    };
+    let meal = start_hour(start)
+        .map(meal_at)
+        .or(named_meal)
+        .unwrap_or(Meal::Dinner);
+
+    Ok(PlannedMeal::new(
+        MealSlot::new(date, meal),
+        Recipe::new(recipe),
+    ))
+}
+
+// When each meal is shown in the calendar, as hours of the day.
+fn meal_hours(meal: Meal) -> (u8, u8) {
+    match meal {
+        Meal::Breakfast => (8, 9),
+        Meal::Lunch => (12, 13),
+        Meal::Dinner => (18, 19),
+    }
+}
+
+// The meal an event starting at `hour` is taken to be.
+fn meal_at(hour: u8) -> Meal {
+    match hour {
+        0..=10 => Meal::Breakfast,
+        11..=15 => Meal::Lunch,
+        _ => Meal::Dinner,
+    }
+}
+
+fn capitalize(meal: Meal) -> String {
+    let mut chars = meal.as_str().chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn format_date(date: Date) -> String {
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+fn format_stamp(stamp: OffsetDateTime) -> String {
+    let stamp = stamp.to_offset(time::UtcOffset::UTC);
+    format!(
+        "{}T{:02}{:02}{:02}Z",
+        format_date(stamp.date()),
+        stamp.hour(),
+        stamp.minute(),
+        stamp.second()
+    )
+}
+
+// Reads the date from a DATE or DATE-TIME value, such as `20261020` or
+// `20261020T180000Z`.
+fn parse_date(value: &str) -> Result<Date, CalendarError> {
+    let invalid = || CalendarError::InvalidDate(value.to_string());
+    let digits = value.get(..8).ok_or_else(invalid)?;
+    let number = |range: std::ops::Range<usize>| {
+        digits
+            .get(range)
+            .and_then(|digits| digits.parse::<u16>().ok())
+            .ok_or_else(invalid)
+    };
+    let month = Month::try_from(number(4..6)? as u8).map_err(|_| invalid())?;
+    Date::from_calendar_date(number(0..4)?.into(), month, number(6..8)? as u8)
+        .map_err(|_| invalid())
+}
+
+fn start_hour(value: &str) -> Option<u8> {
+    let (_, time) = value.split_once('T')?;
+    time.get(..2)?.parse().ok()
+}
+
+// Splits a content line into its upper-cased name and its value, dropping
+// any parameters such as `;TZID=Europe/London`.
+fn split_property(line: &str) -> Option<(String, String)> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let (head, value) = line.split_at(colon);
+    let name = head.split(';').next().unwrap_or(head);
+    Some((name.trim().to_uppercase(), value[1..].to_string()))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => unescaped.push('\n'),
+                Some(c) => unescaped.push(c),
+                None => {}
+            },
+            (c, false) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+// Breaks a line into lines of at most `LINE_LIMIT` bytes, continuing each on
+// the next after a space, without splitting a character.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > LINE_LIMIT {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+// Joins folded lines back together, accepting files saved with bare line
+// feeds as well as CRLF.
+fn unfold(calendar: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in calendar.lines() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continued), Some(last)) => last.push_str(continued),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> Date {
+        crate::date::parse_date(date).unwrap()
+    }
+
+    fn calendar() -> MealCalendar {
+        let meal = |day: &str, meal, recipe: &str, ingredients: &str| {
+            (
+                PlannedMeal::new(MealSlot::new(date(day), meal), Recipe::new(recipe)),
+                Ingredients::from_input_string(ingredients),
+            )
+        };
+        MealCalendar::new(
+            DateRange::week_of(date("2026-10-19")),
+            [
+                meal(
+                    "2026-10-20",
+                    Meal::Dinner,
+                    "crispy sheet-pan noodles",
+                    "2 cloves garlic, 400g tofu, 1 tbsp soy sauce, 2 tbsp hoisin, 1 tbsp maple syrup",
+                ),
+                meal("2026-10-21", Meal::Lunch, "hummus; and pitta", ""),
+            ],
+        )
+        .with_stamp(
+            date("2026-10-18")
+                .with_hms(9, 30, 0)
+                .unwrap()
+                .assume_utc(),
+        )
+    }
+
+    #[test]
+    fn test_to_icalendar() {
+        let ics = calendar().to_icalendar();
+        assert!(ics.split_terminator("\r\n").all(|line| line.len() <= 75));
+        insta::assert_snapshot!(ics.replace("\r\n", "\n"), @r###"
+        BEGIN:VCALENDAR
+        VERSION:2.0
+        PRODID:-//gust//meal plan//EN
+        CALSCALE:GREGORIAN
+        X-GUST-RANGE:20261019/20261025
+        BEGIN:VEVENT
+        UID:20261020-dinner@gust
+        DTSTAMP:20261018T093000Z
+        DTSTART:20261020T180000
+        DTEND:20261020T190000
+        SUMMARY:Dinner: crispy sheet-pan noodles
+        DESCRIPTION:2 clove garlic\n400 g tofu\n1 tbsp soy sauce\n2 tbsp hoisin\n1 
+         tbsp maple syrup
+        END:VEVENT
+        BEGIN:VEVENT
+        UID:20261021-lunch@gust
+        DTSTAMP:20261018T093000Z
+        DTSTART:20261021T120000
+        DTEND:20261021T130000
+        SUMMARY:Lunch: hummus\; and pitta
+        END:VEVENT
+        END:VCALENDAR
+        "###);
+    }
+
+    #[test]
+    fn test_parse_meal_plan() {
+        let plan = parse_meal_plan(&calendar().to_icalendar()).unwrap();
+        insta::assert_snapshot!(plan.to_string(), @r###"
+        plan for 2026-10-19 to 2026-10-25:
+        Monday 2026-10-19: nothing planned
+        Tuesday 2026-10-20:
+          dinner: crispy sheet-pan noodles
+        Wednesday 2026-10-21:
+          lunch: hummus; and pitta
+        Thursday 2026-10-22: nothing planned
+        Friday 2026-10-23: nothing planned
+        Saturday 2026-10-24: nothing planned
+        Sunday 2026-10-25: nothing planned
+        "###);
+
+        // Moved to lunchtime on the Monday after, and saved by a calendar that
+        // ends lines with bare line feeds.
+        let edited = calendar()
+            .to_icalendar()
+            .replace(
+                "DTSTART:20261020T180000",
+                "DTSTART;TZID=Europe/London:20261026T123000",
+            )
+            .replace("\r\n", "\n");
+        let plan = parse_meal_plan(&edited).unwrap();
+        assert_eq!(
+            plan.range(),
+            DateRange::new(date("2026-10-19"), date("2026-10-26"))
+        );
+        insta::assert_snapshot!(
+            plan.meals().iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"),
+            @r###"
+        Wednesday 2026-10-21 lunch: hummus; and pitta
+        Monday 2026-10-26 lunch: crispy sheet-pan noodles
+        "###
+        );
+
+        assert!(matches!(
+            parse_meal_plan("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n"),
+            Err(CalendarError::NoMeals)
+        ));
+        assert!(matches!(
+            parse_meal_plan("hello"),
+            Err(CalendarError::NotACalendar)
+        ));
+    }
+}
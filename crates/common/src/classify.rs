@@ -0,0 +1,203 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{item::Name, section::Section};
+
+// Words that place an item in one of the default sections. Freezer words are
+// checked first so that "frozen peas" isn't taken for fresh peas.
+const KEYWORDS: [(&str, &[&str]); 5] = [
+    ("freezer", &["frozen", "ice cream", "ice cubes"]),
+    (
+        "protein",
+        &[
+            "bacon", "beef", "chicken", "chorizo", "cod", "duck", "fish", "ham", "lamb", "mince",
+            "pork", "prawn", "salmon", "sausage", "shrimp", "steak", "tempeh", "tofu", "tuna",
+            "turkey",
+        ],
+    ),
+    (
+        "dairy",
+        &[
+            "butter",
+            "cheddar",
+            "cheese",
+            "cream",
+            "crème fraîche",
+            "egg",
+            "feta",
+            "milk",
+            "mozzarella",
+            "parmesan",
+            "yoghurt",
+            "yogurt",
+        ],
+    ),
+    (
+        "fresh",
+        &[
+            "apple",
+            "aubergine",
+            "avocado",
+            "banana",
+            "basil",
+            "broccoli",
+            "cabbage",
+            "carrot",
+            "celery",
+            "chilli",
+            "coriander",
+            "courgette",
+            "cucumber",
+            "garlic",
+            "ginger",
+            "leek",
+            "lemon",
+            "lettuce",
+            "lime",
+            "mint",
+            "mushroom",
+            "onion",
+            "parsley",
+            "pea",
+            "potato",
+            "rocket",
+            "scallion",
+            "shallot",
+            "spinach",
+            "thyme",
+            "tomato",
+        ],
+    ),
+    (
+        "pantry",
+        &[
+            "baking", "bean", "chickpea", "cinnamon", "cumin", "flour", "honey", "lentil",
+            "mustard", "noodle", "oats", "oil", "paprika", "pasta", "pepper", "rice", "salt",
+            "sauce", "spice", "stock", "sugar", "vinegar", "yeast",
+        ],
+    ),
+];
+
+/// Suggests a section for an item that doesn't have one.
+///
+/// The sections given to items that end in the same word, such as "red
+/// onion" for "white onion", are preferred. Otherwise the item is matched
+/// against keywords for the default sections, reading the name from its last
+/// word back so that "chicken stock" goes with the stock. Only sections
+/// passed to `new` are ever suggested.
+#[derive(Debug, Default)]
+pub struct SectionClassifier {
+    sections: Vec<Section>,
+    assignments: HashMap<String, HashMap<Section, usize>>,
+}
+
+impl SectionClassifier {
+    pub fn new(sections: Vec<Section>) -> Self {
+        Self {
+            sections,
+            ..Default::default()
+        }
+    }
+
+    /// Learns from the sections already given to items.
+    pub fn with_assignments(
+        mut self,
+        assignments: impl IntoIterator<Item = (Name, Section)>,
+    ) -> Self {
+        for (item, section) in assignments {
+            if let Some(head) = head_word(item.as_str()) {
+                *self
+                    .assignments
+                    .entry(head.to_string())
+                    .or_default()
+                    .entry(section)
+                    .or_default() += 1;
+            }
+        }
+        self
+    }
+
+    pub fn suggest(&self, item: &Name) -> Option<Section> {
+        self.learned(item).or_else(|| self.by_keyword(item))
+    }
+
+    fn learned(&self, item: &Name) -> Option<Section> {
+        self.assignments
+            .get(head_word(item.as_str())?)?
+            .iter()
+            .filter(|(section, _)| self.sections.contains(section))
+            .max_by_key(|&(section, count)| (*count, Reverse(section)))
+            .map(|(section, _)| section.clone())
+    }
+
+    fn by_keyword(&self, item: &Name) -> Option<Section> {
+        let name = item.as_str();
+        let available = || {
+            KEYWORDS
+                .iter()
+                .map(|(section, keywords)| (Section::from(*section), *keywords))
+                .filter(|(section, _)| self.sections.contains(section))
+        };
+
+        let (freezer, frozen_words) = KEYWORDS[0];
+        if frozen_words.iter().any(|word| name.contains(word)) {
+            if let Some((section, _)) = available().find(|(s, _)| s.as_str() == freezer) {
+                return Some(section);
+            }
+        }
+
+        name.split_whitespace().rev().find_map(|word| {
+            available()
+                .find(|(_, keywords)| keywords.iter().any(|keyword| word.starts_with(keyword)))
+                .map(|(section, _)| section)
+        })
+    }
+}
+
+fn head_word(name: &str) -> Option<&str> {
+    name.split_whitespace().last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section::SECTIONS;
+
+    #[test]
+    fn test_suggest_section() {
+        let classifier = SectionClassifier::new(SECTIONS.map(Section::from).to_vec())
+            .with_assignments([(Name::from("red onion"), Section::from("pantry"))]);
+
+        let suggest = |item: &str| {
+            let section = classifier.suggest(&Name::from(item));
+            format!(
+                "{item}: {}",
+                section.as_ref().map_or("none", Section::as_str)
+            )
+        };
+
+        insta::assert_snapshot!(
+            [
+                "chicken stock",
+                "frozen peas",
+                "peas",
+                "free-range eggs",
+                "chicken thighs",
+                "white onion",
+                "lemongrass paste",
+                "kitchen roll",
+            ]
+            .map(suggest)
+            .join("\n"),
+            @r###"
+        chicken stock: pantry
+        frozen peas: freezer
+        peas: fresh
+        free-range eggs: dairy
+        chicken thighs: protein
+        white onion: pantry
+        lemongrass paste: fresh
+        kitchen roll: none
+        "###
+        );
+    }
+}
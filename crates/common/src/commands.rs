@@ -1,8 +1,16 @@
+use std::path::PathBuf;
+
 use url::Url;
 
 use crate::{
+    date::Date,
+    ingredient::{Ingredient, Measure},
     item::Name,
-    recipes::{Ingredients, Recipe},
+    layout::{Aisle, StoreName},
+    list::ListName,
+    plan::{DateRange, MealSlot},
+    query::Query,
+    recipes::{Ingredients, Recipe, Servings},
     section::Section,
 };
 
@@ -11,24 +19,91 @@ pub enum ApiCommand {
     Add(Add),
     Delete(Delete),
     Export,
+    /// Writes the meals planned over `range` to an iCalendar file.
+    ExportMealPlan {
+        range: DateRange,
+        path: PathBuf,
+    },
     FetchRecipe(Url),
+    /// Finds recipes and items through the full-text search index.
+    FullTextSearch(Query),
     ImportFromJson,
+    /// Replaces the plan over the days an iCalendar file covers with the
+    /// meals in it.
+    ImportMealPlan(PathBuf),
     Read(Read),
+    /// Finds items, recipes and sections by name, allowing for misspellings.
+    Search(String),
     Update(Update),
 }
 
+/// The items to hand when looking for something to cook.
+#[derive(Debug, Clone)]
+pub enum Available {
+    Checklist,
+    Items(Vec<Name>),
+    /// Whatever is stocked in the pantry.
+    Pantry,
+}
+
 #[derive(Debug)]
 pub enum Add {
     ChecklistItem(Name),
+    /// Adds an item, refusing sections that don't exist yet unless
+    /// `create_section` is set.
     Item {
         name: Name,
         section: Option<Section>,
+        create_section: bool,
+    },
+    /// Adds another name that `item` goes by.
+    ItemAlias {
+        item: Name,
+        alias: Name,
+    },
+    /// Creates an empty list.
+    List(ListName),
+    /// Creates a list holding a copy of another's items and recipes.
+    CopyList {
+        list: ListName,
+        new_name: ListName,
+    },
+    ListItem {
+        list: ListName,
+        item: Name,
+    },
+    ListRecipe {
+        list: ListName,
+        recipe: Recipe,
+        servings: Option<Servings>,
+    },
+    /// Adds the recipes planned over `range` to a list, noting the list was
+    /// made from the plan.
+    ListFromPlan {
+        list: ListName,
+        range: DateRange,
+    },
+    /// Adds to what's in the pantry.
+    PantryItem {
+        item: Name,
+        measure: Option<Measure>,
+        best_before: Option<Date>,
+    },
+    /// Plans a recipe for a meal, replacing whatever was planned for it.
+    PlannedMeal {
+        slot: MealSlot,
+        recipe: Recipe,
     },
-    ListItem(Name),
-    ListRecipe(Recipe),
     Recipe {
         recipe: Recipe,
         ingredients: Ingredients,
+        servings: Option<Servings>,
+    },
+    Section(Section),
+    /// Adds a store along with its aisles in the order they're walked.
+    Store {
+        store: StoreName,
+        aisles: Vec<Aisle>,
     },
 }
 
@@ -38,21 +113,92 @@ impl Add {
     }
 
     pub fn item_from_name_and_section(name: Name, section: Option<Section>) -> Self {
-        Self::Item { name, section }
+        Self::Item {
+            name,
+            section,
+            create_section: false,
+        }
+    }
+
+    /// Adds an item to a section, creating the section if it's new.
+    pub fn item_with_new_section(name: Name, section: Section) -> Self {
+        Self::Item {
+            name,
+            section: Some(section),
+            create_section: true,
+        }
+    }
+
+    pub fn item_alias(item: Name, alias: Name) -> Self {
+        Self::ItemAlias { item, alias }
+    }
+
+    pub fn section_from_name(section: Section) -> Self {
+        Self::Section(section)
+    }
+
+    pub fn list_from_name(list: ListName) -> Self {
+        Self::List(list)
+    }
+
+    pub fn copy_list(list: ListName, new_name: ListName) -> Self {
+        Self::CopyList { list, new_name }
     }
 
-    pub fn list_item_from_name(name: Name) -> Self {
-        Self::ListItem(name)
+    pub fn list_item_from_name(list: ListName, item: Name) -> Self {
+        Self::ListItem { list, item }
     }
 
-    pub fn list_recipe_from_name(name: Recipe) -> Self {
-        Self::ListRecipe(name)
+    pub fn list_recipe_from_name(list: ListName, recipe: Recipe) -> Self {
+        Self::list_recipe_for_servings(list, recipe, None)
+    }
+
+    /// Adds a recipe's ingredients to the list, scaled to feed `servings`.
+    pub fn list_recipe_for_servings(
+        list: ListName,
+        recipe: Recipe,
+        servings: Option<Servings>,
+    ) -> Self {
+        Self::ListRecipe {
+            list,
+            recipe,
+            servings,
+        }
     }
 
     pub fn recipe_from_name_and_ingredients(recipe: Recipe, ingredients: Ingredients) -> Self {
+        Self::recipe_with_servings(recipe, ingredients, None)
+    }
+
+    pub fn store_with_aisles(store: StoreName, aisles: Vec<Aisle>) -> Self {
+        Self::Store { store, aisles }
+    }
+
+    pub fn list_from_plan(list: ListName, range: DateRange) -> Self {
+        Self::ListFromPlan { list, range }
+    }
+
+    pub fn planned_meal(slot: MealSlot, recipe: Recipe) -> Self {
+        Self::PlannedMeal { slot, recipe }
+    }
+
+    pub fn pantry_item(item: Name, measure: Option<Measure>, best_before: Option<Date>) -> Self {
+        Self::PantryItem {
+            item,
+            measure,
+            best_before,
+        }
+    }
+
+    pub fn recipe_with_servings(
+        recipe: Recipe,
+        ingredients: Ingredients,
+        servings: Option<Servings>,
+    ) -> Self {
         Self::Recipe {
             recipe,
             ingredients,
+            servings,
         }
     }
 }
@@ -61,10 +207,22 @@ impl Add {
 pub enum Delete {
     ChecklistItem(Name),
     ClearChecklist,
-    ClearList,
+    ClearList(ListName),
+    /// Removes the items already checked off a list.
+    ClearCheckedListItems(ListName),
     Item(Name),
-    ListItem(Name),
+    ItemAlias(Name),
+    List(ListName),
+    ListItem {
+        list: ListName,
+        item: Name,
+    },
+    PantryItem(Name),
+    PlannedMeal(MealSlot),
     Recipe(Recipe),
+    /// Deletes a section, leaving its items without one.
+    Section(Section),
+    Store(StoreName),
 }
 
 impl Delete {
@@ -72,6 +230,14 @@ impl Delete {
         Self::Item(name)
     }
 
+    pub fn list_from_name(list: ListName) -> Self {
+        Self::List(list)
+    }
+
+    pub fn list_item_from_name(list: ListName, item: Name) -> Self {
+        Self::ListItem { list, item }
+    }
+
     pub fn recipe_from_name(name: Recipe) -> Self {
         Self::Recipe(name)
     }
@@ -81,12 +247,44 @@ impl Delete {
 pub enum Read {
     All,
     Checklist,
+    /// Recipes using any of the items available, those needing the fewest
+    /// other ingredients first.
+    CookableRecipes(Available),
+    Pantry,
+    /// Groups of items whose names are spelling variants of each other.
+    DuplicateItems,
+    /// Pantry items best before `within_days` from `today`, with recipes that
+    /// use them.
+    Expiring {
+        today: Date,
+        within_days: u32,
+    },
     Item(Name),
-    List,
-    ListRecipes,
-    Recipe(Recipe),
+    /// Every alias along with the item it stands for.
+    ItemAliases,
+    List(ListName),
+    /// A list ordered by the aisles of a store.
+    ListForStore {
+        list: ListName,
+        store: StoreName,
+    },
+    ListRecipes(ListName),
+    /// A list with only the items not yet checked off.
+    RemainingListItems(ListName),
+    Lists,
+    /// The meals planned over a range of days.
+    MealPlan(DateRange),
+    Recipe {
+        recipe: Recipe,
+        servings: Option<Servings>,
+    },
     Recipes,
+    /// Sections suggested for new items that are waiting to be reviewed.
+    SectionSuggestions,
     Sections,
+    /// A store's aisles and what's found in them.
+    Store(StoreName),
+    Stores,
 }
 
 impl Read {
@@ -95,23 +293,247 @@ impl Read {
     }
 
     pub fn recipe_from_name(name: Recipe) -> Self {
-        Self::Recipe(name)
+        Self::recipe_for_servings(name, None)
+    }
+
+    /// Reads a recipe's ingredients, scaled to feed `servings`.
+    pub fn recipe_for_servings(recipe: Recipe, servings: Option<Servings>) -> Self {
+        Self::Recipe { recipe, servings }
+    }
+
+    pub fn expiring(today: Date, within_days: u32) -> Self {
+        Self::Expiring { today, within_days }
     }
 }
 
 #[derive(Debug)]
 pub enum Update {
-    Item(Name),
-    RefreshList,
-    Recipe(Recipe),
+    /// Adds an ingredient to a recipe, replacing any existing amount of it.
+    AddIngredient {
+        recipe: Recipe,
+        ingredient: Ingredient,
+    },
+    DeleteIngredient {
+        recipe: Recipe,
+        ingredient: Name,
+    },
+    /// Replaces one of a recipe's ingredients with another.
+    EditIngredient {
+        recipe: Recipe,
+        ingredient: Name,
+        replacement: Ingredient,
+    },
+    /// Renames an item and/or moves it to another section.
+    Item {
+        name: Name,
+        new_name: Option<Name>,
+        section: Option<Section>,
+    },
+    /// Folds `item` into `into`, moving its recipes, section and list
+    /// entries across and keeping its name as an alias.
+    MergeItems {
+        item: Name,
+        into: Name,
+    },
+    /// Merges every group of duplicate items into its oldest item.
+    MergeDuplicates,
+    /// Takes an amount of an item out of the pantry, or all of it.
+    ConsumePantryItem {
+        item: Name,
+        measure: Option<Measure>,
+    },
+    /// Corrects the pantry to hold what's actually there of an item.
+    AuditPantryItem {
+        item: Name,
+        measure: Option<Measure>,
+        best_before: Option<Date>,
+    },
+    /// Moves a planned meal to a slot with nothing planned for it.
+    MoveMeal {
+        from: MealSlot,
+        to: MealSlot,
+    },
+    /// Swaps the recipes planned for two meals, either of which can be empty.
+    SwapMeals(MealSlot, MealSlot),
+    /// Archives a list, keeping it but no longer allowing changes to it.
+    ArchiveList(ListName),
+    /// Marks an item on a list as in the cart, or not.
+    CheckListItem {
+        list: ListName,
+        item: Name,
+        checked: bool,
+    },
+    RefreshList(ListName),
+    RenameList {
+        list: ListName,
+        new_name: ListName,
+    },
+    RenameRecipe {
+        recipe: Recipe,
+        new_name: Recipe,
+    },
+    /// Moves everything in `section` into `into` and deletes `section`.
+    MergeSection {
+        section: Section,
+        into: Section,
+    },
+    RenameSection {
+        section: Section,
+        new_name: Section,
+    },
+    /// Sets the order sections are shown in on the list.
+    SectionOrder(Vec<Section>),
+    /// Accepts every pending section suggestion.
+    ConfirmSuggestions,
+    /// Accepts the section suggested for an item, or gives it `section`
+    /// instead.
+    ReviewSuggestion {
+        item: Name,
+        section: Option<Section>,
+    },
+    /// Replaces a store's aisles with `aisles`, in the order they're walked.
+    StoreAisles {
+        store: StoreName,
+        aisles: Vec<Aisle>,
+    },
+    /// Places a section in one of a store's aisles.
+    SectionAisle {
+        store: StoreName,
+        section: Section,
+        aisle: Aisle,
+    },
+    /// Places an item in one of a store's aisles, overriding its section's.
+    ItemAisle {
+        store: StoreName,
+        item: Name,
+        aisle: Aisle,
+    },
 }
 
 impl Update {
-    pub fn refresh_list() -> Self {
-        Self::RefreshList
+    pub fn add_ingredient(recipe: Recipe, ingredient: Ingredient) -> Self {
+        Self::AddIngredient { recipe, ingredient }
     }
 
-    pub fn recipe_from_name(name: Recipe) -> Self {
-        Self::Recipe(name)
+    pub fn delete_ingredient(recipe: Recipe, ingredient: Name) -> Self {
+        Self::DeleteIngredient { recipe, ingredient }
+    }
+
+    pub fn edit_ingredient(recipe: Recipe, ingredient: Name, replacement: Ingredient) -> Self {
+        Self::EditIngredient {
+            recipe,
+            ingredient,
+            replacement,
+        }
+    }
+
+    pub fn merge_items(item: Name, into: Name) -> Self {
+        Self::MergeItems { item, into }
+    }
+
+    pub fn consume_pantry_item(item: Name, measure: Option<Measure>) -> Self {
+        Self::ConsumePantryItem { item, measure }
+    }
+
+    pub fn audit_pantry_item(
+        item: Name,
+        measure: Option<Measure>,
+        best_before: Option<Date>,
+    ) -> Self {
+        Self::AuditPantryItem {
+            item,
+            measure,
+            best_before,
+        }
+    }
+
+    pub fn move_meal(from: MealSlot, to: MealSlot) -> Self {
+        Self::MoveMeal { from, to }
+    }
+
+    pub fn swap_meals(slot: MealSlot, other: MealSlot) -> Self {
+        Self::SwapMeals(slot, other)
+    }
+
+    pub fn store_aisles(store: StoreName, aisles: Vec<Aisle>) -> Self {
+        Self::StoreAisles { store, aisles }
+    }
+
+    pub fn section_aisle(store: StoreName, section: Section, aisle: Aisle) -> Self {
+        Self::SectionAisle {
+            store,
+            section,
+            aisle,
+        }
+    }
+
+    pub fn item_aisle(store: StoreName, item: Name, aisle: Aisle) -> Self {
+        Self::ItemAisle { store, item, aisle }
+    }
+
+    pub fn merge_section(section: Section, into: Section) -> Self {
+        Self::MergeSection { section, into }
+    }
+
+    pub fn rename_section(section: Section, new_name: Section) -> Self {
+        Self::RenameSection { section, new_name }
+    }
+
+    pub fn section_order(order: Vec<Section>) -> Self {
+        Self::SectionOrder(order)
+    }
+
+    pub fn confirm_suggestion(item: Name) -> Self {
+        Self::ReviewSuggestion {
+            item,
+            section: None,
+        }
+    }
+
+    pub fn correct_suggestion(item: Name, section: Section) -> Self {
+        Self::ReviewSuggestion {
+            item,
+            section: Some(section),
+        }
+    }
+
+    pub fn item(name: Name, new_name: Option<Name>, section: Option<Section>) -> Self {
+        Self::Item {
+            name,
+            new_name,
+            section,
+        }
+    }
+
+    pub fn check_list_item(list: ListName, item: Name) -> Self {
+        Self::CheckListItem {
+            list,
+            item,
+            checked: true,
+        }
+    }
+
+    pub fn uncheck_list_item(list: ListName, item: Name) -> Self {
+        Self::CheckListItem {
+            list,
+            item,
+            checked: false,
+        }
+    }
+
+    pub fn archive_list(list: ListName) -> Self {
+        Self::ArchiveList(list)
+    }
+
+    pub fn refresh_list(list: ListName) -> Self {
+        Self::RefreshList(list)
+    }
+
+    pub fn rename_list(list: ListName, new_name: ListName) -> Self {
+        Self::RenameList { list, new_name }
+    }
+
+    pub fn rename_recipe(recipe: Recipe, new_name: Recipe) -> Self {
+        Self::RenameRecipe { recipe, new_name }
     }
 }
@@ -0,0 +1,77 @@
+use thiserror::Error;
+use time::{Month, OffsetDateTime};
+
+pub use time::Date;
+
+#[derive(Error, Debug)]
+pub enum DateError {
+    #[error("invalid date: {0} (expected YYYY-MM-DD)")]
+    Invalid(String),
+
+    #[error("invalid period: {0} (expected a number of days or weeks, e.g. 3d or 2w)")]
+    InvalidPeriod(String),
+}
+
+/// Parses a date written as `YYYY-MM-DD`, the way dates are shown and stored.
+pub fn parse_date(s: &str) -> Result<Date, DateError> {
+    let invalid = || DateError::Invalid(s.to_string());
+    let mut parts = s.trim().splitn(3, '-');
+    let mut next = || parts.next().ok_or_else(invalid);
+    let (year, month, day) = (next()?, next()?, next()?);
+
+    let year = year.parse::<i32>().map_err(|_| invalid())?;
+    let month = month
+        .parse::<u8>()
+        .ok()
+        .and_then(|month| Month::try_from(month).ok())
+        .ok_or_else(invalid)?;
+    let day = day.parse::<u8>().map_err(|_| invalid())?;
+    Date::from_calendar_date(year, month, day).map_err(|_| invalid())
+}
+
+/// Parses a number of days, such as `3d`, `3 days` or `2w`. A bare number is
+/// taken to be days.
+pub fn parse_period(s: &str) -> Result<u32, DateError> {
+    let invalid = || DateError::InvalidPeriod(s.to_string());
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number = number.parse::<u32>().map_err(|_| invalid())?;
+    let days = match unit.trim() {
+        "" | "d" | "day" | "days" => 1,
+        "w" | "week" | "weeks" => 7,
+        _ => return Err(invalid()),
+    };
+    number.checked_mul(days).ok_or_else(invalid)
+}
+
+/// Today's date in UTC.
+pub fn today() -> Date {
+    OffsetDateTime::now_utc().date()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        let date = parse_date("2026-10-18").unwrap();
+        assert_eq!(date.to_string(), "2026-10-18");
+        assert_eq!(parse_date(" 2026-1-5 ").unwrap().to_string(), "2026-01-05");
+        assert!(parse_date("2026-02-30").is_err());
+        assert!(parse_date("18/10/2026").is_err());
+        assert!(parse_date("2026-10").is_err());
+    }
+
+    #[test]
+    fn test_parse_period() {
+        assert_eq!(parse_period("3d").unwrap(), 3);
+        assert_eq!(parse_period("3 days").unwrap(), 3);
+        assert_eq!(parse_period("5").unwrap(), 5);
+        assert_eq!(parse_period("2w").unwrap(), 14);
+        assert!(parse_period("d").is_err());
+        assert!(parse_period("3 months").is_err());
+        assert!(parse_period("-1d").is_err());
+    }
+}
@@ -7,6 +7,7 @@ use crate::{item::Item, list::List};
 
 pub const ITEMS_YAML_PATH: &str = "items.yaml";
 pub const LIST_YAML_PATH: &str = "list.yaml";
+pub const MEAL_PLAN_ICS_PATH: &str = "meal-plan.ics";
 
 #[derive(Error, Debug)]
 pub enum ExportError {
@@ -32,3 +33,16 @@ pub trait YamlSerializable {
 
 impl YamlSerializable for Vec<Item> {}
 impl YamlSerializable for List {}
+
+pub trait ICalendarSerializable {
+    fn to_icalendar(&self) -> String;
+
+    fn serialize_to_icalendar_and_write<P>(&self, path: P) -> Result<(), ExportError>
+    where
+        P: AsRef<Path>,
+    {
+        std::fs::write(path, self.to_icalendar())?;
+
+        Ok(())
+    }
+}
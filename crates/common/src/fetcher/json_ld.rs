@@ -0,0 +1,183 @@
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+use super::FetchError;
+
+/// The parts of a schema.org `Recipe` that `gust` cares about, as found in
+/// a page's `<script type="application/ld+json">` blocks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JsonLdRecipe {
+    name: String,
+    ingredients: Vec<String>,
+    recipe_yield: Option<String>,
+}
+
+impl JsonLdRecipe {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn ingredients(&self) -> &[String] {
+        &self.ingredients
+    }
+
+    pub fn recipe_yield(&self) -> Option<&str> {
+        self.recipe_yield.as_deref()
+    }
+
+    /// Returns the first `Recipe` found in the document's JSON-LD blocks,
+    /// looking inside `@graph` arrays and top-level arrays.
+    pub fn from_document(document: &Html) -> Result<Option<Self>, FetchError> {
+        let selector = Selector::parse(r#"script[type="application/ld+json"]"#)
+            .map_err(|e| FetchError::SelectorError(e.to_string()))?;
+
+        Ok(document
+            .select(&selector)
+            .filter_map(|script| serde_json::from_str::<Value>(&script.inner_html()).ok())
+            .find_map(|value| find_recipe(&value).and_then(Self::from_value)))
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        let name = value
+            .get("name")
+            .and_then(Value::as_str)?
+            .trim()
+            .to_string();
+
+        let ingredients = value
+            .get("recipeIngredient")
+            .or_else(|| value.get("ingredients"))
+            .map(strings)
+            .unwrap_or_default();
+
+        if ingredients.is_empty() {
+            return None;
+        }
+
+        let recipe_yield = value
+            .get("recipeYield")
+            .and_then(|value| strings(value).into_iter().next());
+
+        Some(Self {
+            name,
+            ingredients,
+            recipe_yield,
+        })
+    }
+}
+
+fn is_recipe(value: &Value) -> bool {
+    match value.get("@type") {
+        Some(Value::String(t)) => t == "Recipe",
+        Some(Value::Array(types)) => types.iter().any(|t| t.as_str() == Some("Recipe")),
+        _ => false,
+    }
+}
+
+fn find_recipe(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(values) => values.iter().find_map(find_recipe),
+        Value::Object(map) => {
+            if is_recipe(value) {
+                Some(value)
+            } else {
+                map.get("@graph").and_then(find_recipe)
+            }
+        }
+        _ => None,
+    }
+}
+
+// Collects a JSON-LD text property that may be a single value or an array.
+fn strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.trim().to_string()],
+        Value::Number(n) => vec![n.to_string()],
+        Value::Array(values) => values.iter().flat_map(strings).collect(),
+        _ => Vec::new(),
+    }
+    .into_iter()
+    .filter(|s| !s.is_empty())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+
+    use super::JsonLdRecipe;
+
+    fn document(json: &str) -> Html {
+        Html::parse_document(&format!(
+            r#"<html><head><script type="application/ld+json">{json}</script></head><body></body></html>"#
+        ))
+    }
+
+    #[test]
+    fn test_json_ld_recipe() {
+        let doc = document(
+            r#"{
+                "@context": "https://schema.org",
+                "@type": "Recipe",
+                "name": "Fluffy American pancakes",
+                "recipeYield": "Makes 16",
+                "recipeIngredient": ["135g/4¾oz plain flour", "1 tsp baking powder"],
+                "recipeInstructions": [
+                    {"@type": "HowToStep", "text": "Sift the flour."},
+                    {"@type": "HowToStep", "text": "Whisk in the milk."}
+                ]
+            }"#,
+        );
+
+        let recipe = JsonLdRecipe::from_document(&doc).unwrap().unwrap();
+
+        insta::assert_debug_snapshot!(recipe, @r###"
+        JsonLdRecipe {
+            name: "Fluffy American pancakes",
+            ingredients: [
+                "135g/4¾oz plain flour",
+                "1 tsp baking powder",
+            ],
+            recipe_yield: Some(
+                "Makes 16",
+            ),
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_json_ld_recipe_in_graph() {
+        let doc = document(
+            r#"{
+                "@context": "https://schema.org",
+                "@graph": [
+                    {"@type": "WebPage", "name": "Not a recipe"},
+                    {
+                        "@type": ["Recipe", "NewsArticle"],
+                        "name": "Crispy sheet-pan noodles",
+                        "recipeYield": ["4", "4 servings"],
+                        "recipeIngredient": ["1 lb noodles", "2 tbsp soy sauce"],
+                        "recipeInstructions": [{
+                            "@type": "HowToSection",
+                            "name": "Noodles",
+                            "itemListElement": [{"@type": "HowToStep", "text": "Boil the noodles."}]
+                        }]
+                    }
+                ]
+            }"#,
+        );
+
+        let recipe = JsonLdRecipe::from_document(&doc).unwrap().unwrap();
+
+        assert_eq!(recipe.name(), "Crispy sheet-pan noodles");
+        assert_eq!(recipe.ingredients(), ["1 lb noodles", "2 tbsp soy sauce"]);
+        assert_eq!(recipe.recipe_yield(), Some("4"));
+    }
+
+    #[test]
+    fn test_json_ld_no_recipe() {
+        let doc = document(r#"{"@type": "WebPage", "name": "Not a recipe"}"#);
+
+        assert!(JsonLdRecipe::from_document(&doc).unwrap().is_none());
+    }
+}
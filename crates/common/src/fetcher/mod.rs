@@ -1,54 +1,70 @@
-use scraper::{Html, Selector};
+mod json_ld;
+mod source;
+
+use std::sync::Arc;
+
+use scraper::Html;
 use thiserror::Error;
 use url::Url;
 
-use crate::recipes::{Ingredients, Recipe};
+use crate::recipes::{Ingredients, Recipe, Servings};
+
+pub use self::{
+    json_ld::JsonLdRecipe,
+    source::{BbcFood, JsonLd, RecipeSource, SourceRegistry},
+};
 
 #[derive(Error, Debug)]
 pub enum FetchError {
     #[error("CSS selector failed to select anything")]
     CSS,
+    #[error("no schema.org recipe found in the page's JSON-LD")]
+    JsonLd,
     #[error("reqwest error: {0}")]
     Reqwest(#[from] reqwest::Error),
     #[error("Selector Error: {0}")]
     SelectorError(String),
+    #[error("no recipe source supports: {0}")]
+    UnsupportedSite(Url),
 }
 
 pub struct Fetcher {
-    site: Site,
+    source: Arc<dyn RecipeSource>,
     url: Url,
 }
 
-#[allow(dead_code, clippy::upper_case_acronyms)]
-enum Site {
-    BBC,
-    NYT,
-}
+impl TryFrom<Url> for Fetcher {
+    type Error = FetchError;
 
-impl From<Url> for Fetcher {
-    fn from(url: Url) -> Self {
-        match url.host_str() {
-            Some("www.bbc.co.uk") => Self::new(Site::BBC, url),
-            _ => unimplemented!(
-                "'gust' currently only supports requests for recipes from the BBC Food website."
-            ),
-        }
+    fn try_from(url: Url) -> Result<Self, Self::Error> {
+        Self::with_registry(url, &SourceRegistry::default())
     }
 }
 
 impl Fetcher {
-    fn new(site: Site, url: Url) -> Self {
-        Self { site, url }
+    pub fn with_registry(url: Url, registry: &SourceRegistry) -> Result<Self, FetchError> {
+        match registry.source_for(&url) {
+            Some(source) => Ok(Self { source, url }),
+            None => Err(FetchError::UnsupportedSite(url)),
+        }
     }
 
-    pub async fn fetch_recipe(&self) -> Result<(Recipe, Ingredients), FetchError> {
+    pub async fn fetch_recipe(
+        &self,
+    ) -> Result<(Recipe, Ingredients, Option<Servings>), FetchError> {
         let document = self.fetch_html().await?;
+        self.recipe_from_document(&document)
+    }
+
+    fn recipe_from_document(
+        &self,
+        document: &Html,
+    ) -> Result<(Recipe, Ingredients, Option<Servings>), FetchError> {
+        let (name, ingredients, servings) = self.source.recipe(document)?;
         Ok((
-            self.fetch_recipe_name(&document)?.trim().into(),
-            self.fetch_recipe_ingredients(&document)?
-                .into_iter()
-                .map(|i| i.trim().into())
-                .collect(),
+            name.trim().into(),
+            Ingredients::from_lines(ingredients),
+            servings,
         ))
     }
 
@@ -57,50 +73,17 @@ impl Fetcher {
         let body = response.text().await?;
         Ok(Html::parse_document(&body))
     }
-
-    fn fetch_recipe_name(&self, document: &Html) -> Result<String, FetchError> {
-        let recipe_name_selector = match self.site {
-            Site::BBC => Selector::parse(".gel-trafalgar")
-                .map_err(|e| FetchError::SelectorError(e.to_string()))?,
-            Site::NYT => unimplemented!(),
-        };
-
-        match document.select(&recipe_name_selector).next() {
-            Some(recipe_name_element) => Ok(recipe_name_element
-                .text()
-                .collect::<String>()
-                .to_lowercase()),
-            None => Err(FetchError::CSS),
-        }
-    }
-
-    fn fetch_recipe_ingredients(&self, document: &Html) -> Result<Vec<String>, FetchError> {
-        let ingredients_selector = match self.site {
-            Site::BBC => Selector::parse(".recipe-ingredients__list")
-                .map_err(|e| FetchError::SelectorError(e.to_string()))?,
-            Site::NYT => unimplemented!(),
-        };
-
-        if let Some(ingredients_container) = document.select(&ingredients_selector).next() {
-            let mut ingredients = Vec::new();
-            // Iterate through child elements to extract individual ingredients
-            for ingredient_element in ingredients_container.select(
-                &Selector::parse("li").map_err(|e| FetchError::SelectorError(e.to_string()))?,
-            ) {
-                ingredients.push(ingredient_element.text().collect::<String>().to_lowercase());
-            }
-            Ok(ingredients)
-        } else {
-            Err(FetchError::CSS)
-        }
-    }
 }
 
 #[cfg(test)]
 mod tests {
+    use scraper::Html;
     use url::Url;
 
-    use crate::fetcher::Fetcher;
+    use crate::{
+        fetcher::{FetchError, Fetcher, SourceRegistry},
+        recipes::Servings,
+    };
 
     fn url() -> Url {
         Url::parse("https://www.bbc.co.uk/food/recipes/scrambledeggandtoast_75736").unwrap()
@@ -109,9 +92,9 @@ mod tests {
     #[tokio::test]
     async fn test_fetch_recipe_ingredients() {
         let recipe_url = url();
-        let fetcher: Fetcher = recipe_url.into();
+        let fetcher = Fetcher::try_from(recipe_url).unwrap();
         let doc = fetcher.fetch_html().await.unwrap();
-        let ingredients = fetcher.fetch_recipe_ingredients(&doc).unwrap();
+        let ingredients = fetcher.source.recipe_ingredients(&doc).unwrap();
         insta::assert_debug_snapshot!(ingredients, @r#"
         [
             "1 tbsp butter, plus extra for spreading",
@@ -124,12 +107,67 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn test_recipe_from_json_ld() {
+        let fetcher =
+            Fetcher::try_from(Url::parse("https://cooking.example.com/recipes/pancakes").unwrap())
+                .unwrap();
+        let doc = Html::parse_document(
+            r#"<script type="application/ld+json">
+            {"@type": "Recipe", "name": "Pancakes", "recipeYield": "Serves 4", "recipeIngredient": ["2 Eggs", " milk "]}
+            </script>"#,
+        );
+        let (recipe, ingredients, servings) = fetcher.recipe_from_document(&doc).unwrap();
+        assert_eq!(recipe.as_str(), "pancakes");
+        assert_eq!(servings, Some(Servings::new(4)));
+        assert_eq!(
+            ingredients.names().map(|i| i.as_str()).collect::<Vec<_>>(),
+            ["eggs", "milk"]
+        );
+    }
+
+    #[test]
+    fn test_recipe_from_bbc_selectors() {
+        let fetcher = Fetcher::try_from(url()).unwrap();
+        let doc = Html::parse_document(
+            r#"<h1 class="gel-trafalgar">Scrambled Egg</h1>
+            <ul class="recipe-ingredients__list"><li>2 large eggs</li><li>1 tbsp milk</li></ul>"#,
+        );
+        let (recipe, ingredients, servings) = fetcher.recipe_from_document(&doc).unwrap();
+        assert_eq!(recipe.as_str(), "scrambled egg");
+        assert_eq!(servings, None);
+        assert_eq!(
+            ingredients.names().map(|i| i.as_str()).collect::<Vec<_>>(),
+            ["eggs", "milk"]
+        );
+    }
+
+    #[test]
+    fn test_recipe_without_json_ld() {
+        let fetcher =
+            Fetcher::try_from(Url::parse("https://cooking.example.com/about").unwrap()).unwrap();
+        let doc = Html::parse_document("<h1>About us</h1>");
+        assert!(matches!(
+            fetcher.recipe_from_document(&doc),
+            Err(FetchError::JsonLd)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_site() {
+        let url = Url::parse("https://example.com/recipe").unwrap();
+        assert!(matches!(
+            Fetcher::with_registry(url, &SourceRegistry::empty()),
+            Err(FetchError::UnsupportedSite(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_fetch_recipe_name() {
         let recipe_url = url();
-        let fetcher: Fetcher = recipe_url.into();
+        let fetcher = Fetcher::try_from(recipe_url).unwrap();
         let doc = fetcher.fetch_html().await.unwrap();
-        let recipe = fetcher.fetch_recipe_name(&doc).unwrap();
+        let recipe = fetcher.source.recipe_name(&doc).unwrap();
         insta::assert_display_snapshot!(recipe, @"scrambled egg and toast with smoked salmon");
     }
 }
@@ -0,0 +1,243 @@
+use std::sync::Arc;
+
+use scraper::{Html, Selector};
+use url::Url;
+
+use super::{FetchError, JsonLdRecipe};
+use crate::recipes::Servings;
+
+/// A site adapter that knows how to pull a recipe out of a fetched page.
+pub trait RecipeSource: Send + Sync {
+    /// Whether this adapter handles pages served from `host`.
+    fn matches_host(&self, host: &str) -> bool;
+
+    fn recipe_name(&self, document: &Html) -> Result<String, FetchError>;
+
+    fn recipe_ingredients(&self, document: &Html) -> Result<Vec<String>, FetchError>;
+
+    /// How many the recipe serves, read from the JSON-LD `recipeYield` by
+    /// default.
+    fn recipe_servings(&self, document: &Html) -> Option<Servings> {
+        JsonLdRecipe::from_document(document)
+            .ok()
+            .flatten()
+            .and_then(|recipe| recipe.recipe_yield().and_then(Servings::from_yield))
+    }
+
+    /// Reads the name, ingredients and servings together.
+    ///
+    /// Adapters that parse the page into a recipe first override this to
+    /// parse it once rather than once for each part.
+    fn recipe(
+        &self,
+        document: &Html,
+    ) -> Result<(String, Vec<String>, Option<Servings>), FetchError> {
+        Ok((
+            self.recipe_name(document)?,
+            self.recipe_ingredients(document)?,
+            self.recipe_servings(document),
+        ))
+    }
+}
+
+/// The adapters available to a `Fetcher`.
+///
+/// Adapters are tried in order and the first whose `matches_host` accepts
+/// the URL's host is used. Adapters added with `register` take precedence
+/// over the built-in ones.
+#[derive(Clone)]
+pub struct SourceRegistry {
+    sources: Vec<Arc<dyn RecipeSource>>,
+}
+
+impl Default for SourceRegistry {
+    fn default() -> Self {
+        Self {
+            sources: vec![Arc::new(BbcFood), Arc::new(JsonLd)],
+        }
+    }
+}
+
+impl SourceRegistry {
+    /// A registry with no adapters at all.
+    pub fn empty() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, source: impl RecipeSource + 'static) {
+        self.sources.insert(0, Arc::new(source));
+    }
+
+    pub fn with_source(mut self, source: impl RecipeSource + 'static) -> Self {
+        self.register(source);
+        self
+    }
+
+    pub fn source_for(&self, url: &Url) -> Option<Arc<dyn RecipeSource>> {
+        let host = url.host_str()?;
+        self.sources
+            .iter()
+            .find(|source| source.matches_host(host))
+            .cloned()
+    }
+}
+
+fn selector(selectors: &str) -> Result<Selector, FetchError> {
+    Selector::parse(selectors).map_err(|e| FetchError::SelectorError(e.to_string()))
+}
+
+/// Any site publishing a schema.org `Recipe` as JSON-LD.
+pub struct JsonLd;
+
+impl RecipeSource for JsonLd {
+    fn matches_host(&self, _host: &str) -> bool {
+        true
+    }
+
+    fn recipe_name(&self, document: &Html) -> Result<String, FetchError> {
+        self.recipe(document).map(|(name, _, _)| name)
+    }
+
+    fn recipe_ingredients(&self, document: &Html) -> Result<Vec<String>, FetchError> {
+        self.recipe(document).map(|(_, ingredients, _)| ingredients)
+    }
+
+    fn recipe_servings(&self, document: &Html) -> Option<Servings> {
+        self.recipe(document)
+            .ok()
+            .and_then(|(_, _, servings)| servings)
+    }
+
+    fn recipe(
+        &self,
+        document: &Html,
+    ) -> Result<(String, Vec<String>, Option<Servings>), FetchError> {
+        let recipe = JsonLdRecipe::from_document(document)?.ok_or(FetchError::JsonLd)?;
+        Ok((
+            recipe.name().to_lowercase(),
+            recipe
+                .ingredients()
+                .iter()
+                .map(|ingredient| ingredient.to_lowercase())
+                .collect(),
+            recipe.recipe_yield().and_then(Servings::from_yield),
+        ))
+    }
+}
+
+/// [BBC Food](https://www.bbc.co.uk/food), read from its JSON-LD where
+/// present and from the page markup otherwise.
+pub struct BbcFood;
+
+impl RecipeSource for BbcFood {
+    fn matches_host(&self, host: &str) -> bool {
+        matches!(host, "www.bbc.co.uk" | "bbc.co.uk")
+    }
+
+    fn recipe_name(&self, document: &Html) -> Result<String, FetchError> {
+        JsonLd
+            .recipe_name(document)
+            .or_else(|_| Self::selected_name(document))
+    }
+
+    fn recipe_ingredients(&self, document: &Html) -> Result<Vec<String>, FetchError> {
+        JsonLd
+            .recipe_ingredients(document)
+            .or_else(|_| Self::selected_ingredients(document))
+    }
+
+    fn recipe(
+        &self,
+        document: &Html,
+    ) -> Result<(String, Vec<String>, Option<Servings>), FetchError> {
+        JsonLd.recipe(document).or_else(|_| {
+            Ok((
+                Self::selected_name(document)?,
+                Self::selected_ingredients(document)?,
+                None,
+            ))
+        })
+    }
+}
+
+impl BbcFood {
+    fn selected_name(document: &Html) -> Result<String, FetchError> {
+        match document.select(&selector(".gel-trafalgar")?).next() {
+            Some(recipe_name_element) => Ok(recipe_name_element
+                .text()
+                .collect::<String>()
+                .to_lowercase()),
+            None => Err(FetchError::CSS),
+        }
+    }
+
+    fn selected_ingredients(document: &Html) -> Result<Vec<String>, FetchError> {
+        if let Some(ingredients_container) = document
+            .select(&selector(".recipe-ingredients__list")?)
+            .next()
+        {
+            let mut ingredients = Vec::new();
+            // Iterate through child elements to extract individual ingredients
+            for ingredient_element in ingredients_container.select(&selector("li")?) {
+                ingredients.push(ingredient_element.text().collect::<String>().to_lowercase());
+            }
+            Ok(ingredients)
+        } else {
+            Err(FetchError::CSS)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+    use url::Url;
+
+    use super::*;
+
+    struct Example;
+
+    impl RecipeSource for Example {
+        fn matches_host(&self, host: &str) -> bool {
+            host == "example.com"
+        }
+
+        fn recipe_name(&self, _document: &Html) -> Result<String, FetchError> {
+            Ok("example".to_string())
+        }
+
+        fn recipe_ingredients(&self, _document: &Html) -> Result<Vec<String>, FetchError> {
+            Ok(vec!["eggs".to_string()])
+        }
+    }
+
+    #[test]
+    fn test_registered_source_takes_precedence() {
+        let registry = SourceRegistry::default().with_source(Example);
+        let doc = Html::parse_document("");
+
+        let source = registry
+            .source_for(&Url::parse("https://example.com/recipe").unwrap())
+            .unwrap();
+        assert_eq!(source.recipe_name(&doc).unwrap(), "example");
+
+        let source = registry
+            .source_for(&Url::parse("https://example.org/recipe").unwrap())
+            .unwrap();
+        assert!(matches!(source.recipe_name(&doc), Err(FetchError::JsonLd)));
+    }
+
+    #[test]
+    fn test_no_matching_source() {
+        let registry = SourceRegistry::empty().with_source(Example);
+
+        assert!(registry
+            .source_for(&Url::parse("https://example.org/recipe").unwrap())
+            .is_none());
+        assert!(SourceRegistry::default()
+            .source_for(&Url::parse("file:///tmp/recipe.html").unwrap())
+            .is_none());
+    }
+}
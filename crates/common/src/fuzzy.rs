@@ -0,0 +1,200 @@
+use std::fmt::{self, Display};
+
+/// The number of single-character insertions, deletions or substitutions
+/// needed to turn `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if a == *b {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// The candidate closest to `target`, if any is close enough to be a likely
+/// misspelling of it.
+pub fn closest<'a>(target: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (target.chars().count() / 3).max(2);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(target, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// What a search match names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    Item,
+    Recipe,
+    Section,
+}
+
+impl Display for MatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Item => write!(f, "item"),
+            Self::Recipe => write!(f, "recipe"),
+            Self::Section => write!(f, "section"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    kind: MatchKind,
+    name: String,
+}
+
+impl SearchMatch {
+    pub fn new(kind: MatchKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+        }
+    }
+
+    pub fn kind(&self) -> MatchKind {
+        self.kind
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The candidates that match `query`, best first.
+///
+/// Exact matches come first, then names starting with the query, names with
+/// a word starting with it and names containing it. Last come names where
+/// every word of the query is a likely misspelling of one of their words.
+pub fn search<'a>(
+    query: &str,
+    candidates: impl IntoIterator<Item = (MatchKind, &'a str)>,
+) -> Vec<SearchMatch> {
+    let query = query.trim().to_lowercase();
+    let mut matches = candidates
+        .into_iter()
+        .filter_map(|(kind, name)| Some((rank(&query, name)?, kind, name)))
+        .collect::<Vec<_>>();
+    matches.sort();
+
+    matches
+        .into_iter()
+        .map(|(_, kind, name)| SearchMatch::new(kind, name))
+        .collect()
+}
+
+// How well `candidate` matches `query`, lower being better.
+fn rank(query: &str, candidate: &str) -> Option<(u8, usize)> {
+    let candidate = candidate.to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    if candidate == query {
+        return Some((0, 0));
+    }
+    if candidate.starts_with(query) {
+        return Some((1, 0));
+    }
+    let words = candidate.split_whitespace().collect::<Vec<_>>();
+    if words.iter().any(|word| word.starts_with(query)) {
+        return Some((2, 0));
+    }
+    if candidate.contains(query) {
+        return Some((3, 0));
+    }
+
+    let mut total = 0;
+    for word in query.split_whitespace() {
+        let max_distance = word.chars().count() / 3;
+        let distance = words
+            .iter()
+            .map(|candidate| {
+                // Count a word cut short, such as "tomatoe", by its prefix.
+                let prefix = candidate
+                    .chars()
+                    .take(word.chars().count())
+                    .collect::<String>();
+                edit_distance(word, candidate).min(edit_distance(word, &prefix))
+            })
+            .min()?;
+        if distance > max_distance {
+            return None;
+        }
+        total += distance;
+    }
+    Some((4, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("dairy", "dairy"), 0);
+        assert_eq!(edit_distance("diary", "dairy"), 2);
+        assert_eq!(edit_distance("fresh", "frsh"), 1);
+        assert_eq!(edit_distance("", "pantry"), 6);
+    }
+
+    #[test]
+    fn test_closest() {
+        let sections = ["fresh", "pantry", "protein", "dairy", "freezer"];
+
+        assert_eq!(closest("diary", sections), Some("dairy"));
+        assert_eq!(closest("protien", sections), Some("protein"));
+        assert_eq!(closest("hardware", sections), None);
+    }
+
+    #[test]
+    fn test_search() {
+        let candidates = [
+            (MatchKind::Recipe, "tomato soup"),
+            (MatchKind::Item, "tomatoes"),
+            (MatchKind::Item, "sun-dried tomatoes"),
+            (MatchKind::Item, "chicken thighs"),
+            (MatchKind::Recipe, "roast chicken"),
+            (MatchKind::Section, "fresh"),
+        ];
+        let search = |query: &str| {
+            let found = search(query, candidates)
+                .iter()
+                .map(|found| format!("{} {}", found.kind(), found.name()))
+                .collect::<Vec<_>>();
+            if found.is_empty() {
+                "none".to_string()
+            } else {
+                found.join(", ")
+            }
+        };
+
+        insta::assert_snapshot!(
+            ["tomato", "Chiken", "roast chiken", "frsh", "bread"]
+                .map(|query| format!("{query}: {}", search(query)))
+                .join("\n"),
+            @r###"
+        tomato: item tomatoes, recipe tomato soup, item sun-dried tomatoes
+        Chiken: item chicken thighs, recipe roast chicken
+        roast chiken: recipe roast chicken
+        frsh: section fresh
+        bread: none
+        "###
+        );
+    }
+}
@@ -0,0 +1,808 @@
+use std::{fmt, ops::Add};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    item::Name,
+    unit::{Density, Dimension, Unit, UnitSystem},
+};
+
+// Words describing the size or measure of an ingredient rather than what it is.
+const DESCRIPTORS: [&str; 10] = [
+    "small",
+    "medium",
+    "large",
+    "extra-large",
+    "big",
+    "heaped",
+    "level",
+    "rounded",
+    "generous",
+    "good",
+];
+
+// How an ingredient is prepared, e.g. "finely chopped onion".
+const PREPARATIONS: [&str; 26] = [
+    "beaten",
+    "chopped",
+    "cooked",
+    "crushed",
+    "cubed",
+    "deseeded",
+    "diced",
+    "drained",
+    "grated",
+    "halved",
+    "juiced",
+    "julienned",
+    "mashed",
+    "melted",
+    "minced",
+    "peeled",
+    "quartered",
+    "rinsed",
+    "shredded",
+    "sifted",
+    "sliced",
+    "softened",
+    "toasted",
+    "torn",
+    "trimmed",
+    "zested",
+];
+
+const ADVERBS: [&str; 9] = [
+    "coarsely", "finely", "freshly", "lightly", "loosely", "roughly", "thickly", "thinly", "well",
+];
+
+// Words that start a comma-separated note on the previous ingredient rather
+// than a new ingredient, e.g. "1 tbsp butter, plus extra for spreading".
+const CONTINUATIONS: [&str; 10] = [
+    "plus", "or", "to", "for", "at", "about", "such", "if", "optional", "divided",
+];
+
+/// An amount of an ingredient, e.g. `1½` or `2-3`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Quantity {
+    Exact(f64),
+    Range(f64, f64),
+}
+
+impl Quantity {
+    pub fn new(low: f64, high: Option<f64>) -> Self {
+        match high {
+            Some(high) if high != low => Self::Range(low, high),
+            _ => Self::Exact(low),
+        }
+    }
+
+    pub fn low(&self) -> f64 {
+        match self {
+            Self::Exact(amount) | Self::Range(amount, _) => *amount,
+        }
+    }
+
+    pub fn high(&self) -> Option<f64> {
+        match self {
+            Self::Exact(_) => None,
+            Self::Range(_, high) => Some(*high),
+        }
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        match self {
+            Self::Exact(amount) => Self::Exact(amount * factor),
+            Self::Range(low, high) => Self::Range(low * factor, high * factor),
+        }
+    }
+}
+
+impl Add for Quantity {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Exact(a), Self::Exact(b)) => Self::Exact(a + b),
+            (a, b) => Self::Range(
+                a.low() + b.low(),
+                a.high().unwrap_or(a.low()) + b.high().unwrap_or(b.low()),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Exact(amount) => write!(f, "{}", Number(*amount)),
+            Self::Range(low, high) => write!(f, "{}-{}", Number(*low), Number(*high)),
+        }
+    }
+}
+
+struct Number(f64);
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounded = (self.0 * 100.0).round() / 100.0;
+        if rounded.fract() == 0.0 {
+            write!(f, "{rounded}")
+        } else {
+            write!(f, "{}", format!("{rounded:.2}").trim_end_matches('0'))
+        }
+    }
+}
+
+/// A quantity in a given unit, e.g. `3 tbsp`, or a count such as `2` when
+/// there is no unit.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Measure {
+    quantity: Quantity,
+    unit: Option<Unit>,
+}
+
+impl Measure {
+    pub fn new(quantity: Quantity, unit: Option<Unit>) -> Self {
+        Self { quantity, unit }
+    }
+
+    /// Parses an amount on its own, such as "500g" or "2 tbsp".
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        let (quantity, rest) = parse_quantity(&s)?;
+        let (unit, rest) = match Unit::parse_prefix(rest) {
+            Some((unit, rest)) => (Some(unit), rest),
+            None => (None, rest),
+        };
+        rest.trim().is_empty().then(|| Self::new(quantity, unit))
+    }
+
+    pub fn quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    pub fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
+    /// Adds `other` to this measure, converting it into this measure's unit
+    /// where possible, and returning `other` back if they can't be combined.
+    /// A volume added to a mass, or a mass to a volume, is totalled by mass.
+    /// This measure is left as it was when the two can't be combined.
+    pub fn try_add(&mut self, other: Measure, density: Option<Density>) -> Result<(), Measure> {
+        let base = match (&self.unit, &other.unit) {
+            (Some(unit), Some(other_unit))
+                if unit.dimension() == Dimension::Volume
+                    && other_unit.dimension() == Dimension::Mass =>
+            {
+                self.convert_to(other_unit, density)
+                    .unwrap_or_else(|| self.clone())
+            }
+            _ => self.clone(),
+        };
+
+        let factor = match (&base.unit, &other.unit) {
+            (None, None) => Some(1.0),
+            (Some(unit), Some(other_unit)) => other_unit.conversion_factor(unit, density),
+            _ => None,
+        };
+
+        match factor {
+            Some(factor) => {
+                *self = Self::new(base.quantity + other.quantity.scale(factor), base.unit);
+                Ok(())
+            }
+            None => Err(other),
+        }
+    }
+
+    /// Converts this measure into `unit`, if possible.
+    pub fn convert_to(&self, unit: &Unit, density: Option<Density>) -> Option<Measure> {
+        let factor = self.unit.as_ref()?.conversion_factor(unit, density)?;
+        Some(Self::new(self.quantity.scale(factor), Some(unit.clone())))
+    }
+
+    /// Expresses this measure in the units `system` would use for its size,
+    /// e.g. `1500 g` as `1.5 kg` or `3.3 lb`.
+    pub fn to_system(&self, system: UnitSystem) -> Measure {
+        let Some(unit) = &self.unit else {
+            return self.clone();
+        };
+        let base = match unit.dimension() {
+            Dimension::Mass => Unit::Gram,
+            Dimension::Volume => Unit::Millilitre,
+            Dimension::Count => return self.clone(),
+        };
+        let Some(base_amount) = self
+            .convert_to(&base, None)
+            .map(|measure| measure.quantity.high().unwrap_or(measure.quantity.low()))
+        else {
+            return self.clone();
+        };
+
+        self.convert_to(&system.preferred_unit(unit, base_amount), None)
+            .unwrap_or_else(|| self.clone())
+    }
+}
+
+impl fmt::Display for Measure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.unit {
+            Some(unit) => write!(f, "{} {unit}", self.quantity),
+            None => write!(f, "{}", self.quantity),
+        }
+    }
+}
+
+/// A single line of a recipe's ingredients, split into its parts.
+///
+/// "135g/4¾oz plain flour" parses to a quantity of `135`, a unit of `g` and
+/// the item `plain flour`, and "1 large egg, lightly beaten" to `1` `egg`
+/// prepared "large, lightly beaten".
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Ingredient {
+    quantity: Option<Quantity>,
+    unit: Option<Unit>,
+    name: Name,
+    preparation: Option<String>,
+}
+
+impl Ingredient {
+    pub fn parse(line: &str) -> Self {
+        let line = line.trim().to_lowercase();
+
+        let (line, mut notes) = take_parentheticals(&line);
+        let (main, after_comma) = match line.split_once(',') {
+            Some((main, rest)) => (main, Some(rest.trim())),
+            None => (line.as_str(), None),
+        };
+
+        let mut rest = main.trim();
+        let mut quantity = None;
+        let mut unit = None;
+
+        if let Some((q, r)) = parse_quantity(rest) {
+            quantity = Some(q);
+            rest = r;
+            if let Some((u, r)) = Unit::parse_prefix(rest) {
+                unit = Some(u);
+                rest = r;
+            }
+            rest = skip_alternative_measure(rest);
+            if unit.is_none() {
+                if let Some((u, r)) = Unit::parse_prefix(rest) {
+                    unit = Some(u);
+                    rest = r;
+                }
+            }
+        }
+
+        rest = rest.trim_start();
+        rest = rest.strip_prefix("of ").unwrap_or(rest);
+
+        let (leading, name) = take_leading_preparation(rest);
+        if let Some(leading) = leading {
+            notes.insert(0, leading);
+        }
+        notes.extend(after_comma.filter(|s| !s.is_empty()).map(String::from));
+
+        let name = name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_matches(|c: char| c.is_ascii_punctuation())
+            .to_string();
+
+        Self {
+            name: if name.is_empty() {
+                Name::from(main)
+            } else {
+                Name::from(name.as_str())
+            },
+            quantity,
+            unit,
+            preparation: (!notes.is_empty()).then(|| notes.join(", ")),
+        }
+    }
+
+    pub fn with_quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn with_measure(mut self, measure: Measure) -> Self {
+        self.quantity = Some(measure.quantity);
+        self.unit = measure.unit;
+        self
+    }
+
+    pub fn with_preparation(mut self, preparation: &str) -> Self {
+        self.preparation = Some(preparation.to_string());
+        self
+    }
+
+    pub fn quantity(&self) -> Option<Quantity> {
+        self.quantity
+    }
+
+    /// The ingredient's quantity and unit, if it has a quantity.
+    pub fn measure(&self) -> Option<Measure> {
+        self.quantity
+            .map(|quantity| Measure::new(quantity, self.unit.clone()))
+    }
+
+    pub fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn preparation(&self) -> Option<&str> {
+        self.preparation.as_deref()
+    }
+
+    pub fn into_name(self) -> Name {
+        self.name
+    }
+
+    /// Multiplies the ingredient's quantity, if any, by `factor`.
+    pub fn scale(mut self, factor: f64) -> Self {
+        self.quantity = self.quantity.map(|quantity| quantity.scale(factor));
+        self
+    }
+
+    /// Shows the ingredient's quantity in the units of `system`.
+    pub fn to_system(mut self, system: UnitSystem) -> Self {
+        if let Some(measure) = self.measure().map(|measure| measure.to_system(system)) {
+            self.quantity = Some(measure.quantity());
+            self.unit = measure.unit().cloned();
+        }
+        self
+    }
+}
+
+impl From<Name> for Ingredient {
+    fn from(name: Name) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Display for Ingredient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(quantity) = self.quantity {
+            write!(f, "{quantity} ")?;
+        }
+        if let Some(unit) = &self.unit {
+            write!(f, "{unit} ")?;
+        }
+        write!(f, "{}", self.name)?;
+        if let Some(preparation) = &self.preparation {
+            write!(f, ", {preparation}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits a comma-separated list of ingredients, keeping notes such as
+/// "lightly beaten" with the ingredient they belong to.
+pub fn split_ingredient_list(s: &str) -> Vec<String> {
+    fn push(segment: &str, lines: &mut Vec<String>) {
+        let segment = segment.trim();
+        if segment.is_empty() {
+            return;
+        }
+        match lines.last_mut() {
+            Some(last) if is_continuation(segment) => {
+                last.push_str(", ");
+                last.push_str(segment);
+            }
+            _ => lines.push(segment.to_string()),
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut depth = 0usize;
+    let mut segment = String::new();
+
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                push(&segment, &mut lines);
+                segment.clear();
+                continue;
+            }
+            _ => {}
+        }
+        segment.push(c);
+    }
+    push(&segment, &mut lines);
+
+    lines
+}
+
+fn is_continuation(segment: &str) -> bool {
+    let lowercase = segment.to_lowercase();
+    let mut words = lowercase.split_whitespace();
+    let Some(first) = words.next() else {
+        return false;
+    };
+    CONTINUATIONS.contains(&first)
+        || PREPARATIONS.contains(&first)
+        || (ADVERBS.contains(&first) && words.next().is_some_and(|w| PREPARATIONS.contains(&w)))
+}
+
+fn take_parentheticals(s: &str) -> (String, Vec<String>) {
+    let mut text = String::new();
+    let mut notes = Vec::new();
+    let mut note = String::new();
+    let mut depth = 0usize;
+
+    for c in s.chars() {
+        match c {
+            '(' => {
+                if depth > 0 {
+                    note.push(c);
+                }
+                depth += 1;
+            }
+            ')' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    let trimmed = note.trim();
+                    if !trimmed.is_empty() {
+                        notes.push(trimmed.to_string());
+                    }
+                    note.clear();
+                } else {
+                    note.push(c);
+                }
+            }
+            _ if depth > 0 => note.push(c),
+            _ => text.push(c),
+        }
+    }
+
+    (text, notes)
+}
+
+// Moves leading size words and preparations ("large", "finely chopped") out
+// of the item's name.
+fn take_leading_preparation(s: &str) -> (Option<String>, &str) {
+    let mut taken = Vec::new();
+    let mut rest = s.trim_start();
+
+    loop {
+        let mut words = rest.splitn(3, char::is_whitespace);
+        let (Some(first), Some(second)) = (words.next(), words.next()) else {
+            break;
+        };
+
+        let count = if DESCRIPTORS.contains(&first) || PREPARATIONS.contains(&first) {
+            1
+        } else if ADVERBS.contains(&first) && PREPARATIONS.contains(&second) {
+            2
+        } else {
+            break;
+        };
+
+        let mut remaining = rest;
+        for _ in 0..count {
+            let (word, r) = remaining
+                .split_once(char::is_whitespace)
+                .unwrap_or((remaining, ""));
+            taken.push(word);
+            remaining = r.trim_start();
+        }
+        if remaining.is_empty() {
+            taken.truncate(taken.len() - count);
+            break;
+        }
+        rest = remaining;
+    }
+
+    ((!taken.is_empty()).then(|| taken.join(" ")), rest)
+}
+
+fn vulgar_fraction(c: char) -> Option<f64> {
+    Some(match c {
+        '¼' => 0.25,
+        '½' => 0.5,
+        '¾' => 0.75,
+        '⅓' => 1.0 / 3.0,
+        '⅔' => 2.0 / 3.0,
+        '⅕' => 0.2,
+        '⅖' => 0.4,
+        '⅗' => 0.6,
+        '⅘' => 0.8,
+        '⅙' => 1.0 / 6.0,
+        '⅚' => 5.0 / 6.0,
+        '⅛' => 0.125,
+        '⅜' => 0.375,
+        '⅝' => 0.625,
+        '⅞' => 0.875,
+        _ => return None,
+    })
+}
+
+fn take_digits(s: &str) -> (&str, &str) {
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+// Parses "a/b" at the start of `s`.
+fn parse_fraction(s: &str) -> Option<(f64, &str)> {
+    let (numerator, rest) = take_digits(s);
+    let rest = rest.strip_prefix(['/', '⁄'])?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let numerator: f64 = numerator.parse().ok()?;
+    let denominator: f64 = rest[..end].parse().ok()?;
+    (denominator != 0.0).then(|| (numerator / denominator, &rest[end..]))
+}
+
+// Parses a number such as "2", "1.5", "1/2", "½", "1½" or "1 1/2" at the
+// start of `s`.
+fn parse_number(s: &str) -> Option<(f64, &str)> {
+    if let Some(c) = s.chars().next() {
+        if let Some(value) = vulgar_fraction(c) {
+            return Some((value, &s[c.len_utf8()..]));
+        }
+    }
+
+    if let Some(parsed) = parse_fraction(s) {
+        return Some(parsed);
+    }
+
+    let (whole, rest) = take_digits(s);
+    let whole: f64 = whole.parse().ok()?;
+
+    if let Some(c) = rest.chars().next() {
+        if let Some(value) = vulgar_fraction(c) {
+            return Some((whole + value, &rest[c.len_utf8()..]));
+        }
+    }
+
+    if let Some(after_space) = rest.strip_prefix(' ') {
+        if let Some(c) = after_space.chars().next() {
+            if let Some(value) = vulgar_fraction(c) {
+                return Some((whole + value, &after_space[c.len_utf8()..]));
+            }
+        }
+        if let Some((value, r)) = parse_fraction(after_space) {
+            return Some((whole + value, r));
+        }
+    }
+
+    Some((whole, rest))
+}
+
+fn parse_quantity(s: &str) -> Option<(Quantity, &str)> {
+    let (low, rest) = parse_number(s)?;
+
+    let trimmed = rest.trim_start();
+    let range_rest = trimmed
+        .strip_prefix(['-', '–'])
+        .or_else(|| trimmed.strip_prefix("to "));
+    if let Some((high, rest)) = range_rest.and_then(|r| parse_number(r.trim_start())) {
+        return Some((Quantity::Range(low, high), rest));
+    }
+
+    Some((Quantity::Exact(low), rest))
+}
+
+// Skips a second measure given in other units, as in "130ml/4½fl oz milk",
+// or the size of a container, as in "1 x 400g tin".
+fn skip_alternative_measure(s: &str) -> &str {
+    let trimmed = s.trim_start();
+    let Some(after) = trimmed
+        .strip_prefix('/')
+        .or_else(|| trimmed.strip_prefix("x "))
+    else {
+        return s;
+    };
+
+    match parse_quantity(after.trim_start()) {
+        Some((_, rest)) => Unit::parse_prefix(rest)
+            .map(|(_, rest)| rest)
+            .unwrap_or(rest),
+        None => s,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(line: &str) -> (Option<Quantity>, Option<String>, String, Option<String>) {
+        let ingredient = Ingredient::parse(line);
+        (
+            ingredient.quantity(),
+            ingredient.unit().map(ToString::to_string),
+            ingredient.name().to_string(),
+            ingredient.preparation().map(String::from),
+        )
+    }
+
+    #[test]
+    fn test_parse_bbc_lines() {
+        insta::assert_debug_snapshot!(
+            [
+                "135g/4¾oz plain flour",
+                "130ml/4½fl oz milk",
+                "1 tbsp butter, plus extra for spreading",
+                "2 large free-range eggs",
+                "1 slice wholemeal bread, toasted",
+                "2 tbsp melted butter (allowed to cool slightly)",
+                "salt and freshly ground black pepper",
+            ]
+            .map(|line| Ingredient::parse(line).to_string()),
+            @r###"
+        [
+            "135 g plain flour",
+            "130 ml milk",
+            "1 tbsp butter, plus extra for spreading",
+            "2 free-range eggs, large",
+            "1 slice wholemeal bread, toasted",
+            "2 tbsp butter, melted, allowed to cool slightly",
+            "salt and freshly ground black pepper",
+        ]
+        "###
+        );
+    }
+
+    #[test]
+    fn test_parse_quantities() {
+        assert_eq!(parts("½ tsp salt").0, Some(Quantity::Exact(0.5)));
+        assert_eq!(parts("1½ cups rice").0, Some(Quantity::Exact(1.5)));
+        assert_eq!(parts("1 1/2 cups rice").0, Some(Quantity::Exact(1.5)));
+        assert_eq!(parts("3/4 cup sugar").0, Some(Quantity::Exact(0.75)));
+        assert_eq!(
+            parts("2-3 cloves garlic").0,
+            Some(Quantity::Range(2.0, 3.0))
+        );
+        assert_eq!(
+            parts("2 to 3 cloves garlic").0,
+            Some(Quantity::Range(2.0, 3.0))
+        );
+        assert_eq!(parts("0.5 kg potatoes").0, Some(Quantity::Exact(0.5)));
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        assert_eq!(
+            parts("1 x 400g tin chopped tomatoes"),
+            (
+                Some(Quantity::Exact(1.0)),
+                Some("tin".to_string()),
+                "tomatoes".to_string(),
+                Some("chopped".to_string()),
+            )
+        );
+        assert_eq!(
+            parts("2 cloves of garlic, finely chopped"),
+            (
+                Some(Quantity::Exact(2.0)),
+                Some("clove".to_string()),
+                "garlic".to_string(),
+                Some("finely chopped".to_string()),
+            )
+        );
+        assert_eq!(
+            parts("1 large onion"),
+            (
+                Some(Quantity::Exact(1.0)),
+                None,
+                "onion".to_string(),
+                Some("large".to_string()),
+            )
+        );
+        assert_eq!(
+            parts("Smoked Salmon"),
+            (None, None, "smoked salmon".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_measure_try_add() {
+        let mut measure = Measure::new(Quantity::Exact(1.0), Some(Unit::Tablespoon));
+
+        assert!(measure
+            .try_add(
+                Measure::new(Quantity::Exact(2.0), Some(Unit::Tablespoon)),
+                None
+            )
+            .is_ok());
+        assert!(measure
+            .try_add(Measure::new(Quantity::Exact(100.0), Some(Unit::Gram)), None)
+            .is_err());
+        assert_eq!(measure.to_string(), "3 tbsp");
+        assert!(measure
+            .try_add(
+                Measure::new(Quantity::Range(1.0, 2.0), Some(Unit::Tablespoon)),
+                None
+            )
+            .is_ok());
+        assert!(measure
+            .try_add(
+                Measure::new(Quantity::Exact(3.0), Some(Unit::Teaspoon)),
+                None
+            )
+            .is_ok());
+
+        assert_eq!(measure.to_string(), "5-6 tbsp");
+    }
+
+    #[test]
+    fn test_measure_try_add_with_density() {
+        let mut measure = Measure::new(Quantity::Exact(100.0), Some(Unit::Gram));
+
+        assert!(measure
+            .try_add(
+                Measure::new(Quantity::Exact(100.0), Some(Unit::Millilitre)),
+                Some(Density::new(0.5))
+            )
+            .is_ok());
+
+        assert_eq!(measure.to_string(), "150 g");
+    }
+
+    #[test]
+    fn test_measure_to_system() {
+        let to_system = |quantity, unit, system| {
+            Measure::new(Quantity::Exact(quantity), Some(unit))
+                .to_system(system)
+                .to_string()
+        };
+
+        assert_eq!(to_system(1500.0, Unit::Gram, UnitSystem::Metric), "1.5 kg");
+        assert_eq!(to_system(8.0, Unit::Ounce, UnitSystem::Metric), "226.8 g");
+        assert_eq!(
+            to_system(1000.0, Unit::Gram, UnitSystem::Imperial),
+            "2.2 lb"
+        );
+        assert_eq!(
+            to_system(2.0, Unit::Tablespoon, UnitSystem::Metric),
+            "2 tbsp"
+        );
+        assert_eq!(
+            to_system(480.0, Unit::Millilitre, UnitSystem::Imperial),
+            "16.89 fl oz"
+        );
+        assert_eq!(
+            to_system(1.5, Unit::Litre, UnitSystem::Imperial),
+            "2.64 pint"
+        );
+        assert_eq!(to_system(1.0, Unit::Pint, UnitSystem::Metric), "568.26 ml");
+    }
+
+    #[test]
+    fn test_split_ingredient_list() {
+        insta::assert_debug_snapshot!(
+            split_ingredient_list("135g/4¾oz plain flour, 1 large egg, lightly beaten, 2 tbsp melted butter (allowed to cool, slightly), plus extra for cooking, salt, freshly ground black pepper"),
+            @r###"
+        [
+            "135g/4¾oz plain flour",
+            "1 large egg, lightly beaten",
+            "2 tbsp melted butter (allowed to cool, slightly), plus extra for cooking",
+            "salt",
+            "freshly ground black pepper",
+        ]
+        "###
+        );
+    }
+}
@@ -1,6 +1,64 @@
-use crate::item::Item;
+use crate::{
+    item::{Item, Name},
+    list::List,
+    section::{Section, UNSORTED},
+};
 use question::{Answer, Question};
 
+/// Whether an item is needed for the coming shop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Need {
+    Yes,
+    No,
+    /// Not this item, nor anything else in its section.
+    SkipSection,
+}
+
+/// Asks the questions of an interactive flow. Flows take a prompt rather than
+/// asking on the terminal themselves, so that the answers can come from
+/// anywhere.
+pub trait Prompt {
+    /// Called before asking about the items in a section.
+    fn start_section(&mut self, _section: &str) {}
+
+    fn need_item(&mut self, item: &Item) -> Need;
+}
+
+/// Asks on the terminal.
+#[derive(Debug, Default)]
+pub struct TerminalPrompt;
+
+impl Prompt for TerminalPrompt {
+    fn start_section(&mut self, section: &str) {
+        println!("\n{section}:");
+    }
+
+    fn need_item(&mut self, item: &Item) -> Need {
+        match user_wants_to_add_item_to_list(item) {
+            Some(true) => Need::Yes,
+            Some(false) => Need::No,
+            None => Need::SkipSection,
+        }
+    }
+}
+
+/// Asks about each item on `checklist`, a section at a time, and returns the
+/// items that are needed.
+pub fn walk_checklist(checklist: &List, prompt: &mut impl Prompt) -> Vec<Name> {
+    let mut needed = Vec::new();
+    for (section, items) in checklist.by_section() {
+        prompt.start_section(section.map_or(UNSORTED, Section::as_str));
+        for item in items {
+            match prompt.need_item(item.item()) {
+                Need::Yes => needed.push(item.name().clone()),
+                Need::No => {}
+                Need::SkipSection => break,
+            }
+        }
+    }
+    needed
+}
+
 // Returns `None` in case user wishes to skip being asked further.
 pub fn user_wants_to_add_item_to_list(item: &Item) -> Option<bool> {
     let res = Question::new(&format!(
@@ -25,3 +83,63 @@ pub fn item_matches(item: &Item) -> Answer {
         .show_defaults()
         .confirm()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answers from a script, noting each question asked.
+    struct ScriptedPrompt {
+        answers: Vec<(&'static str, Need)>,
+        asked: Vec<String>,
+    }
+
+    impl Prompt for ScriptedPrompt {
+        fn start_section(&mut self, section: &str) {
+            self.asked.push(format!("{section}:"));
+        }
+
+        fn need_item(&mut self, item: &Item) -> Need {
+            self.asked.push(format!("  {item}?"));
+            self.answers
+                .iter()
+                .find(|(name, _)| *name == item.name().as_str())
+                .map_or(Need::No, |(_, need)| *need)
+        }
+    }
+
+    #[test]
+    fn test_walk_checklist() {
+        let checklist = [
+            Item::new("milk").with_section("dairy"),
+            Item::new("bin bags"),
+            Item::new("bananas").with_section("fresh"),
+            Item::new("butter").with_section("dairy"),
+            Item::new("yoghurt").with_section("dairy"),
+            Item::new("apples").with_section("fresh"),
+        ]
+        .into_iter()
+        .collect::<List>();
+        let mut prompt = ScriptedPrompt {
+            answers: vec![
+                ("bananas", Need::Yes),
+                ("milk", Need::SkipSection),
+                ("bin bags", Need::Yes),
+            ],
+            asked: Vec::new(),
+        };
+
+        let needed = walk_checklist(&checklist, &mut prompt);
+
+        assert_eq!(needed, [Name::from("bananas"), Name::from("bin bags")]);
+        insta::assert_snapshot!(prompt.asked.join("\n"), @r###"
+        fresh:
+          bananas?
+          apples?
+        dairy:
+          milk?
+        unsorted:
+          bin bags?
+        "###);
+    }
+}
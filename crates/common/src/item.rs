@@ -85,4 +85,151 @@ impl Name {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// The name reduced to a form shared by its spelling variants, used to
+    /// tell whether two names are the same item. See [`normalize`].
+    pub fn normalized(&self) -> String {
+        normalize(&self.0)
+    }
+}
+
+// Plurals that don't follow the rules in `singular`.
+const IRREGULAR_PLURALS: [(&str, &str); 10] = [
+    ("brownies", "brownie"),
+    ("calves", "calf"),
+    ("cookies", "cookie"),
+    ("halves", "half"),
+    ("knives", "knife"),
+    ("leaves", "leaf"),
+    ("loaves", "loaf"),
+    ("pies", "pie"),
+    ("smoothies", "smoothie"),
+    ("veggies", "veggie"),
+];
+
+// Words ending in "s" that aren't plurals.
+const NOT_PLURALS: [&str; 6] = [
+    "asparagus",
+    "brussels",
+    "couscous",
+    "hummus",
+    "molasses",
+    "swiss",
+];
+
+const ARTICLES: [&str; 3] = ["a", "an", "the"];
+
+/// Reduces an item name to a form its spelling variants share, so that
+/// "Eggs", "egg" and "the eggs" all give "egg", and "crème fraîche" matches
+/// "creme fraiche".
+///
+/// Accents are folded, punctuation and leading articles dropped, and the
+/// last word made singular. The result is only meant for comparing names,
+/// not for showing them.
+pub fn normalize(name: &str) -> String {
+    let folded = name
+        .to_lowercase()
+        .chars()
+        .filter(|c| *c != '\'' && *c != '’')
+        .flat_map(fold_accent)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>();
+
+    let mut words = folded
+        .split_whitespace()
+        .skip_while(|word| ARTICLES.contains(word))
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if let Some(last) = words.last_mut() {
+        *last = singular(last);
+    }
+    words.join(" ")
+}
+
+fn fold_accent(c: char) -> Vec<char> {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => vec!['a'],
+        'ç' => vec!['c'],
+        'è' | 'é' | 'ê' | 'ë' => vec!['e'],
+        'ì' | 'í' | 'î' | 'ï' => vec!['i'],
+        'ñ' => vec!['n'],
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => vec!['o'],
+        'ù' | 'ú' | 'û' | 'ü' => vec!['u'],
+        'ý' | 'ÿ' => vec!['y'],
+        'æ' => vec!['a', 'e'],
+        'œ' => vec!['o', 'e'],
+        'ß' => vec!['s', 's'],
+        c => vec![c],
+    }
+}
+
+fn singular(word: &str) -> String {
+    if let Some((_, singular)) = IRREGULAR_PLURALS.iter().find(|(plural, _)| *plural == word) {
+        return singular.to_string();
+    }
+    if NOT_PLURALS.contains(&word) || word.len() <= 3 {
+        return word.to_string();
+    }
+
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if ["oes", "ches", "shes", "sses", "xes", "zes"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        word[..word.len() - 2].to_string()
+    } else if word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+
+    #[test]
+    fn test_normalize() {
+        insta::assert_snapshot!(
+            [
+                "Eggs",
+                "the eggs",
+                "tomatoes",
+                "cherry tomatoes",
+                "berries",
+                "cookies",
+                "bay leaves",
+                "olives",
+                "peaches",
+                "glass noodles",
+                "hummus",
+                "asparagus",
+                "Crème Fraîche",
+                "free-range eggs",
+                "baker's yeast",
+                "peas",
+            ]
+            .map(|name| format!("{name} -> {}", normalize(name)))
+            .join("\n"),
+            @r###"
+        Eggs -> egg
+        the eggs -> egg
+        tomatoes -> tomato
+        cherry tomatoes -> cherry tomato
+        berries -> berry
+        cookies -> cookie
+        bay leaves -> bay leaf
+        olives -> olive
+        peaches -> peach
+        glass noodles -> glass noodle
+        hummus -> hummus
+        asparagus -> asparagus
+        Crème Fraîche -> creme fraiche
+        free-range eggs -> free range egg
+        baker's yeast -> bakers yeast
+        peas -> pea
+        "###
+        );
+    }
 }
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    item::{Item, Name},
+    section::Section,
+};
+
+/// The name of a store whose layout the list can follow, such as "tesco".
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct StoreName(String);
+
+impl StoreName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for StoreName {
+    fn from(value: &str) -> Self {
+        Self(value.trim().to_lowercase())
+    }
+}
+
+impl From<String> for StoreName {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl fmt::Display for StoreName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An aisle of a store, named the way the store names it, e.g. "bread".
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aisle(String);
+
+impl Aisle {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Aisle {
+    fn from(value: &str) -> Self {
+        Self(value.trim().to_lowercase())
+    }
+}
+
+impl From<String> for Aisle {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl fmt::Display for Aisle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How a store is laid out: its aisles in the order they're walked, which
+/// aisle each section is found in, and items kept somewhere other than their
+/// section's aisle.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct StoreLayout {
+    store: StoreName,
+    aisles: Vec<Aisle>,
+    sections: Vec<(Section, Aisle)>,
+    items: Vec<(Name, Aisle)>,
+}
+
+impl StoreLayout {
+    pub fn new(store: StoreName) -> Self {
+        Self {
+            store,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_aisles(mut self, aisles: Vec<Aisle>) -> Self {
+        self.aisles = aisles;
+        self
+    }
+
+    pub fn with_section_aisle(mut self, section: Section, aisle: Aisle) -> Self {
+        self.sections.push((section, aisle));
+        self
+    }
+
+    pub fn with_item_aisle(mut self, item: Name, aisle: Aisle) -> Self {
+        self.items.push((item, aisle));
+        self
+    }
+
+    pub fn store(&self) -> &StoreName {
+        &self.store
+    }
+
+    pub fn aisles(&self) -> &[Aisle] {
+        &self.aisles
+    }
+
+    pub fn section_aisles(&self) -> &[(Section, Aisle)] {
+        &self.sections
+    }
+
+    pub fn item_aisles(&self) -> &[(Name, Aisle)] {
+        &self.items
+    }
+
+    /// The aisle `item` is found in: its own aisle if it has one, otherwise
+    /// the aisle of its section.
+    pub fn aisle_for(&self, item: &Item) -> Option<&Aisle> {
+        self.items
+            .iter()
+            .find(|(name, _)| name == item.name())
+            .map(|(_, aisle)| aisle)
+            .or_else(|| {
+                let section = item.section()?;
+                self.sections
+                    .iter()
+                    .find(|(s, _)| s == section)
+                    .map(|(_, aisle)| aisle)
+            })
+    }
+}
+
+impl fmt::Display for StoreLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}:", self.store)?;
+        for aisle in &self.aisles {
+            writeln!(f, "  {aisle}")?;
+            for (section, _) in self.sections.iter().filter(|(_, a)| a == aisle) {
+                writeln!(f, "    section: {section}")?;
+            }
+            for (item, _) in self.items.iter().filter(|(_, a)| a == aisle) {
+                writeln!(f, "    item: {item}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list::List;
+
+    use super::*;
+
+    #[test]
+    fn test_list_by_aisle() {
+        let layout = StoreLayout::new(StoreName::from("corner shop"))
+            .with_aisles(vec![Aisle::from("bread"), Aisle::from("produce")])
+            .with_section_aisle(Section::from("fresh"), Aisle::from("produce"))
+            .with_section_aisle(Section::from("bakery"), Aisle::from("bread"))
+            .with_item_aisle(Name::from("eggs"), Aisle::from("bread"));
+
+        let list = [
+            Item::new("apples").with_section("fresh"),
+            Item::new("milk").with_section("dairy"),
+            Item::new("eggs").with_section("dairy"),
+            Item::new("bagels").with_section("bakery"),
+            Item::new("batteries"),
+        ]
+        .into_iter()
+        .collect::<List>();
+
+        insta::assert_debug_snapshot!(
+            list.by_aisle(&layout)
+                .into_iter()
+                .map(|(aisle, items)| {
+                    let items = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    format!("{}: {}", aisle.unwrap_or("unsorted"), items.join(", "))
+                })
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            "bread: eggs, bagels",
+            "produce: apples",
+            "dairy: milk",
+            "unsorted: batteries",
+        ]
+        "###
+        );
+    }
+}
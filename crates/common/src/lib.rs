@@ -1,11 +1,21 @@
+pub mod calendar;
+pub mod classify;
 pub mod commands;
+pub mod date;
 pub mod export;
 pub mod fetcher;
+pub mod fuzzy;
+pub mod ingredient;
 pub mod input;
 pub mod item;
 pub mod items;
+pub mod layout;
 pub mod list;
 pub mod load;
+pub mod pantry;
+pub mod plan;
+pub mod query;
 pub mod recipes;
 pub mod section;
 pub mod telemetry;
+pub mod unit;
@@ -1,11 +1,67 @@
-use crate::{item::Item, load::Load, recipes::Recipe};
+use std::{fmt, ops::Deref};
+
+use crate::{
+    ingredient::Measure,
+    item::Item,
+    layout::{Aisle, StoreLayout},
+    load::Load,
+    plan::DateRange,
+    recipes::Recipe,
+    section::{Section, SECTIONS},
+    unit::{Density, UnitSystem},
+};
 use serde::{Deserialize, Serialize};
 
+/// The name of the list used when no other is given.
+pub const DEFAULT_LIST: &str = "default";
+
+/// The name of a shopping list, such as "weekly shop" or "party".
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListName(String);
+
+impl ListName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for ListName {
+    fn default() -> Self {
+        Self(DEFAULT_LIST.to_string())
+    }
+}
+
+impl From<&str> for ListName {
+    fn from(value: &str) -> Self {
+        Self(value.trim().to_lowercase())
+    }
+}
+
+impl From<String> for ListName {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl fmt::Display for ListName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct List {
+    #[serde(default)]
+    name: ListName,
     checklist: Vec<Item>,
     recipes: Vec<Recipe>,
-    items: Vec<Item>,
+    items: Vec<ListItem>,
+    #[serde(skip)]
+    section_order: Vec<Section>,
+    #[serde(skip)]
+    layout: Option<StoreLayout>,
+    #[serde(skip)]
+    plan: Option<DateRange>,
 }
 
 impl Load for List {
@@ -23,11 +79,31 @@ impl FromIterator<Item> for List {
     }
 }
 
+impl FromIterator<ListItem> for List {
+    fn from_iter<I: IntoIterator<Item = ListItem>>(iter: I) -> Self {
+        let mut c = List::new();
+
+        for i in iter {
+            c.add_list_item(i);
+        }
+        c
+    }
+}
+
 impl List {
     pub fn new() -> Self {
         Self::default()
     }
 
+    pub fn with_name(mut self, name: ListName) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn name(&self) -> &ListName {
+        &self.name
+    }
+
     pub fn with_checklist(mut self, checklist: Vec<Item>) -> Self {
         self.checklist.extend(checklist);
         self
@@ -38,11 +114,364 @@ impl List {
         self
     }
 
-    pub fn items(&self) -> &Vec<Item> {
+    /// The order to walk through sections in, such as the layout of a store.
+    pub fn with_section_order(mut self, order: Vec<Section>) -> Self {
+        self.section_order = order;
+        self
+    }
+
+    pub fn items(&self) -> &Vec<ListItem> {
         &self.items
     }
 
+    /// Orders the list for walking through a particular store.
+    pub fn with_layout(mut self, layout: StoreLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    pub fn layout(&self) -> Option<&StoreLayout> {
+        self.layout.as_ref()
+    }
+
+    /// Notes the days of the meal plan the list was made from.
+    pub fn with_plan(mut self, plan: Option<DateRange>) -> Self {
+        self.plan = plan;
+        self
+    }
+
+    pub fn plan(&self) -> Option<DateRange> {
+        self.plan
+    }
+
+    /// The items grouped by section, following the list's section order or
+    /// `SECTIONS` when it has none. Sections missing from the order come next
+    /// in alphabetical order, and items without a section come last.
+    pub fn by_section(&self) -> Vec<(Option<&Section>, Vec<&ListItem>)> {
+        let default_order = SECTIONS.map(Section::from);
+        let order = if self.section_order.is_empty() {
+            &default_order[..]
+        } else {
+            &self.section_order[..]
+        };
+
+        self.grouped(
+            |item| item.section(),
+            |section| order.iter().position(|s| s == section),
+        )
+    }
+
+    /// The items grouped by aisle of `layout`, in the order the aisles are
+    /// walked. Items in no aisle are grouped by section after the aisles, and
+    /// items without a section come last.
+    pub fn by_aisle<'a>(
+        &'a self,
+        layout: &'a StoreLayout,
+    ) -> Vec<(Option<&'a str>, Vec<&'a ListItem>)> {
+        self.grouped(
+            |item| {
+                layout
+                    .aisle_for(item)
+                    .map(Aisle::as_str)
+                    .or_else(|| item.section().map(Section::as_str))
+            },
+            |heading| {
+                layout
+                    .aisles()
+                    .iter()
+                    .position(|aisle| aisle.as_str() == heading)
+            },
+        )
+    }
+
+    /// The items grouped the way they'd be found: by aisle when the list
+    /// has a store layout, and by section otherwise.
+    pub fn groups(&self) -> Vec<(Option<&str>, Vec<&ListItem>)> {
+        match &self.layout {
+            Some(layout) => self.by_aisle(layout),
+            None => self
+                .by_section()
+                .into_iter()
+                .map(|(section, items)| (section.map(Section::as_str), items))
+                .collect(),
+        }
+    }
+
+    // Groups items by `key`, ordering the groups by `position`, then
+    // alphabetically, with items that have no key last.
+    fn grouped<'a, K>(
+        &'a self,
+        key: impl Fn(&'a ListItem) -> Option<K>,
+        position: impl Fn(K) -> Option<usize>,
+    ) -> Vec<(Option<K>, Vec<&'a ListItem>)>
+    where
+        K: Copy + Ord,
+    {
+        let mut groups: Vec<(Option<K>, Vec<&ListItem>)> = Vec::new();
+        for item in &self.items {
+            let item_key = key(item);
+            match groups.iter_mut().find(|(k, _)| *k == item_key) {
+                Some((_, items)) => items.push(item),
+                None => groups.push((item_key, vec![item])),
+            }
+        }
+
+        groups.sort_by_key(|&(k, _)| (k.is_none(), k.and_then(&position).unwrap_or(usize::MAX), k));
+        groups
+    }
+
     pub fn add_item(&mut self, item: Item) {
+        self.add_list_item(item.into());
+    }
+
+    pub fn add_list_item(&mut self, item: ListItem) {
         self.items.push(item);
     }
+
+    /// Keeps only the items that haven't been checked off yet.
+    pub fn remaining(mut self) -> Self {
+        self.items.retain(|item| !item.checked);
+        self
+    }
+
+    /// Shows every item's amounts in the units of `system`.
+    pub fn to_system(mut self, system: UnitSystem) -> Self {
+        for item in self.items.iter_mut() {
+            item.measures = item.measures.to_system(system);
+        }
+        self
+    }
+}
+
+/// An item on the list along with how much of it is needed.
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ListItem {
+    #[serde(flatten)]
+    item: Item,
+    #[serde(default, skip_serializing_if = "Measures::is_empty")]
+    measures: Measures,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    checked: bool,
+    /// When the item was last checked off or put back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checked_at: Option<String>,
+}
+
+impl ListItem {
+    pub fn new(item: Item) -> Self {
+        Self {
+            item,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_measures(mut self, measures: Measures) -> Self {
+        self.measures = measures;
+        self
+    }
+
+    pub fn with_checked(mut self, checked: bool, checked_at: Option<String>) -> Self {
+        self.checked = checked;
+        self.checked_at = checked_at;
+        self
+    }
+
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    pub fn measures(&self) -> &Measures {
+        &self.measures
+    }
+
+    /// Whether the item is already in the cart.
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn checked_at(&self) -> Option<&str> {
+        self.checked_at.as_deref()
+    }
+}
+
+impl From<Item> for ListItem {
+    fn from(item: Item) -> Self {
+        Self::new(item)
+    }
+}
+
+impl Deref for ListItem {
+    type Target = Item;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl fmt::Display for ListItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.checked {
+            write!(f, "[x] ")?;
+        }
+        if self.measures.is_empty() {
+            write!(f, "{}", self.item)
+        } else {
+            write!(f, "{} ({})", self.item, self.measures)
+        }
+    }
+}
+
+/// The running total of an item needed across recipes.
+///
+/// Measures in the same unit are summed, while measures that can't be
+/// combined, such as `100 g` and `2 tbsp` of butter, are kept side by side.
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Measures(Vec<Measure>);
+
+impl Measures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn add(&mut self, measure: Measure) {
+        self.add_with_density(measure, None);
+    }
+
+    /// Adds `measure`, using `density` to combine volumes with masses.
+    pub fn add_with_density(&mut self, mut measure: Measure, density: Option<Density>) {
+        for existing in self.0.iter_mut() {
+            match existing.try_add(measure, density) {
+                Ok(()) => return,
+                Err(unmatched) => measure = unmatched,
+            }
+        }
+        self.0.push(measure);
+    }
+}
+
+impl Measures {
+    pub fn to_system(&self, system: UnitSystem) -> Self {
+        self.0
+            .iter()
+            .map(|measure| measure.to_system(system))
+            .collect()
+    }
+}
+
+impl FromIterator<Measure> for Measures {
+    fn from_iter<I: IntoIterator<Item = Measure>>(iter: I) -> Self {
+        let mut c = Measures::new();
+
+        for i in iter {
+            c.add(i);
+        }
+        c
+    }
+}
+
+impl Deref for Measures {
+    type Target = Vec<Measure>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for Measures {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let measures = self.0.iter().map(ToString::to_string).collect::<Vec<_>>();
+        write!(f, "{}", measures.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ingredient::{Measure, Quantity},
+        item::Item,
+        section::Section,
+        unit::{Density, Unit},
+    };
+
+    use super::{List, Measures};
+
+    #[test]
+    fn test_list_by_section() {
+        let list = [
+            Item::new("batteries"),
+            Item::new("milk").with_section("dairy"),
+            Item::new("apples").with_section("fresh"),
+            Item::new("soap").with_section("household"),
+            Item::new("cheese").with_section("dairy"),
+        ]
+        .into_iter()
+        .collect::<List>();
+
+        let headings = |list: &List| {
+            list.by_section()
+                .into_iter()
+                .map(|(section, items)| {
+                    let items = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    format!(
+                        "{}: {}",
+                        section.map_or("unsorted", Section::as_str),
+                        items.join(", ")
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        insta::assert_debug_snapshot!(headings(&list), @r###"
+        [
+            "fresh: apples",
+            "dairy: milk, cheese",
+            "household: soap",
+            "unsorted: batteries",
+        ]
+        "###);
+
+        let list = list.with_section_order(vec![Section::from("dairy"), Section::from("fresh")]);
+        insta::assert_debug_snapshot!(headings(&list), @r###"
+        [
+            "dairy: milk, cheese",
+            "fresh: apples",
+            "household: soap",
+            "unsorted: batteries",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn test_measures_sum_compatible_units() {
+        let measures = [
+            Measure::new(Quantity::Exact(1.0), Some(Unit::Tablespoon)),
+            Measure::new(Quantity::Exact(100.0), Some(Unit::Gram)),
+            Measure::new(Quantity::Exact(2.0), Some(Unit::Tablespoon)),
+            Measure::new(Quantity::Exact(2.0), None),
+            Measure::new(Quantity::Exact(1.0), Some(Unit::Kilogram)),
+        ]
+        .into_iter()
+        .collect::<Measures>();
+
+        assert_eq!(measures.to_string(), "3 tbsp, 1100 g, 2");
+    }
+
+    #[test]
+    fn test_measures_with_density() {
+        let mut measures = Measures::new();
+        measures.add_with_density(
+            Measure::new(Quantity::Exact(100.0), Some(Unit::Gram)),
+            Some(Density::new(1.0)),
+        );
+        measures.add_with_density(
+            Measure::new(Quantity::Exact(2.0), Some(Unit::Tablespoon)),
+            Some(Density::new(1.0)),
+        );
+
+        assert_eq!(measures.to_string(), "130 g");
+    }
 }
@@ -0,0 +1,398 @@
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+use time::Duration;
+
+use crate::{
+    date::Date,
+    ingredient::{Measure, Quantity},
+    item::Name,
+    list::Measures,
+    recipes::Recipe,
+    unit::{Density, Unit, UnitSystem},
+};
+
+// Amounts smaller than this are taken to be nothing.
+const EPSILON: f64 = 1e-9;
+
+/// An item at home, with how much of it there is and the date it's best
+/// eaten by. An item with no amounts is simply there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PantryItem {
+    name: Name,
+    measures: Measures,
+    best_before: Option<Date>,
+}
+
+impl PantryItem {
+    pub fn new(name: Name) -> Self {
+        Self {
+            name,
+            measures: Measures::new(),
+            best_before: None,
+        }
+    }
+
+    pub fn with_measures(mut self, measures: Measures) -> Self {
+        self.measures = measures;
+        self
+    }
+
+    pub fn with_best_before(mut self, best_before: Option<Date>) -> Self {
+        self.best_before = best_before;
+        self
+    }
+
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn measures(&self) -> &Measures {
+        &self.measures
+    }
+
+    pub fn best_before(&self) -> Option<Date> {
+        self.best_before
+    }
+
+    /// Shows the item's amounts in the units of `system`.
+    pub fn to_system(mut self, system: UnitSystem) -> Self {
+        self.measures = self.measures.to_system(system);
+        self
+    }
+}
+
+impl fmt::Display for PantryItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.measures.is_empty() {
+            write!(f, ": {}", self.measures)?;
+        }
+        if let Some(best_before) = self.best_before {
+            write!(f, " (best before {best_before})")?;
+        }
+        Ok(())
+    }
+}
+
+/// A pantry item that's about to go off, or already has.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ExpiringItem {
+    name: Name,
+    #[serde(
+        serialize_with = "as_string",
+        skip_serializing_if = "Measures::is_empty"
+    )]
+    amount: Measures,
+    #[serde(serialize_with = "as_string")]
+    best_before: Date,
+    /// Days until the best-before date, negative once it's passed.
+    days_left: i64,
+}
+
+impl ExpiringItem {
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn best_before(&self) -> Date {
+        self.best_before
+    }
+
+    pub fn days_left(&self) -> i64 {
+        self.days_left
+    }
+}
+
+impl fmt::Display for ExpiringItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.amount.is_empty() {
+            write!(f, ": {}", self.amount)?;
+        }
+        write!(f, ", best before {} (", self.best_before)?;
+        match self.days_left {
+            0 => write!(f, "today")?,
+            1 => write!(f, "tomorrow")?,
+            -1 => write!(f, "yesterday")?,
+            days if days < 0 => write!(f, "{} days ago", -days)?,
+            days => write!(f, "in {days} days")?,
+        }
+        write!(f, ")")
+    }
+}
+
+/// A recipe that would use up expiring items.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RecipeSuggestion {
+    recipe: Recipe,
+    uses: Vec<Name>,
+}
+
+impl RecipeSuggestion {
+    pub fn recipe(&self) -> &Recipe {
+        &self.recipe
+    }
+
+    /// The expiring items the recipe uses, soonest to go off first.
+    pub fn uses(&self) -> &[Name] {
+        &self.uses
+    }
+}
+
+impl fmt::Display for RecipeSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let uses = self.uses.iter().map(Name::as_str).collect::<Vec<_>>();
+        write!(f, "{}: {}", self.recipe, uses.join(", "))
+    }
+}
+
+/// What in the pantry is going off soon, and the recipes to use it up in.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ExpiryReport {
+    within_days: u32,
+    items: Vec<ExpiringItem>,
+    recipes: Vec<RecipeSuggestion>,
+}
+
+impl ExpiryReport {
+    /// Picks the items in `pantry` that are best before `within_days` from
+    /// `today`, including those already past it, soonest first.
+    ///
+    /// `recipes` are recipes along with their ingredients. Those using the
+    /// most expiring items are suggested first, and of those, the ones using
+    /// the item that goes off soonest.
+    pub fn new(
+        today: Date,
+        within_days: u32,
+        pantry: impl IntoIterator<Item = PantryItem>,
+        recipes: impl IntoIterator<Item = (Recipe, Vec<Name>)>,
+    ) -> Self {
+        let last_day = today
+            .checked_add(Duration::days(within_days.into()))
+            .unwrap_or(Date::MAX);
+
+        let mut items = pantry
+            .into_iter()
+            .filter_map(|item| {
+                let best_before = item.best_before.filter(|date| *date <= last_day)?;
+                Some(ExpiringItem {
+                    name: item.name,
+                    amount: item.measures,
+                    best_before,
+                    days_left: (best_before - today).whole_days(),
+                })
+            })
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| {
+            (a.best_before, a.name.as_str()).cmp(&(b.best_before, b.name.as_str()))
+        });
+
+        let mut recipes = recipes
+            .into_iter()
+            .filter_map(|(recipe, ingredients)| {
+                let uses = items
+                    .iter()
+                    .map(|item| &item.name)
+                    .filter(|name| ingredients.contains(name))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (!uses.is_empty()).then_some(RecipeSuggestion { recipe, uses })
+            })
+            .collect::<Vec<_>>();
+        let soonest = |suggestion: &RecipeSuggestion| {
+            items
+                .iter()
+                .position(|item| item.name == suggestion.uses[0])
+                .unwrap_or(items.len())
+        };
+        recipes.sort_by(|a, b| {
+            b.uses
+                .len()
+                .cmp(&a.uses.len())
+                .then_with(|| soonest(a).cmp(&soonest(b)))
+                .then_with(|| a.recipe.as_str().cmp(b.recipe.as_str()))
+        });
+
+        Self {
+            within_days,
+            items,
+            recipes,
+        }
+    }
+
+    pub fn within_days(&self) -> u32 {
+        self.within_days
+    }
+
+    pub fn items(&self) -> &[ExpiringItem] {
+        &self.items
+    }
+
+    pub fn recipes(&self) -> &[RecipeSuggestion] {
+        &self.recipes
+    }
+}
+
+impl fmt::Display for ExpiryReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let days = match self.within_days {
+            1 => "1 day".to_string(),
+            days => format!("{days} days"),
+        };
+        if self.items.is_empty() {
+            return writeln!(f, "nothing expiring within {days}");
+        }
+        writeln!(f, "expiring within {days}:")?;
+        for item in &self.items {
+            writeln!(f, "  {item}")?;
+        }
+        if !self.recipes.is_empty() {
+            writeln!(f, "use them up in:")?;
+            for recipe in &self.recipes {
+                writeln!(f, "  {recipe}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn as_string<T: fmt::Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Takes what it can of `need` from `stock`, reducing each measure in
+/// `stock` by the amount taken from it, and returns what's still needed.
+///
+/// Amounts are only taken from measures `need` converts into, so 200 g of
+/// butter can come out of tablespoons but not out of a count. A range is
+/// drawn at its upper end.
+pub fn draw(need: &Measure, stock: &mut [Measure], density: Option<Density>) -> Option<Measure> {
+    let wanted = need.quantity().high().unwrap_or(need.quantity().low());
+    if wanted <= EPSILON {
+        return None;
+    }
+
+    // The share of `need` still to be found.
+    let mut remaining = 1.0;
+    for available in stock.iter_mut() {
+        let Some(factor) = conversion_factor(need.unit(), available.unit(), density) else {
+            continue;
+        };
+        let wanted = wanted * factor;
+        let have = available.quantity().low();
+        let taken = (wanted * remaining).min(have);
+        if taken <= EPSILON {
+            continue;
+        }
+        *available = Measure::new(Quantity::Exact(have - taken), available.unit().cloned());
+        remaining -= taken / wanted;
+        if remaining <= EPSILON {
+            return None;
+        }
+    }
+
+    Some(Measure::new(
+        need.quantity().scale(remaining),
+        need.unit().cloned(),
+    ))
+}
+
+/// Whether nothing is left of `measure`.
+pub fn used_up(measure: &Measure) -> bool {
+    measure
+        .quantity()
+        .high()
+        .unwrap_or(measure.quantity().low())
+        <= EPSILON
+}
+
+fn conversion_factor(
+    from: Option<&Unit>,
+    to: Option<&Unit>,
+    density: Option<Density>,
+) -> Option<f64> {
+    match (from, to) {
+        (None, None) => Some(1.0),
+        (Some(from), Some(to)) => from.conversion_factor(to, density),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(s: &str) -> Measure {
+        Measure::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut stock = vec![measure("300 g")];
+        assert_eq!(draw(&measure("200 g"), &mut stock, None), None);
+        assert_eq!(stock, vec![measure("100 g")]);
+
+        let still_needed = draw(&measure("0.5 kg"), &mut stock, None).unwrap();
+        assert_eq!(still_needed.to_string(), "0.4 kg");
+        assert_eq!(stock, vec![measure("0 g")]);
+
+        let mut stock = vec![measure("2"), measure("1 l")];
+        assert_eq!(draw(&measure("2 cups"), &mut stock, None), None);
+        assert_eq!(stock[0], measure("2"));
+        assert_eq!(stock[1].to_string(), "0.52 l");
+
+        let mut stock = vec![measure("6")];
+        assert_eq!(
+            draw(&measure("200 g"), &mut stock, None),
+            Some(measure("200 g"))
+        );
+    }
+
+    #[test]
+    fn test_expiry_report() {
+        use crate::date::parse_date;
+
+        let date = |date: &str| Some(parse_date(date).unwrap());
+        let item = |name: &str, amount: &str, best_before| {
+            PantryItem::new(Name::from(name))
+                .with_measures(Measures::from_iter(Measure::parse(amount)))
+                .with_best_before(best_before)
+        };
+        let pantry = [
+            item("milk", "1 l", date("2026-10-19")),
+            item("spinach", "200 g", date("2026-10-21")),
+            item("yoghurt", "", date("2026-10-16")),
+            item("eggs", "6", date("2026-10-30")),
+            item("rice", "1 kg", None),
+        ];
+        let recipes = [
+            ("pancakes", "milk, eggs, flour"),
+            ("saag", "spinach, yoghurt, onion"),
+            ("rice pudding", "rice, milk"),
+            ("omelette", "eggs"),
+        ]
+        .map(|(recipe, ingredients)| {
+            (
+                Recipe::new(recipe),
+                ingredients.split(", ").map(Name::from).collect(),
+            )
+        });
+
+        let report = ExpiryReport::new(parse_date("2026-10-18").unwrap(), 3, pantry, recipes);
+        insta::assert_snapshot!(report.to_string(), @r###"
+        expiring within 3 days:
+          yoghurt, best before 2026-10-16 (2 days ago)
+          milk: 1 l, best before 2026-10-19 (tomorrow)
+          spinach: 200 g, best before 2026-10-21 (in 3 days)
+        use them up in:
+          saag: yoghurt, spinach
+          pancakes: milk
+          rice pudding: milk
+        "###);
+        insta::assert_snapshot!(
+            serde_json::to_string(&report.items()[..2]).unwrap(),
+            @r###"[{"name":"yoghurt","best_before":"2026-10-16","days_left":-2},{"name":"milk","amount":"1 l","best_before":"2026-10-19","days_left":1}]"###
+        );
+    }
+}
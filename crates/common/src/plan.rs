@@ -0,0 +1,309 @@
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+use time::{Duration, Weekday};
+
+use crate::{
+    date::{parse_date, Date},
+    recipes::Recipe,
+};
+
+#[derive(Error, Debug)]
+pub enum PlanError {
+    #[error("unknown day: {0} (expected a weekday, 'today', 'tomorrow' or YYYY-MM-DD)")]
+    UnknownDay(String),
+
+    #[error("unknown meal: {0} (expected breakfast, lunch or dinner)")]
+    UnknownMeal(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Meal {
+    Breakfast,
+    Lunch,
+    Dinner,
+}
+
+impl Meal {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Breakfast => "breakfast",
+            Self::Lunch => "lunch",
+            Self::Dinner => "dinner",
+        }
+    }
+}
+
+impl FromStr for Meal {
+    type Err = PlanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "breakfast" => Ok(Self::Breakfast),
+            "lunch" => Ok(Self::Lunch),
+            "dinner" => Ok(Self::Dinner),
+            _ => Err(PlanError::UnknownMeal(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Meal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A meal on a particular day, which can have one recipe planned for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MealSlot {
+    date: Date,
+    meal: Meal,
+}
+
+impl MealSlot {
+    pub fn new(date: Date, meal: Meal) -> Self {
+        Self { date, meal }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn meal(&self) -> Meal {
+        self.meal
+    }
+}
+
+impl fmt::Display for MealSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.date.weekday(), self.date, self.meal)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedMeal {
+    slot: MealSlot,
+    recipe: Recipe,
+}
+
+impl PlannedMeal {
+    pub fn new(slot: MealSlot, recipe: Recipe) -> Self {
+        Self { slot, recipe }
+    }
+
+    pub fn slot(&self) -> MealSlot {
+        self.slot
+    }
+
+    pub fn recipe(&self) -> &Recipe {
+        &self.recipe
+    }
+}
+
+impl fmt::Display for PlannedMeal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.slot, self.recipe)
+    }
+}
+
+/// The days from `start` to `end`, including both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    start: Date,
+    end: Date,
+}
+
+impl DateRange {
+    pub fn new(start: Date, end: Date) -> Self {
+        Self {
+            start: start.min(end),
+            end: start.max(end),
+        }
+    }
+
+    /// The week, Monday to Sunday, that `date` falls in.
+    pub fn week_of(date: Date) -> Self {
+        let monday = date
+            .checked_sub(Duration::days(
+                date.weekday().number_days_from_monday().into(),
+            ))
+            .unwrap_or(date);
+        Self::new(monday, end_of_week(monday))
+    }
+
+    /// The seven days starting from `date`.
+    pub fn week_from(date: Date) -> Self {
+        Self::new(
+            date,
+            date.checked_add(Duration::days(6)).unwrap_or(Date::MAX),
+        )
+    }
+
+    pub fn start(&self) -> Date {
+        self.start
+    }
+
+    pub fn end(&self) -> Date {
+        self.end
+    }
+
+    pub fn contains(&self, date: Date) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = Date> {
+        let end = self.end;
+        std::iter::successors(Some(self.start), |day| day.next_day())
+            .take_while(move |day| *day <= end)
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} to {}", self.start, self.end)
+    }
+}
+
+/// The meals planned over a range of days.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MealPlan {
+    range: DateRange,
+    meals: Vec<PlannedMeal>,
+}
+
+impl MealPlan {
+    /// Keeps the meals that fall within `range`, in the order they're eaten.
+    pub fn new(range: DateRange, meals: impl IntoIterator<Item = PlannedMeal>) -> Self {
+        let mut meals = meals
+            .into_iter()
+            .filter(|meal| range.contains(meal.slot.date))
+            .collect::<Vec<_>>();
+        meals.sort_by_key(|meal| (meal.slot.date, meal.slot.meal));
+        Self { range, meals }
+    }
+
+    pub fn range(&self) -> DateRange {
+        self.range
+    }
+
+    pub fn meals(&self) -> &[PlannedMeal] {
+        &self.meals
+    }
+}
+
+impl fmt::Display for MealPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "plan for {}:", self.range)?;
+        for day in self.range.days() {
+            let meals = self
+                .meals
+                .iter()
+                .filter(|meal| meal.slot.date == day)
+                .collect::<Vec<_>>();
+            if meals.is_empty() {
+                writeln!(f, "{} {}: nothing planned", day.weekday(), day)?;
+                continue;
+            }
+            writeln!(f, "{} {}:", day.weekday(), day)?;
+            for meal in meals {
+                writeln!(f, "  {}: {}", meal.slot.meal, meal.recipe)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The Sunday ending the week that `date` falls in.
+pub fn end_of_week(date: Date) -> Date {
+    date.checked_add(Duration::days(
+        (6 - date.weekday().number_days_from_monday()).into(),
+    ))
+    .unwrap_or(date)
+}
+
+/// Reads a day as a date, or as "today", "tomorrow" or the name of a day,
+/// which is taken to be the next such day from `today`, including today.
+pub fn parse_day(s: &str, today: Date) -> Result<Date, PlanError> {
+    let day = s.trim().to_lowercase();
+    let weekday = match day.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => {
+            return today
+                .next_day()
+                .ok_or_else(|| PlanError::UnknownDay(s.to_string()))
+        }
+        "mon" | "monday" => Weekday::Monday,
+        "tue" | "tues" | "tuesday" => Weekday::Tuesday,
+        "wed" | "wednesday" => Weekday::Wednesday,
+        "thu" | "thurs" | "thursday" => Weekday::Thursday,
+        "fri" | "friday" => Weekday::Friday,
+        "sat" | "saturday" => Weekday::Saturday,
+        "sun" | "sunday" => Weekday::Sunday,
+        _ => return parse_date(&day).map_err(|_| PlanError::UnknownDay(s.to_string())),
+    };
+    DateRange::week_from(today)
+        .days()
+        .find(|day| day.weekday() == weekday)
+        .ok_or_else(|| PlanError::UnknownDay(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day() {
+        // A Sunday.
+        let today = parse_date("2026-10-18").unwrap();
+        let parse = |day: &str| match parse_day(day, today) {
+            Ok(date) => format!("{day}: {} {date}", date.weekday()),
+            Err(error) => format!("{day}: {error}"),
+        };
+
+        insta::assert_snapshot!(
+            ["Tuesday", "sun", "today", "tomorrow", "2026-11-05", "someday"]
+                .map(parse)
+                .join("\n"),
+            @r###"
+        Tuesday: Tuesday 2026-10-20
+        sun: Sunday 2026-10-18
+        today: Sunday 2026-10-18
+        tomorrow: Monday 2026-10-19
+        2026-11-05: Thursday 2026-11-05
+        someday: unknown day: someday (expected a weekday, 'today', 'tomorrow' or YYYY-MM-DD)
+        "###
+        );
+    }
+
+    #[test]
+    fn test_meal_plan() {
+        let date = |date: &str| parse_date(date).unwrap();
+        let meal = |day: &str, meal: Meal, recipe: &str| {
+            PlannedMeal::new(MealSlot::new(date(day), meal), Recipe::new(recipe))
+        };
+
+        let plan = MealPlan::new(
+            DateRange::week_of(date("2026-10-21")),
+            [
+                meal("2026-10-20", Meal::Dinner, "crispy sheet-pan noodles"),
+                meal("2026-10-20", Meal::Breakfast, "pancakes"),
+                meal("2026-10-23", Meal::Lunch, "hummus"),
+                meal("2026-10-26", Meal::Dinner, "next week's dinner"),
+            ],
+        );
+
+        insta::assert_snapshot!(plan.to_string(), @r###"
+        plan for 2026-10-19 to 2026-10-25:
+        Monday 2026-10-19: nothing planned
+        Tuesday 2026-10-20:
+          breakfast: pancakes
+          dinner: crispy sheet-pan noodles
+        Wednesday 2026-10-21: nothing planned
+        Thursday 2026-10-22: nothing planned
+        Friday 2026-10-23:
+          lunch: hummus
+        Saturday 2026-10-24: nothing planned
+        Sunday 2026-10-25: nothing planned
+        "###);
+    }
+}
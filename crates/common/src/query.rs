@@ -0,0 +1,259 @@
+use std::fmt::{self, Display};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum QueryError {
+    #[error("nothing to search for")]
+    Empty,
+
+    #[error("'{0}' needs a term after it")]
+    MissingTerm(String),
+
+    #[error("nothing to search for, only things to leave out")]
+    OnlyExcluded,
+
+    #[error("unclosed quote in: {0}")]
+    UnclosedQuote(String),
+}
+
+/// A word to search for. Words ending in `*` match any word they start, and
+/// quoted words must appear together in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Word(String),
+    Prefix(String),
+    Phrase(String),
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "{word}"),
+            Self::Prefix(prefix) => write!(f, "{prefix}*"),
+            Self::Phrase(phrase) => write!(f, "\"{phrase}\""),
+        }
+    }
+}
+
+/// Terms that must all match, less any that mustn't.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Clause {
+    include: Vec<Term>,
+    exclude: Vec<Term>,
+}
+
+impl Clause {
+    pub fn include(&self) -> &[Term] {
+        &self.include
+    }
+
+    pub fn exclude(&self) -> &[Term] {
+        &self.exclude
+    }
+
+    fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+}
+
+impl Display for Clause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms = self
+            .include
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.exclude.iter().map(|term| format!("NOT {term}")))
+            .collect::<Vec<_>>();
+        write!(f, "{}", terms.join(" AND "))
+    }
+}
+
+/// A full-text search, such as `chickpea* AND NOT coriander`.
+///
+/// Terms are joined by `AND`, `OR` and `NOT`, where terms with nothing
+/// between them must all match and `AND` binds tighter than `OR`. Only
+/// upper-case operators are read as operators, so "salt and pepper" searches
+/// for all three words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    clauses: Vec<Clause>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut clauses = Vec::new();
+        let mut clause = Clause::default();
+        let mut operator: Option<&str> = None;
+        let mut negated = false;
+
+        for token in tokens(input)? {
+            match token {
+                Token::And if operator.is_none() && !clause.is_empty() => {
+                    operator = Some("AND");
+                }
+                Token::Or if operator.is_none() && !clause.is_empty() => {
+                    clauses.push(std::mem::take(&mut clause));
+                    operator = Some("OR");
+                }
+                Token::Not if !negated => {
+                    negated = true;
+                    operator = Some("NOT");
+                }
+                Token::And => {
+                    return Err(QueryError::MissingTerm(operator_before("AND", operator)))
+                }
+                Token::Or => return Err(QueryError::MissingTerm(operator_before("OR", operator))),
+                Token::Not => return Err(QueryError::MissingTerm("NOT".to_string())),
+                Token::Term(term) => {
+                    if negated {
+                        clause.exclude.push(term);
+                    } else {
+                        clause.include.push(term);
+                    }
+                    negated = false;
+                    operator = None;
+                }
+            }
+        }
+
+        if let Some(operator) = operator {
+            return Err(QueryError::MissingTerm(operator.to_string()));
+        }
+        if !clause.is_empty() {
+            clauses.push(clause);
+        }
+        if clauses.is_empty() {
+            return Err(QueryError::Empty);
+        }
+        if clauses.iter().any(|clause| clause.include.is_empty()) {
+            return Err(QueryError::OnlyExcluded);
+        }
+        Ok(Self { clauses })
+    }
+
+    /// Clauses any one of which is enough for a match.
+    pub fn clauses(&self) -> &[Clause] {
+        &self.clauses
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clauses = self
+            .clauses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{}", clauses.join(" OR "))
+    }
+}
+
+enum Token {
+    And,
+    Or,
+    Not,
+    Term(Term),
+}
+
+// The operator a misplaced operator follows, or the operator itself when it
+// starts the query.
+fn operator_before(current: &str, previous: Option<&str>) -> String {
+    previous.unwrap_or(current).to_string()
+}
+
+fn tokens(input: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let Some(end) = quoted.find('"') else {
+                return Err(QueryError::UnclosedQuote(input.to_string()));
+            };
+            let phrase = words(&quoted[..end]).join(" ");
+            if !phrase.is_empty() {
+                tokens.push(Token::Term(Term::Phrase(phrase)));
+            }
+            rest = quoted[end + 1..].trim_start();
+            continue;
+        }
+
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '"')
+            .unwrap_or(rest.len());
+        let (word, remainder) = rest.split_at(end);
+        rest = remainder.trim_start();
+
+        match word {
+            "AND" => tokens.push(Token::And),
+            "OR" => tokens.push(Token::Or),
+            "NOT" => tokens.push(Token::Not),
+            _ => {
+                let prefix = word.ends_with('*');
+                // Punctuation inside a word, as in "free-range", splits it
+                // into a phrase.
+                let parts = words(word);
+                let term = match parts.as_slice() {
+                    [] => continue,
+                    [word] if prefix => Term::Prefix(word.clone()),
+                    [word] => Term::Word(word.clone()),
+                    parts => Term::Phrase(parts.join(" ")),
+                };
+                tokens.push(Token::Term(term));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn words(input: &str) -> Vec<String> {
+    input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let parse = |input: &str| match Query::parse(input) {
+            Ok(query) => format!("{input:?} => {query}"),
+            Err(error) => format!("{input:?} => error: {error}"),
+        };
+
+        insta::assert_snapshot!(
+            [
+                "chickpeas NOT coriander",
+                "Chickpea* tomato OR lentil AND NOT cumin",
+                "\"smoked paprika\" free-range eggs",
+                "salt and pepper",
+                "NOT coriander",
+                "chickpeas AND",
+                "OR lentils",
+                "chickpeas AND OR lentils",
+                "\"smoked paprika",
+                "  ",
+            ]
+            .map(parse)
+            .join("\n"),
+            @r###"
+        "chickpeas NOT coriander" => chickpeas AND NOT coriander
+        "Chickpea* tomato OR lentil AND NOT cumin" => chickpea* AND tomato OR lentil AND NOT cumin
+        "\"smoked paprika\" free-range eggs" => "smoked paprika" AND "free range" AND eggs
+        "salt and pepper" => salt AND and AND pepper
+        "NOT coriander" => error: nothing to search for, only things to leave out
+        "chickpeas AND" => error: 'AND' needs a term after it
+        "OR lentils" => error: 'OR' needs a term after it
+        "chickpeas AND OR lentils" => error: 'AND' needs a term after it
+        "\"smoked paprika" => error: unclosed quote in: "smoked paprika
+        "  " => error: nothing to search for
+        "###
+        );
+    }
+}
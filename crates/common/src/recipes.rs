@@ -2,7 +2,11 @@ use std::{fmt, ops::Deref};
 
 use serde::{Deserialize, Serialize};
 
-use crate::item::Name;
+use crate::{
+    ingredient::{split_ingredient_list, Ingredient},
+    item::Name,
+    unit::{Density, UnitSystem},
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, Hash, Eq, PartialEq)]
 pub struct Recipe(String);
@@ -43,25 +47,192 @@ impl From<String> for Recipe {
     }
 }
 
+/// How many people a recipe feeds.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct Servings(u32);
+
+impl fmt::Display for Servings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Servings {
+    pub fn new(servings: u32) -> Self {
+        Self(servings)
+    }
+
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+
+    /// Reads the servings from a recipe yield such as "Serves 4-6",
+    /// "4 servings" or a bare "4", taking the lower end of a range.
+    ///
+    /// Other yields, such as "Makes 16" or "2 loaves", count something other
+    /// than servings and give `None`.
+    pub fn from_yield(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        let servings = match s.strip_prefix("serves") {
+            Some(rest) => leading_count(rest.trim_start_matches([':', ' ']))?.0,
+            None => match leading_count(&s)? {
+                (count, rest) if rest.is_empty() || rest.starts_with("serving") => count,
+                _ => return None,
+            },
+        };
+
+        (servings > 0).then_some(Self(servings))
+    }
+
+    /// The factor to multiply this recipe's quantities by to feed `target`.
+    pub fn scale_factor(&self, target: Servings) -> f64 {
+        f64::from(target.0) / f64::from(self.0)
+    }
+}
+
+// The number `s` starts with, skipping the upper end of a range such as
+// "4-6", along with what follows it.
+fn leading_count(s: &str) -> Option<(u32, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let count = s[..end].parse().ok()?;
+    let rest = s[end..].trim_start();
+    let rest = match rest.strip_prefix(['-', '\u{2013}']) {
+        Some(upper) => upper
+            .trim_start()
+            .trim_start_matches(|c: char| c.is_ascii_digit()),
+        None => rest,
+    };
+    Some((count, rest.trim()))
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
-pub struct Ingredients(Vec<Name>);
+pub struct Ingredients(Vec<Ingredient>);
+
+/// How much of a recipe can be made from the items at hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipeCoverage {
+    recipe: Recipe,
+    have: Vec<Name>,
+    missing: Vec<Name>,
+}
+
+impl RecipeCoverage {
+    pub fn new(recipe: Recipe) -> Self {
+        Self {
+            recipe,
+            have: Vec::new(),
+            missing: Vec::new(),
+        }
+    }
+
+    pub fn add_ingredient(&mut self, item: Name, have: bool) {
+        if have {
+            self.have.push(item);
+        } else {
+            self.missing.push(item);
+        }
+    }
+
+    pub fn recipe(&self) -> &Recipe {
+        &self.recipe
+    }
+
+    pub fn have(&self) -> &[Name] {
+        &self.have
+    }
+
+    pub fn missing(&self) -> &[Name] {
+        &self.missing
+    }
+}
+
+impl fmt::Display for RecipeCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} of {} ingredients",
+            self.recipe,
+            self.have.len(),
+            self.have.len() + self.missing.len()
+        )?;
+        if !self.missing.is_empty() {
+            let missing = self.missing.iter().map(Name::as_str).collect::<Vec<_>>();
+            write!(f, ", missing {}", missing.join(", "))?;
+        }
+        Ok(())
+    }
+}
 
 impl Ingredients {
     pub(crate) fn new() -> Self {
         Self::default()
     }
 
-    pub(crate) fn add(&mut self, elem: Name) {
+    /// Adds a line to the ingredients, adding its amount to that of an
+    /// earlier line for the same item where their units can be combined,
+    /// and otherwise keeping it as a line of its own.
+    pub(crate) fn add(&mut self, elem: Ingredient) {
+        let Some(measure) = elem.measure() else {
+            // A line without an amount adds nothing to one already listed.
+            if !self.0.iter().any(|i| i.name() == elem.name()) {
+                self.0.push(elem);
+            }
+            return;
+        };
+
+        let density = Density::for_item(elem.name());
+        for existing in self.0.iter_mut().filter(|i| i.name() == elem.name()) {
+            let total = match existing.measure() {
+                None => measure,
+                Some(mut total) => match total.try_add(measure.clone(), density) {
+                    Ok(()) => total,
+                    Err(_) => continue,
+                },
+            };
+            *existing = existing.clone().with_measure(total);
+            return;
+        }
         self.0.push(elem);
     }
 
     pub fn from_input_string(s: &str) -> Self {
         Self::from(s)
     }
+
+    /// Parses raw ingredient lines, such as "135g/4¾oz plain flour".
+    pub fn from_lines<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        lines
+            .into_iter()
+            .map(|line| Ingredient::parse(line.as_ref()))
+            .collect()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &Name> {
+        self.0.iter().map(Ingredient::name)
+    }
+
+    pub fn to_system(self, system: UnitSystem) -> Self {
+        self.0
+            .into_iter()
+            .map(|ingredient| ingredient.to_system(system))
+            .collect()
+    }
+
+    /// Multiplies every ingredient's quantity by `factor`.
+    pub fn scale(self, factor: f64) -> Self {
+        self.0
+            .into_iter()
+            .map(|ingredient| ingredient.scale(factor))
+            .collect()
+    }
 }
 
-impl FromIterator<Name> for Ingredients {
-    fn from_iter<I: IntoIterator<Item = Name>>(iter: I) -> Self {
+impl FromIterator<Ingredient> for Ingredients {
+    fn from_iter<I: IntoIterator<Item = Ingredient>>(iter: I) -> Self {
         let mut c = Ingredients::new();
 
         for i in iter {
@@ -71,16 +242,86 @@ impl FromIterator<Name> for Ingredients {
     }
 }
 
+impl FromIterator<Name> for Ingredients {
+    fn from_iter<I: IntoIterator<Item = Name>>(iter: I) -> Self {
+        iter.into_iter().map(Ingredient::from).collect()
+    }
+}
+
 impl From<&str> for Ingredients {
     fn from(s: &str) -> Self {
-        s.split(',').map(Name::from).collect()
+        Self::from_lines(split_ingredient_list(s))
     }
 }
 
 impl Deref for Ingredients {
-    type Target = Vec<Name>;
+    type Target = Vec<Ingredient>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Ingredients, Servings};
+
+    #[test]
+    fn test_servings_from_yield() {
+        assert_eq!(Servings::from_yield("4"), Some(Servings::new(4)));
+        assert_eq!(Servings::from_yield("Serves 4-6"), Some(Servings::new(4)));
+        assert_eq!(Servings::from_yield("serves: 2"), Some(Servings::new(2)));
+        assert_eq!(Servings::from_yield("4 servings"), Some(Servings::new(4)));
+        assert_eq!(Servings::from_yield("6–8 servings"), Some(Servings::new(6)));
+        assert_eq!(Servings::from_yield("1 serving"), Some(Servings::new(1)));
+        // Yields that count something other than servings.
+        assert_eq!(Servings::from_yield("Makes 16"), None);
+        assert_eq!(Servings::from_yield("16 cookies"), None);
+        assert_eq!(Servings::from_yield("2 loaves"), None);
+        assert_eq!(Servings::from_yield("a crowd"), None);
+        assert_eq!(Servings::from_yield("0"), None);
+    }
+
+    #[test]
+    fn test_scale_ingredients() {
+        let ingredients = Ingredients::from("200g flour, 1-2 tbsp sugar, salt");
+        let factor = Servings::new(2).scale_factor(Servings::new(3));
+
+        insta::assert_snapshot!(
+            ingredients
+                .scale(factor)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            @r###"
+        300 g flour
+        1.5-3 tbsp sugar
+        salt
+        "###
+        );
+    }
+
+    #[test]
+    fn test_repeated_ingredients() {
+        let ingredients = Ingredients::from(
+            "salt, 2 tbsp butter, 1 tsp salt, 50g butter, pepper, 2 cloves garlic, 1 tsp garlic, \
+             ½ tsp pepper, 1 clove garlic",
+        );
+
+        insta::assert_snapshot!(
+            ingredients
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            @r###"
+        1 tsp salt
+        77.33 g butter
+        0.5 tsp pepper
+        3 clove garlic
+        1 tsp garlic
+        "###
+        );
+    }
+}
@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 
 pub const SECTIONS: [&str; 5] = ["fresh", "pantry", "protein", "dairy", "freezer"];
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+/// The heading for list items that have no section.
+pub const UNSORTED: &str = "unsorted";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Section(String);
 
 impl Section {
@@ -0,0 +1,363 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::item::Name;
+
+#[derive(Error, Debug)]
+pub enum UnitError {
+    #[error("unknown unit system: {0} (expected 'metric' or 'imperial')")]
+    UnknownSystem(String),
+}
+
+/// What a unit measures. Units can only be converted within a dimension,
+/// or between mass and volume given a density.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Mass,
+    Volume,
+    Count,
+}
+
+/// A unit of measure found in recipes.
+///
+/// Spoons follow the metric 5 ml teaspoon and 15 ml tablespoon, a cup is the
+/// 240 ml US cup, and fluid ounces and pints are imperial.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum Unit {
+    Gram,
+    Kilogram,
+    Ounce,
+    Pound,
+    Millilitre,
+    Litre,
+    Teaspoon,
+    Tablespoon,
+    Cup,
+    FluidOunce,
+    Pint,
+    /// A countable portion such as a clove, slice or tin.
+    Portion(String),
+}
+
+// Unit spellings, longest match wins when parsing.
+const UNITS: [(&str, &[&str]); 23] = [
+    ("g", &["g", "gr", "gram", "grams", "gramme", "grammes"]),
+    (
+        "kg",
+        &["kg", "kgs", "kilo", "kilos", "kilogram", "kilograms"],
+    ),
+    (
+        "ml",
+        &[
+            "ml",
+            "millilitre",
+            "millilitres",
+            "milliliter",
+            "milliliters",
+        ],
+    ),
+    ("l", &["l", "litre", "litres", "liter", "liters"]),
+    ("oz", &["oz", "ounce", "ounces"]),
+    (
+        "fl oz",
+        &["fl oz", "fl. oz", "floz", "fluid ounce", "fluid ounces"],
+    ),
+    ("lb", &["lb", "lbs", "pound", "pounds"]),
+    ("tsp", &["tsp", "tsps", "teaspoon", "teaspoons"]),
+    (
+        "tbsp",
+        &["tbsp", "tbsps", "tbs", "tablespoon", "tablespoons"],
+    ),
+    ("cup", &["cup", "cups"]),
+    ("pint", &["pint", "pints", "pt"]),
+    ("clove", &["clove", "cloves"]),
+    ("slice", &["slice", "slices"]),
+    ("pinch", &["pinch", "pinches"]),
+    ("handful", &["handful", "handfuls"]),
+    ("bunch", &["bunch", "bunches"]),
+    ("sprig", &["sprig", "sprigs"]),
+    ("can", &["can", "cans"]),
+    ("tin", &["tin", "tins"]),
+    ("pack", &["pack", "packs", "packet", "packets"]),
+    ("piece", &["piece", "pieces"]),
+    ("stick", &["stick", "sticks"]),
+    ("dash", &["dash", "dashes"]),
+];
+
+impl Unit {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Gram => "g",
+            Self::Kilogram => "kg",
+            Self::Ounce => "oz",
+            Self::Pound => "lb",
+            Self::Millilitre => "ml",
+            Self::Litre => "l",
+            Self::Teaspoon => "tsp",
+            Self::Tablespoon => "tbsp",
+            Self::Cup => "cup",
+            Self::FluidOunce => "fl oz",
+            Self::Pint => "pint",
+            Self::Portion(portion) => portion,
+        }
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            Self::Gram | Self::Kilogram | Self::Ounce | Self::Pound => Dimension::Mass,
+            Self::Millilitre
+            | Self::Litre
+            | Self::Teaspoon
+            | Self::Tablespoon
+            | Self::Cup
+            | Self::FluidOunce
+            | Self::Pint => Dimension::Volume,
+            Self::Portion(_) => Dimension::Count,
+        }
+    }
+
+    // Grams per unit of mass, or millilitres per unit of volume.
+    fn base_factor(&self) -> Option<f64> {
+        Some(match self {
+            Self::Gram | Self::Millilitre => 1.0,
+            Self::Kilogram | Self::Litre => 1000.0,
+            Self::Ounce => 28.349_523,
+            Self::Pound => 453.592_37,
+            Self::Teaspoon => 5.0,
+            Self::Tablespoon => 15.0,
+            Self::Cup => 240.0,
+            Self::FluidOunce => 28.413_062,
+            Self::Pint => 568.261_25,
+            Self::Portion(_) => return None,
+        })
+    }
+
+    /// The factor to multiply an amount in this unit by to express it in
+    /// `other`, using `density` to cross between mass and volume.
+    pub fn conversion_factor(&self, other: &Unit, density: Option<Density>) -> Option<f64> {
+        if self == other {
+            return Some(1.0);
+        }
+
+        let from = self.base_factor()?;
+        let to = other.base_factor()?;
+
+        match (self.dimension(), other.dimension()) {
+            (a, b) if a == b => Some(from / to),
+            (Dimension::Volume, Dimension::Mass) => Some(from * density?.0 / to),
+            (Dimension::Mass, Dimension::Volume) => Some(from / density?.0 / to),
+            _ => None,
+        }
+    }
+
+    /// Parses a unit at the start of `s`, returning the rest of `s`.
+    pub fn parse_prefix(s: &str) -> Option<(Unit, &str)> {
+        let trimmed = s.trim_start();
+
+        // Aliases are ASCII, so matching them against `trimmed` itself keeps
+        // their lengths valid offsets into it.
+        let (unit, alias) = UNITS
+            .iter()
+            .flat_map(|(unit, aliases)| aliases.iter().map(move |alias| (*unit, *alias)))
+            .filter(|(_, alias)| {
+                trimmed
+                    .get(..alias.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(alias))
+                    && !trimmed[alias.len()..]
+                        .chars()
+                        .next()
+                        .is_some_and(char::is_alphabetic)
+            })
+            .max_by_key(|(_, alias)| alias.len())?;
+
+        let rest = &trimmed[alias.len()..];
+        Some((Unit::from(unit), rest.strip_prefix('.').unwrap_or(rest)))
+    }
+}
+
+impl From<&str> for Unit {
+    fn from(s: &str) -> Self {
+        let s = s.trim().to_lowercase();
+        let canonical = UNITS
+            .iter()
+            .find(|(_, aliases)| aliases.contains(&s.as_str()))
+            .map(|(unit, _)| *unit)
+            .unwrap_or(s.as_str());
+
+        match canonical {
+            "g" => Self::Gram,
+            "kg" => Self::Kilogram,
+            "oz" => Self::Ounce,
+            "lb" => Self::Pound,
+            "ml" => Self::Millilitre,
+            "l" => Self::Litre,
+            "tsp" => Self::Teaspoon,
+            "tbsp" => Self::Tablespoon,
+            "cup" => Self::Cup,
+            "fl oz" => Self::FluidOunce,
+            "pint" => Self::Pint,
+            portion => Self::Portion(portion.to_string()),
+        }
+    }
+}
+
+impl From<String> for Unit {
+    fn from(s: String) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl From<Unit> for String {
+    fn from(unit: Unit) -> Self {
+        unit.as_str().to_string()
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Grams per millilitre, used to convert an item between mass and volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Density(f64);
+
+// Approximate densities of common ingredients, in grams per millilitre.
+const DENSITIES: [(&str, f64); 16] = [
+    ("butter", 0.911),
+    ("caster sugar", 0.81),
+    ("cocoa powder", 0.42),
+    ("cream", 1.0),
+    ("double cream", 1.0),
+    ("flour", 0.53),
+    ("granulated sugar", 0.85),
+    ("honey", 1.42),
+    ("icing sugar", 0.56),
+    ("milk", 1.03),
+    ("oil", 0.92),
+    ("olive oil", 0.91),
+    ("plain flour", 0.53),
+    ("rice", 0.85),
+    ("sugar", 0.85),
+    ("water", 1.0),
+];
+
+impl Density {
+    pub fn new(grams_per_ml: f64) -> Self {
+        Self(grams_per_ml)
+    }
+
+    /// The density of a common ingredient, if known.
+    pub fn for_item(name: &Name) -> Option<Self> {
+        DENSITIES
+            .iter()
+            .find(|(item, _)| *item == name.as_str())
+            .map(|(_, density)| Self(*density))
+    }
+}
+
+/// The system of units to show amounts in. Imperial volumes are British
+/// fluid ounces and pints, not US cups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+impl FromStr for UnitSystem {
+    type Err = UnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "metric" => Ok(Self::Metric),
+            "imperial" => Ok(Self::Imperial),
+            other => Err(UnitError::UnknownSystem(other.to_string())),
+        }
+    }
+}
+
+impl UnitSystem {
+    /// The unit this system would use for an amount of `unit`, given the
+    /// amount's size in grams or millilitres. Spoon measures are used by
+    /// both systems and are left alone in metric.
+    pub(crate) fn preferred_unit(&self, unit: &Unit, base_amount: f64) -> Unit {
+        match (self, unit.dimension()) {
+            (_, Dimension::Count) => unit.clone(),
+            (Self::Metric, Dimension::Mass) if base_amount >= 1000.0 => Unit::Kilogram,
+            (Self::Metric, Dimension::Mass) => Unit::Gram,
+            (Self::Metric, Dimension::Volume) => match unit {
+                Unit::Teaspoon | Unit::Tablespoon => unit.clone(),
+                _ if base_amount >= 1000.0 => Unit::Litre,
+                _ => Unit::Millilitre,
+            },
+            (Self::Imperial, Dimension::Mass) if base_amount >= 453.592_37 => Unit::Pound,
+            (Self::Imperial, Dimension::Mass) => Unit::Ounce,
+            (Self::Imperial, Dimension::Volume) if base_amount >= 568.261_25 => Unit::Pint,
+            (Self::Imperial, Dimension::Volume) if base_amount >= 28.413_062 => Unit::FluidOunce,
+            (Self::Imperial, Dimension::Volume) if base_amount >= 15.0 => Unit::Tablespoon,
+            (Self::Imperial, Dimension::Volume) => Unit::Teaspoon,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prefix() {
+        assert_eq!(
+            Unit::parse_prefix("fl oz milk"),
+            Some((Unit::FluidOunce, " milk"))
+        );
+        assert_eq!(
+            Unit::parse_prefix("tbsp. sugar"),
+            Some((Unit::Tablespoon, " sugar"))
+        );
+        assert_eq!(
+            Unit::parse_prefix("cloves garlic"),
+            Some((Unit::Portion("clove".to_string()), " garlic"))
+        );
+        assert_eq!(Unit::parse_prefix("large egg"), None);
+        assert_eq!(
+            Unit::parse_prefix("TBSP sugar"),
+            Some((Unit::Tablespoon, " sugar"))
+        );
+        // A Kelvin sign, which is longer than the "k" it lowercases to.
+        assert_eq!(Unit::parse_prefix("\u{212A}g flour"), None);
+    }
+
+    #[test]
+    fn test_conversion_factor() {
+        let factor = |from: Unit, to: Unit, density| from.conversion_factor(&to, density);
+
+        assert_eq!(factor(Unit::Tablespoon, Unit::Teaspoon, None), Some(3.0));
+        assert_eq!(factor(Unit::Kilogram, Unit::Gram, None), Some(1000.0));
+        assert!((factor(Unit::Pound, Unit::Ounce, None).unwrap() - 16.0).abs() < 1e-6);
+        assert_eq!(factor(Unit::Cup, Unit::Gram, None), None);
+        assert_eq!(
+            factor(Unit::Millilitre, Unit::Gram, Some(Density::new(0.5))),
+            Some(0.5)
+        );
+        assert_eq!(
+            factor(
+                Unit::Portion("clove".to_string()),
+                Unit::Portion("slice".to_string()),
+                None
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unit_round_trip() {
+        for unit in ["g", "fl oz", "tbsp", "clove"] {
+            assert_eq!(String::from(Unit::from(unit)), unit);
+        }
+        assert_eq!(Unit::from("Tablespoons"), Unit::Tablespoon);
+    }
+}
@@ -1,5 +1,8 @@
+use std::path::PathBuf;
+
 use api::ApiError;
-use clap::{builder::NonEmptyStringValueParser, Arg, Command, ValueHint};
+use clap::{builder::NonEmptyStringValueParser, value_parser, Arg, ArgAction, Command, ValueHint};
+use common::export::MEAL_PLAN_ICS_PATH;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -7,9 +10,27 @@ pub enum CliError {
     #[error("API error: {0}")]
     ApiError(#[from] ApiError),
 
+    #[error("date error: {0}")]
+    DateError(#[from] common::date::DateError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     #[error("invalid input: {0}")]
     ParseInputError(String),
 
+    #[error("plan error: {0}")]
+    PlanError(#[from] common::plan::PlanError),
+
+    #[error("search error: {0}")]
+    QueryError(#[from] common::query::QueryError),
+
+    #[error("unit error: {0}")]
+    UnitError(#[from] common::unit::UnitError),
+
+    #[error("unexpected response when reading {0}")]
+    UnexpectedResponse(&'static str),
+
     #[error("URL parse error: {0}")]
     UrlParseError(#[from] url::ParseError),
 }
@@ -49,6 +70,101 @@ fn item() -> Arg {
         .help("item name")
 }
 
+fn list_name() -> Arg {
+    Arg::new("list")
+        .long("list")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .default_value(common::list::DEFAULT_LIST)
+        .help("which list to use")
+}
+
+fn from_list() -> Arg {
+    Arg::new("from")
+        .long("from")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("copies the items and recipes of another list into the new list")
+}
+
+fn store_name() -> Arg {
+    Arg::new("store")
+        .long("store")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("which store's layout to use")
+}
+
+fn aisles() -> Arg {
+    Arg::new("aisles")
+        .long("aisles")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("comma-separated aisles in the order they're walked, e.g. 'produce, bread, dairy'")
+}
+
+fn aisle() -> Arg {
+    Arg::new("aisle")
+        .long("aisle")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("one of the store's aisles")
+}
+
+fn check() -> Arg {
+    Arg::new("check")
+        .long("check")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("checks an item off the list")
+}
+
+fn uncheck() -> Arg {
+    Arg::new("uncheck")
+        .long("uncheck")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("puts a checked item back on the list")
+}
+
+fn remaining() -> Arg {
+    Arg::new("remaining")
+        .long("remaining")
+        .action(ArgAction::SetTrue)
+        .help("shows only the items not yet checked off")
+}
+
+fn archive() -> Arg {
+    Arg::new("archive")
+        .long("archive")
+        .action(ArgAction::SetTrue)
+        .help("archives the list so it can no longer be changed")
+}
+
+fn new_name() -> Arg {
+    Arg::new("name")
+        .long("name")
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("provides new name")
+}
+
+fn replacement() -> Arg {
+    Arg::new("with")
+        .long("with")
+        .required(true)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("provides the replacement ingredient, e.g. '3 large eggs'")
+}
+
 fn recipe() -> Arg {
     Arg::new("recipe")
         .long("recipe")
@@ -65,6 +181,30 @@ fn section() -> Arg {
         .help("provides item's section")
 }
 
+fn create_section() -> Arg {
+    Arg::new("create-section")
+        .long("create-section")
+        .action(ArgAction::SetTrue)
+        .requires("section")
+        .help("creates the item's section if it doesn't exist yet")
+}
+
+fn into_section() -> Arg {
+    Arg::new("into")
+        .long("into")
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("provides the section to merge into")
+}
+
+fn servings() -> Arg {
+    Arg::new("servings")
+        .long("servings")
+        .num_args(1)
+        .value_parser(clap::value_parser!(u32).range(1..))
+        .help("how many the recipe serves, scaling its quantities to match")
+}
+
 fn url() -> Arg {
     Arg::new("url")
         .long("url")
@@ -76,12 +216,61 @@ fn url() -> Arg {
         )
 }
 
+fn amount() -> Arg {
+    Arg::new("amount")
+        .long("amount")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("how much of the item there is, e.g. '500 g' or '6'")
+}
+
+fn best_before() -> Arg {
+    Arg::new("best-before")
+        .long("best-before")
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("the date the item is best eaten by, as YYYY-MM-DD")
+}
+
+fn day(id: &'static str) -> Arg {
+    Arg::new(id)
+        .long(id)
+        .num_args(1)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help(
+            "a weekday, 'today', 'tomorrow' or YYYY-MM-DD, where a weekday is the next one to come",
+        )
+}
+
+fn meal(id: &'static str) -> Arg {
+    Arg::new(id)
+        .long(id)
+        .num_args(1)
+        .value_parser(["breakfast", "lunch", "dinner"])
+        .help("which meal of the day")
+}
+
 fn clear_checklist() -> Command {
     Command::new("clear")
         .subcommand_required(false)
         .about("delete everything from checklist")
 }
 
+fn clear_list() -> Command {
+    Command::new("clear")
+        .subcommand_required(false)
+        .about("delete everything from list")
+}
+
+fn clear_checked() -> Command {
+    Command::new("checked")
+        .subcommand_required(false)
+        .about("delete the items checked off the list")
+}
+
 fn refresh_list() -> Command {
     Command::new("clear")
         .subcommand_required(false)
@@ -98,18 +287,85 @@ fn sections() -> Command {
     Command::new("sections").about("see sections")
 }
 
+fn section_order() -> Arg {
+    Arg::new("order")
+        .long("order")
+        .required(true)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("comma-separated sections in the order to shop them, e.g. 'fresh, dairy, pantry'")
+}
+
+fn duplicates() -> Command {
+    Command::new("duplicates").about("find items that are spelling variants of each other")
+}
+
+fn suggestions() -> Command {
+    Command::new("suggestions").about("see the sections suggested for new items")
+}
+
+fn alias() -> Arg {
+    Arg::new("alias")
+        .long("alias")
+        .required(true)
+        .value_hint(ValueHint::Unknown)
+        .value_parser(NonEmptyStringValueParser::new())
+        .help("another name for the item, e.g. 'scallions' for 'spring onions'")
+}
+
+fn alias_command() -> Command {
+    Command::new("alias")
+        .about("work with item aliases")
+        .arg(alias())
+}
+
+fn section_command() -> Command {
+    Command::new("section")
+        .about("work with a section")
+        .arg(section().required(true).help("provides section name"))
+}
+
 fn checklist() -> Command {
     Command::new("checklist")
         .about("work with the checklist")
         .arg(item())
 }
 
+fn pantry() -> Command {
+    Command::new("pantry")
+        .about("work with what's at home")
+        .arg(item().required(true))
+}
+
+fn plan() -> Command {
+    Command::new("plan")
+        .about("work with the meal plan")
+        .arg(day("day").required(true))
+        .arg(meal("meal").default_value("dinner"))
+}
+
 fn read_list() -> Command {
-    Command::new("list").about("read the list")
+    Command::new("list")
+        .about("read the list")
+        .arg(list_name())
+        .arg(remaining())
+        .arg(store_name().conflicts_with("remaining"))
+}
+
+fn store_command() -> Command {
+    Command::new("store")
+        .about("work with a store's layout")
+        .arg(store_name().required(true))
+}
+
+fn read_lists() -> Command {
+    Command::new("lists").about("read the names of all lists")
 }
 
 fn list() -> Command {
-    Command::new("list").about("work with the list")
+    Command::new("list")
+        .about("work with the list")
+        .arg(list_name())
 }
 
 fn add() -> Command {
@@ -118,10 +374,45 @@ fn add() -> Command {
         .about("add stuff")
         .arg(item())
         .arg(section())
+        .arg(create_section())
         .arg(recipe())
         .arg(ingredients())
+        .arg(servings())
         .arg(checklist_item())
-        .subcommand(list().arg(item()).arg(recipe()))
+        .subcommand(
+            list()
+                .about("add to a list, or create a list when given no item or recipe")
+                .arg(item())
+                .arg(recipe())
+                .arg(servings())
+                .arg(from_list())
+                .arg(
+                    day("plan-from")
+                        .conflicts_with_all(["item", "recipe", "from"])
+                        .help("adds the recipes planned from this day, e.g. 'monday'"),
+                )
+                .arg(day("plan-to").requires("plan-from").help(
+                    "the last day of the plan to add, by default the Sunday after --plan-from",
+                )),
+        )
+        .subcommand(
+            alias_command()
+                .about("add another name for an item")
+                .arg(item().required(true)),
+        )
+        .subcommand(section_command())
+        .subcommand(store_command().arg(aisles()))
+        .subcommand(
+            pantry()
+                .about("stock the pantry with an item")
+                .arg(amount())
+                .arg(best_before()),
+        )
+        .subcommand(
+            plan()
+                .about("plan a recipe for a meal, replacing whatever was planned")
+                .arg(recipe().required(true)),
+        )
 }
 
 fn delete() -> Command {
@@ -136,7 +427,19 @@ fn delete() -> Command {
         )
         .arg(recipe())
         .arg(item())
-        .subcommand(list().arg(recipe()).arg(item()))
+        .subcommand(
+            list()
+                .about("delete from a list, or delete a list when given only --list")
+                .subcommand(clear_list())
+                .subcommand(clear_checked())
+                .arg(recipe())
+                .arg(item()),
+        )
+        .subcommand(alias_command().about("delete an item alias"))
+        .subcommand(section_command().about("delete a section, leaving its items unsorted"))
+        .subcommand(store_command())
+        .subcommand(pantry().about("remove an item from the pantry"))
+        .subcommand(plan().about("take a meal off the plan"))
 }
 
 fn fetch() -> Command {
@@ -146,14 +449,90 @@ fn fetch() -> Command {
         .arg(url())
 }
 
+fn search() -> Command {
+    Command::new("search")
+        .about("search items, recipes and sections, allowing for misspellings")
+        .arg(
+            Arg::new("query")
+                .required(true)
+                .value_parser(NonEmptyStringValueParser::new())
+                .help("what to search for, e.g. 'tomato'"),
+        )
+        .arg(
+            Arg::new("full-text")
+                .long("full-text")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "search recipes, their ingredients and notes with AND, OR, NOT and \
+                    prefix* terms, e.g. 'chickpea* NOT coriander'",
+                ),
+        )
+}
+
+fn cookable() -> Command {
+    Command::new("cookable")
+        .about("rank recipes by how many of their ingredients you have, from the pantry unless given --have or --checklist")
+        .arg(
+            Arg::new("have")
+                .long("have")
+                .num_args(1)
+                .value_hint(ValueHint::Unknown)
+                .value_parser(NonEmptyStringValueParser::new())
+                .help("comma-separated items you have, e.g. 'eggs, milk'"),
+        )
+        .arg(
+            Arg::new("checklist")
+                .long("checklist")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("have")
+                .help("take the items on the checklist as what you have"),
+        )
+}
+
+fn expiring() -> Command {
+    Command::new("expiring")
+        .about("read what's in the pantry going off soon, and recipes that use it up")
+        .arg(
+            Arg::new("within")
+                .long("within")
+                .num_args(1)
+                .value_hint(ValueHint::Unknown)
+                .value_parser(NonEmptyStringValueParser::new())
+                .default_value("3d")
+                .help("how far ahead to look, in days or weeks, e.g. '3d' or '1w'"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("print the report as JSON"),
+        )
+}
+
 fn read() -> Command {
     Command::new("read")
         .subcommand_required(false)
         .about("read stuff")
         .arg(item())
         .arg(recipe())
+        .arg(servings())
         .subcommand(read_list())
+        .subcommand(read_lists())
+        .subcommand(Command::new("aliases").about("read every item alias"))
+        .subcommand(duplicates())
+        .subcommand(cookable())
+        .subcommand(store_command())
+        .subcommand(Command::new("stores").about("read the names of all stores"))
         .subcommand(checklist())
+        .subcommand(Command::new("pantry").about("read what's in the pantry"))
+        .subcommand(expiring())
+        .subcommand(
+            Command::new("plan")
+                .about("read the meal plan for the next seven days, or for a week")
+                .arg(day("week").help(
+                    "any day of the week to read, Monday to Sunday, rather than the next seven days",
+                )),
+        )
         .subcommand(read_all_items())
         .subcommand(
             Command::new("recipes")
@@ -161,6 +540,7 @@ fn read() -> Command {
                 .about("read all recipes"),
         )
         .subcommand(sections())
+        .subcommand(suggestions())
 }
 
 fn update() -> Command {
@@ -168,36 +548,152 @@ fn update() -> Command {
         .subcommand_required(false)
         .about("update stuff")
         .arg(item())
+        .arg(new_name())
+        .arg(section())
+        .arg(
+            into_section()
+                .requires("item")
+                .conflicts_with_all(["name", "section"])
+                .help("merges the item into this one, keeping its name as an alias"),
+        )
         .subcommand(
             Command::new("recipe")
                 .subcommand_required(false)
                 .about("update recipe")
-                .arg(recipe())
+                .arg(recipe().required(true))
                 .arg(ingredient())
+                .arg(new_name())
                 .subcommand(
                     Command::new("delete-ingredient")
                         .about("delete an ingredient from a recipe")
-                        .arg(ingredient()),
+                        .arg(ingredient().required(true)),
                 )
                 .subcommand(
                     Command::new("edit-ingredient")
                         .about("edits an ingredient in a recipe")
-                        .arg(ingredient()),
+                        .arg(ingredient().required(true))
+                        .arg(replacement()),
                 ),
         )
-        .subcommand(list().subcommand(refresh_list()))
+        .subcommand(
+            section_command()
+                .about("rename a section, or merge it into another")
+                .arg(new_name().conflicts_with("into"))
+                .arg(into_section()),
+        )
+        .subcommand(duplicates().about("merge each group of duplicate items into its oldest item"))
+        .subcommand(
+            suggestions()
+                .about("confirm or correct the sections suggested for new items")
+                .arg(item().help("confirms the section suggested for this item"))
+                .arg(
+                    section()
+                        .requires("item")
+                        .help("gives the item this section instead"),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("item")
+                        .help("confirms every suggested section"),
+                ),
+        )
+        .subcommand(
+            sections()
+                .about("set the order sections are shown in on the list")
+                .arg(section_order()),
+        )
+        .subcommand(
+            store_command()
+                .arg(aisles())
+                .arg(section())
+                .arg(item())
+                .arg(aisle()),
+        )
+        .subcommand(
+            pantry()
+                .about("use an item from the pantry, or record how much of it is left")
+                .arg(
+                    Arg::new("use")
+                        .long("use")
+                        .num_args(1)
+                        .value_hint(ValueHint::Unknown)
+                        .value_parser(NonEmptyStringValueParser::new())
+                        .conflicts_with_all(["amount", "best-before"])
+                        .help("how much of the item was used, e.g. '200 g'"),
+                )
+                .arg(amount().help("replaces how much of the item there is, with 0 removing it"))
+                .arg(best_before().requires("amount")),
+        )
+        .subcommand(
+            plan()
+                .about("move a planned meal to another day or meal, or swap it with one")
+                .arg(day("to-day").help("the day to move the meal to"))
+                .arg(meal("to-meal").help("the meal to move it to, by default the same meal"))
+                .arg(
+                    Arg::new("swap")
+                        .long("swap")
+                        .action(ArgAction::SetTrue)
+                        .help("swaps the two meals rather than refusing to replace one"),
+                ),
+        )
+        .subcommand(
+            list()
+                .arg(new_name())
+                .arg(archive())
+                .arg(check())
+                .arg(uncheck())
+                .subcommand(refresh_list()),
+        )
+}
+
+fn shop() -> Command {
+    Command::new("shop")
+        .subcommand_required(true)
+        .about("get ready to shop")
+        .subcommand(
+            Command::new("prepare")
+                .about("walk through the checklist a section at a time, adding what's needed to the list")
+                .arg(list_name()),
+        )
+}
+
+fn calendar_file() -> Arg {
+    Arg::new("file")
+        .long("file")
+        .num_args(1)
+        .value_hint(ValueHint::FilePath)
+        .value_parser(value_parser!(PathBuf))
+        .default_value(MEAL_PLAN_ICS_PATH)
+        .help("the iCalendar file")
 }
 
 fn import() -> Command {
     Command::new("import")
         .subcommand_required(false)
         .about("import from 'items.json' and 'list.json' files")
+        .subcommand(
+            Command::new("plan")
+                .about(
+                    "replace the meal plan over the days an iCalendar file covers with its meals",
+                )
+                .arg(calendar_file()),
+        )
 }
 
 fn export() -> Command {
     Command::new("export")
         .subcommand_required(false)
         .about("export items to 'items.yaml' and list to 'list.yaml' files")
+        .subcommand(
+            Command::new("plan")
+                .about("export the meal plan for the next seven days, or for a week, to an iCalendar file")
+                .arg(day("week").help(
+                    "any day of the week to export, Monday to Sunday, rather than the next seven days",
+                ))
+                .arg(calendar_file()),
+        )
 }
 
 fn store() -> Arg {
@@ -209,6 +705,14 @@ fn store() -> Arg {
         .help("which database to use")
 }
 
+fn units() -> Arg {
+    Arg::new("units")
+        .long("units")
+        .num_args(1)
+        .value_parser(["metric", "imperial"])
+        .help("show amounts in metric or imperial units")
+}
+
 pub fn cli() -> Command {
     Command::new("gust")
         .about("gust: rust-powered grocery list creator")
@@ -219,7 +723,10 @@ pub fn cli() -> Command {
         .subcommand(fetch())
         .subcommand(read())
         .subcommand(update())
+        .subcommand(search())
+        .subcommand(shop())
         .subcommand(import())
         .subcommand(export())
         .arg(store())
+        .arg(units())
 }
@@ -1,25 +1,167 @@
+use std::path::PathBuf;
+
 use common::{
-    commands::{Add, ApiCommand, Delete, Read, Update},
+    commands::{Add, ApiCommand, Available, Delete, Read, Update},
+    date::{self, parse_date, parse_period, Date, DateError},
+    ingredient::{Ingredient, Measure},
     item::Name,
-    recipes::{Ingredients, Recipe},
+    layout::{Aisle, StoreName},
+    list::ListName,
+    plan::{end_of_week, parse_day, DateRange, Meal, MealSlot, PlanError},
+    query::Query,
+    recipes::{Ingredients, Recipe, Servings},
     section::Section,
 };
 
-use clap::ArgMatches;
+use clap::{parser::ValueSource, ArgMatches};
 use url::Url;
 
 use crate::CliError;
 
+/// What's asked for on the command line: either a single command for the
+/// API, or a flow that asks questions on the terminal between commands.
+pub enum Invocation {
+    Command(UserCommand),
+    /// Walks through the checklist interactively, adding what's needed to
+    /// a list.
+    PrepareShop(ListName),
+}
+
+impl TryFrom<ArgMatches> for Invocation {
+    type Error = CliError;
+
+    fn try_from(matches: ArgMatches) -> Result<Self, Self::Error> {
+        if let Some(("shop", matches)) = matches.subcommand() {
+            return match matches.subcommand() {
+                Some(("prepare", matches)) => Ok(Invocation::PrepareShop(list_name(matches))),
+                _ => unreachable!(),
+            };
+        }
+        UserCommand::try_from(matches).map(Invocation::Command)
+    }
+}
+
 pub enum UserCommand {
     Add(Add),
     Delete(Delete),
     Export,
+    ExportMealPlan { range: DateRange, path: PathBuf },
     FetchRecipe(Url),
+    FullTextSearch(Query),
     ImportFromJson,
+    ImportMealPlan(PathBuf),
     Read(Read),
+    Search(String),
     Update(Update),
 }
 
+fn servings(matches: &ArgMatches) -> Option<Servings> {
+    matches
+        .get_one::<u32>("servings")
+        .copied()
+        .map(Servings::new)
+}
+
+fn list_name(matches: &ArgMatches) -> ListName {
+    matches
+        .get_one::<String>("list")
+        .map(|list| ListName::from(list.as_str()))
+        .unwrap_or_default()
+}
+
+fn store_name(matches: &ArgMatches) -> Option<StoreName> {
+    matches
+        .get_one::<String>("store")
+        .map(|store| StoreName::from(store.as_str()))
+}
+
+fn alias(matches: &ArgMatches) -> Name {
+    Name::from(
+        matches
+            .get_one::<String>("alias")
+            .expect("alias required")
+            .as_str(),
+    )
+}
+
+fn section(matches: &ArgMatches) -> Section {
+    Section::from(
+        matches
+            .get_one::<String>("section")
+            .expect("section required")
+            .trim(),
+    )
+}
+
+fn pantry_item(matches: &ArgMatches) -> Name {
+    Name::from(
+        matches
+            .get_one::<String>("item")
+            .expect("item required")
+            .as_str(),
+    )
+}
+
+fn amount(matches: &ArgMatches, id: &str) -> Result<Option<Measure>, String> {
+    matches
+        .get_one::<String>(id)
+        .map(|amount| Measure::parse(amount).ok_or_else(|| format!("not an amount: {amount}")))
+        .transpose()
+}
+
+fn best_before(matches: &ArgMatches) -> Result<Option<Date>, DateError> {
+    matches
+        .get_one::<String>("best-before")
+        .map(|date| parse_date(date))
+        .transpose()
+}
+
+fn day(matches: &ArgMatches, id: &str) -> Result<Option<Date>, PlanError> {
+    matches
+        .get_one::<String>(id)
+        .map(|day| parse_day(day, date::today()))
+        .transpose()
+}
+
+fn meal(matches: &ArgMatches, id: &str) -> Result<Option<Meal>, PlanError> {
+    matches
+        .get_one::<String>(id)
+        .map(|meal| meal.parse())
+        .transpose()
+}
+
+// The slot named by `--day` and `--meal`.
+fn meal_slot(matches: &ArgMatches) -> Result<MealSlot, PlanError> {
+    Ok(MealSlot::new(
+        day(matches, "day")?.expect("day required"),
+        meal(matches, "meal")?.expect("'meal' has a default setting"),
+    ))
+}
+
+// The week holding `--week`, or the next seven days.
+fn plan_range(matches: &ArgMatches) -> Result<DateRange, PlanError> {
+    Ok(match day(matches, "week")? {
+        Some(day) => DateRange::week_of(day),
+        None => DateRange::week_from(date::today()),
+    })
+}
+
+fn calendar_file(matches: &ArgMatches) -> PathBuf {
+    matches
+        .get_one::<PathBuf>("file")
+        .expect("'file' has a default setting")
+        .clone()
+}
+
+fn comma_separated<'a, T: From<&'a str>>(value: &'a str) -> Vec<T> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(T::from)
+        .collect()
+}
+
 impl TryFrom<ArgMatches> for UserCommand {
     type Error = CliError;
 
@@ -30,17 +172,22 @@ impl TryFrom<ArgMatches> for UserCommand {
                     matches.get_one::<String>("recipe"),
                     matches.get_one::<String>("ingredients"),
                 ) {
-                    Add::recipe_from_name_and_ingredients(
+                    Add::recipe_with_servings(
                         Recipe::from_input_string(recipe),
                         Ingredients::from_input_string(ingredients),
+                        servings(matches),
                     )
                 } else if let Some(name) = matches.get_one::<String>("item") {
-                    Add::item_from_name_and_section(
-                        Name::from(name.as_str()),
-                        matches
-                            .get_one::<String>("section")
-                            .map(|section| Section::from(section.trim())),
-                    )
+                    let name = Name::from(name.as_str());
+                    let section = matches
+                        .get_one::<String>("section")
+                        .map(|section| Section::from(section.trim()));
+                    match section {
+                        Some(section) if matches.get_flag("create-section") => {
+                            Add::item_with_new_section(name, section)
+                        }
+                        section => Add::item_from_name_and_section(name, section),
+                    }
                 } else if let Some(item) = matches.get_one::<String>("checklist-item") {
                     Add::checklist_item_from_name(Name::from(item.as_str()))
                 } else {
@@ -52,14 +199,54 @@ impl TryFrom<ArgMatches> for UserCommand {
                                 .as_str(),
                         )),
                         Some(("list", matches)) => {
+                            let list = list_name(matches);
                             if let Some(name) = matches.get_one::<String>("recipe") {
-                                Add::list_recipe_from_name(name.as_str().into())
+                                Add::list_recipe_for_servings(
+                                    list,
+                                    name.as_str().into(),
+                                    servings(matches),
+                                )
                             } else if let Some(name) = matches.get_one::<String>("item") {
-                                Add::list_item_from_name(Name::from(name.as_str()))
+                                Add::list_item_from_name(list, Name::from(name.as_str()))
+                            } else if let Some(from) = matches.get_one::<String>("from") {
+                                Add::copy_list(ListName::from(from.as_str()), list)
+                            } else if let Some(start) = day(matches, "plan-from")? {
+                                let end = day(matches, "plan-to")?.unwrap_or(end_of_week(start));
+                                Add::list_from_plan(list, DateRange::new(start, end))
                             } else {
-                                unimplemented!()
+                                Add::list_from_name(list)
                             }
                         }
+                        Some(("alias", matches)) => Add::item_alias(
+                            Name::from(
+                                matches
+                                    .get_one::<String>("item")
+                                    .expect("item required")
+                                    .as_str(),
+                            ),
+                            alias(matches),
+                        ),
+                        Some(("section", matches)) => Add::section_from_name(section(matches)),
+                        Some(("store", matches)) => Add::store_with_aisles(
+                            store_name(matches).expect("store required"),
+                            matches
+                                .get_one::<String>("aisles")
+                                .map(|aisles| comma_separated(aisles))
+                                .unwrap_or_default(),
+                        ),
+                        Some(("pantry", matches)) => Add::pantry_item(
+                            pantry_item(matches),
+                            amount(matches, "amount").map_err(CliError::ParseInputError)?,
+                            best_before(matches)?,
+                        ),
+                        Some(("plan", matches)) => Add::planned_meal(
+                            meal_slot(matches)?,
+                            Recipe::from_input_string(
+                                matches
+                                    .get_one::<String>("recipe")
+                                    .expect("recipe required"),
+                            ),
+                        ),
                         _ => unreachable!(),
                     }
                 },
@@ -72,11 +259,39 @@ impl TryFrom<ArgMatches> for UserCommand {
                 } else {
                     match matches.subcommand() {
                         Some(("checklist", matches)) => {
-                            let Some(name) = matches.get_one::<String>("checklist-item") else {
-                                unimplemented!()
-                            };
-                            Delete::ChecklistItem(Name::from(name.as_str()))
+                            if let Some(("clear", _)) = matches.subcommand() {
+                                Delete::ClearChecklist
+                            } else {
+                                let Some(name) = matches.get_one::<String>("checklist-item") else {
+                                    unimplemented!()
+                                };
+                                Delete::ChecklistItem(Name::from(name.as_str()))
+                            }
                         }
+                        Some(("list", matches)) => {
+                            let list = list_name(matches);
+                            if let Some(("clear", _)) = matches.subcommand() {
+                                Delete::ClearList(list)
+                            } else if let Some(("checked", _)) = matches.subcommand() {
+                                Delete::ClearCheckedListItems(list)
+                            } else if let Some(name) = matches.get_one::<String>("item") {
+                                Delete::list_item_from_name(list, Name::from(name.as_str()))
+                            } else if matches.value_source("list") == Some(ValueSource::CommandLine)
+                            {
+                                Delete::list_from_name(list)
+                            } else {
+                                return Err(CliError::ParseInputError(
+                                    "provide --list to delete a whole list".to_string(),
+                                ));
+                            }
+                        }
+                        Some(("alias", matches)) => Delete::ItemAlias(alias(matches)),
+                        Some(("section", matches)) => Delete::Section(section(matches)),
+                        Some(("store", matches)) => {
+                            Delete::Store(store_name(matches).expect("store required"))
+                        }
+                        Some(("pantry", matches)) => Delete::PantryItem(pantry_item(matches)),
+                        Some(("plan", matches)) => Delete::PlannedMeal(meal_slot(matches)?),
                         _ => unimplemented!(),
                     }
                 },
@@ -90,37 +305,243 @@ impl TryFrom<ArgMatches> for UserCommand {
             }
             Some(("read", matches)) => Ok(UserCommand::Read(
                 if let Some(name) = matches.get_one::<String>("recipe") {
-                    Read::recipe_from_name(name.as_str().into())
+                    Read::recipe_for_servings(name.as_str().into(), servings(matches))
                 } else if let Some(name) = matches.get_one::<String>("item") {
                     Read::item_from_name(Name::from(name.as_str()))
                 } else {
                     match matches.subcommand() {
+                        Some(("aliases", _matches)) => Read::ItemAliases,
                         Some(("checklist", _matches)) => Read::Checklist,
-                        Some(("list", _matches)) => Read::List,
+                        Some(("cookable", matches)) => {
+                            Read::CookableRecipes(match matches.get_one::<String>("have") {
+                                Some(have) => Available::Items(comma_separated(have)),
+                                None if matches.get_flag("checklist") => Available::Checklist,
+                                None => Available::Pantry,
+                            })
+                        }
+                        Some(("duplicates", _matches)) => Read::DuplicateItems,
+                        Some(("expiring", matches)) => Read::expiring(
+                            date::today(),
+                            parse_period(
+                                matches
+                                    .get_one::<String>("within")
+                                    .expect("'within' has a default setting"),
+                            )?,
+                        ),
+                        Some(("list", matches)) if matches.get_flag("remaining") => {
+                            Read::RemainingListItems(list_name(matches))
+                        }
+                        Some(("list", matches)) => match store_name(matches) {
+                            Some(store) => Read::ListForStore {
+                                list: list_name(matches),
+                                store,
+                            },
+                            None => Read::List(list_name(matches)),
+                        },
+                        Some(("lists", _matches)) => Read::Lists,
+                        Some(("pantry", _matches)) => Read::Pantry,
+                        Some(("plan", matches)) => Read::MealPlan(plan_range(matches)?),
                         Some(("library", _matches)) => Read::All,
                         Some(("recipes", _matches)) => Read::Recipes,
                         Some(("sections", _matches)) => Read::Sections,
+                        Some(("suggestions", _matches)) => Read::SectionSuggestions,
+                        Some(("store", matches)) => {
+                            Read::Store(store_name(matches).expect("store required"))
+                        }
+                        Some(("stores", _matches)) => Read::Stores,
                         _ => Read::All,
                     }
                 },
             )),
             Some(("update", matches)) => Ok(UserCommand::Update(match matches.subcommand() {
                 Some(("recipe", matches)) => {
-                    let Some(name) = matches.get_one::<String>("recipe") else {
-                        todo!()
-                    };
-                    Update::recipe_from_name(name.as_str().into())
+                    let recipe = Recipe::from_input_string(
+                        matches
+                            .get_one::<String>("recipe")
+                            .expect("recipe required"),
+                    );
+                    match matches.subcommand() {
+                        Some(("delete-ingredient", matches)) => Update::delete_ingredient(
+                            recipe,
+                            Name::from(
+                                matches
+                                    .get_one::<String>("ingredient")
+                                    .expect("ingredient required")
+                                    .as_str(),
+                            ),
+                        ),
+                        Some(("edit-ingredient", matches)) => Update::edit_ingredient(
+                            recipe,
+                            Name::from(
+                                matches
+                                    .get_one::<String>("ingredient")
+                                    .expect("ingredient required")
+                                    .as_str(),
+                            ),
+                            Ingredient::parse(
+                                matches
+                                    .get_one::<String>("with")
+                                    .expect("replacement required"),
+                            ),
+                        ),
+                        _ => {
+                            if let Some(ingredient) = matches.get_one::<String>("ingredient") {
+                                Update::add_ingredient(recipe, Ingredient::parse(ingredient))
+                            } else if let Some(name) = matches.get_one::<String>("name") {
+                                Update::rename_recipe(recipe, Recipe::from_input_string(name))
+                            } else {
+                                return Err(CliError::ParseInputError(
+                                    "provide --ingredient or --name to update a recipe".to_string(),
+                                ));
+                            }
+                        }
+                    }
+                }
+                Some(("section", matches)) => {
+                    let section = section(matches);
+                    if let Some(name) = matches.get_one::<String>("name") {
+                        Update::rename_section(section, Section::from(name.trim()))
+                    } else if let Some(into) = matches.get_one::<String>("into") {
+                        Update::merge_section(section, Section::from(into.trim()))
+                    } else {
+                        return Err(CliError::ParseInputError(
+                            "provide --name or --into to update a section".to_string(),
+                        ));
+                    }
+                }
+                Some(("duplicates", _matches)) => Update::MergeDuplicates,
+                Some(("pantry", matches)) => {
+                    let item = pantry_item(matches);
+                    if let Some(used) = amount(matches, "use").map_err(CliError::ParseInputError)? {
+                        Update::consume_pantry_item(item, Some(used))
+                    } else if let Some(amount) =
+                        amount(matches, "amount").map_err(CliError::ParseInputError)?
+                    {
+                        Update::audit_pantry_item(item, Some(amount), best_before(matches)?)
+                    } else {
+                        return Err(CliError::ParseInputError(
+                            "provide --use or --amount to update the pantry".to_string(),
+                        ));
+                    }
+                }
+                Some(("plan", matches)) => {
+                    let from = meal_slot(matches)?;
+                    let to_day = day(matches, "to-day")?;
+                    let to_meal = meal(matches, "to-meal")?;
+                    if to_day.is_none() && to_meal.is_none() {
+                        return Err(CliError::ParseInputError(
+                            "provide --to-day or --to-meal to move a meal".to_string(),
+                        ));
+                    }
+                    let to = MealSlot::new(
+                        to_day.unwrap_or(from.date()),
+                        to_meal.unwrap_or(from.meal()),
+                    );
+                    if matches.get_flag("swap") {
+                        Update::swap_meals(from, to)
+                    } else {
+                        Update::move_meal(from, to)
+                    }
+                }
+                Some(("suggestions", matches)) => {
+                    if matches.get_flag("all") {
+                        Update::ConfirmSuggestions
+                    } else if let Some(item) = matches.get_one::<String>("item") {
+                        let item = Name::from(item.as_str());
+                        match matches.get_one::<String>("section") {
+                            Some(section) => {
+                                Update::correct_suggestion(item, Section::from(section.trim()))
+                            }
+                            None => Update::confirm_suggestion(item),
+                        }
+                    } else {
+                        return Err(CliError::ParseInputError(
+                            "provide --item or --all to review suggested sections".to_string(),
+                        ));
+                    }
+                }
+                Some(("sections", matches)) => Update::section_order(comma_separated(
+                    matches.get_one::<String>("order").expect("order required"),
+                )),
+                Some(("store", matches)) => {
+                    let store = store_name(matches).expect("store required");
+                    let aisle = matches
+                        .get_one::<String>("aisle")
+                        .map(|aisle| Aisle::from(aisle.as_str()));
+                    if let Some(aisles) = matches.get_one::<String>("aisles") {
+                        Update::store_aisles(store, comma_separated(aisles))
+                    } else if let (Some(section), Some(aisle)) =
+                        (matches.get_one::<String>("section"), aisle.clone())
+                    {
+                        Update::section_aisle(store, Section::from(section.as_str()), aisle)
+                    } else if let (Some(item), Some(aisle)) =
+                        (matches.get_one::<String>("item"), aisle)
+                    {
+                        Update::item_aisle(store, Name::from(item.as_str()), aisle)
+                    } else {
+                        return Err(CliError::ParseInputError(
+                            "provide --aisles, or --aisle with --section or --item, to update a store"
+                                .to_string(),
+                        ));
+                    }
                 }
                 Some(("list", matches)) => {
-                    let Some(("clear", _)) = matches.subcommand() else {
+                    let list = list_name(matches);
+                    if let Some(("clear", _)) = matches.subcommand() {
+                        Update::refresh_list(list)
+                    } else if let Some(name) = matches.get_one::<String>("name") {
+                        Update::rename_list(list, ListName::from(name.as_str()))
+                    } else if let Some(item) = matches.get_one::<String>("check") {
+                        Update::check_list_item(list, Name::from(item.as_str()))
+                    } else if let Some(item) = matches.get_one::<String>("uncheck") {
+                        Update::uncheck_list_item(list, Name::from(item.as_str()))
+                    } else if matches.get_flag("archive") {
+                        Update::archive_list(list)
+                    } else {
+                        return Err(CliError::ParseInputError(
+                            "provide --name, --check, --uncheck or --archive to update a list"
+                                .to_string(),
+                        ));
+                    }
+                }
+                _ => {
+                    let Some(name) = matches.get_one::<String>("item") else {
                         unimplemented!()
                     };
-                    Update::RefreshList
+                    let name = Name::from(name.as_str());
+                    match matches.get_one::<String>("into") {
+                        Some(into) => Update::merge_items(name, Name::from(into.as_str())),
+                        None => Update::item(
+                            name,
+                            matches
+                                .get_one::<String>("name")
+                                .map(|name| Name::from(name.as_str())),
+                            matches
+                                .get_one::<String>("section")
+                                .map(|section| Section::from(section.trim())),
+                        ),
+                    }
                 }
-                _ => unimplemented!(),
             })),
-            Some(("import", _)) => Ok(UserCommand::ImportFromJson),
-            Some(("export", _)) => Ok(UserCommand::Export),
+            Some(("search", matches)) => {
+                let query = matches.get_one::<String>("query").expect("query required");
+                if matches.get_flag("full-text") {
+                    Ok(UserCommand::FullTextSearch(Query::parse(query)?))
+                } else {
+                    Ok(UserCommand::Search(query.to_string()))
+                }
+            }
+            Some(("import", matches)) => match matches.subcommand() {
+                Some(("plan", matches)) => Ok(UserCommand::ImportMealPlan(calendar_file(matches))),
+                _ => Ok(UserCommand::ImportFromJson),
+            },
+            Some(("export", matches)) => match matches.subcommand() {
+                Some(("plan", matches)) => Ok(UserCommand::ExportMealPlan {
+                    range: plan_range(matches)?,
+                    path: calendar_file(matches),
+                }),
+                _ => Ok(UserCommand::Export),
+            },
             _ => unreachable!(),
         }
     }
@@ -132,9 +553,13 @@ impl From<UserCommand> for ApiCommand {
             UserCommand::Add(cmd) => Self::Add(cmd),
             UserCommand::Delete(cmd) => Self::Delete(cmd),
             UserCommand::Export => Self::Export,
+            UserCommand::ExportMealPlan { range, path } => Self::ExportMealPlan { range, path },
             UserCommand::FetchRecipe(cmd) => Self::FetchRecipe(cmd),
+            UserCommand::FullTextSearch(query) => Self::FullTextSearch(query),
             UserCommand::ImportFromJson => Self::ImportFromJson,
+            UserCommand::ImportMealPlan(path) => Self::ImportMealPlan(path),
             UserCommand::Read(cmd) => Self::Read(cmd),
+            UserCommand::Search(query) => Self::Search(query),
             UserCommand::Update(cmd) => Self::Update(cmd),
         }
     }
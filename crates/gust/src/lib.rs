@@ -4,6 +4,7 @@
 //       https://doc.rust-lang.org/book/ch07-05-separating-modules-into-different-files.html
 mod cli;
 mod command;
+mod shop;
 pub mod startup;
 
 // Note: Re-exports the content of the square_content module to keep paths short.
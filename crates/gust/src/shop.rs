@@ -0,0 +1,148 @@
+use std::fmt;
+
+use api::{ApiDispatch, ApiResponse};
+use common::{
+    commands::{Add, ApiCommand, Read},
+    input::{walk_checklist, Prompt},
+    item::Name,
+    list::{List, ListName},
+};
+
+use crate::CliError;
+
+/// The items added to a list while getting ready to shop.
+pub struct PreparedShop {
+    list: ListName,
+    added: Vec<Name>,
+}
+
+impl fmt::Display for PreparedShop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.added.is_empty() {
+            return writeln!(f, "\nnothing added to list '{}'", self.list);
+        }
+        writeln!(f, "\nadded to list '{}':", self.list)?;
+        for item in &self.added {
+            writeln!(f, "  {item}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Walks through the checklist a section at a time, asking `prompt` which
+/// items are needed, and adds those to `list`.
+pub async fn prepare(
+    api: &ApiDispatch,
+    list: ListName,
+    prompt: &mut impl Prompt,
+) -> Result<PreparedShop, CliError> {
+    let ApiResponse::Checklist(items) = api.dispatch(ApiCommand::Read(Read::Checklist)).await?
+    else {
+        return Err(CliError::UnexpectedResponse("the checklist"));
+    };
+    let ApiResponse::Sections(sections) = api.dispatch(ApiCommand::Read(Read::Sections)).await?
+    else {
+        return Err(CliError::UnexpectedResponse("sections"));
+    };
+    let checklist = items
+        .into_iter()
+        .collect::<List>()
+        .with_section_order(sections);
+
+    let added = walk_checklist(&checklist, prompt);
+    for item in &added {
+        api.dispatch(ApiCommand::Add(Add::list_item_from_name(
+            list.clone(),
+            item.clone(),
+        )))
+        .await?;
+    }
+    Ok(PreparedShop { list, added })
+}
+
+#[cfg(test)]
+mod tests {
+    use api::Api;
+    use common::{input::Need, item::Item, section::Section};
+    use persistence::store::StoreType;
+
+    use super::*;
+
+    // Answers from a script, keeping track of the sections asked about.
+    struct ScriptedPrompt {
+        answers: Vec<(&'static str, Need)>,
+        sections: Vec<String>,
+    }
+
+    impl Prompt for ScriptedPrompt {
+        fn start_section(&mut self, section: &str) {
+            self.sections.push(section.to_string());
+        }
+
+        fn need_item(&mut self, item: &Item) -> Need {
+            self.answers
+                .iter()
+                .find(|(name, _)| item.name() == &Name::from(*name))
+                .map_or(Need::No, |(_, need)| *need)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prepare() {
+        let api = Api::init(StoreType::SqliteInMem).await.unwrap();
+        for (item, section) in [
+            ("apples", "fresh"),
+            ("bananas", "fresh"),
+            ("milk", "dairy"),
+            ("yoghurt", "dairy"),
+            ("rice", "pantry"),
+        ] {
+            api.dispatch(ApiCommand::Add(Add::item_from_name_and_section(
+                Name::from(item),
+                Some(Section::from(section)),
+            )))
+            .await
+            .unwrap();
+            api.dispatch(ApiCommand::Add(Add::checklist_item_from_name(Name::from(
+                item,
+            ))))
+            .await
+            .unwrap();
+        }
+
+        let mut prompt = ScriptedPrompt {
+            answers: vec![
+                ("apples", Need::Yes),
+                ("bananas", Need::No),
+                ("milk", Need::SkipSection),
+                ("yoghurt", Need::Yes),
+                ("rice", Need::Yes),
+            ],
+            sections: Vec::new(),
+        };
+        let list = ListName::default();
+        let prepared = prepare(&api, list.clone(), &mut prompt).await.unwrap();
+
+        assert_eq!(prompt.sections, ["fresh", "pantry", "dairy"]);
+        insta::assert_snapshot!(prepared.to_string().trim_start(), @r###"
+        added to list 'default':
+          apples
+          rice
+        "###);
+
+        let ApiResponse::List(list) = api
+            .dispatch(ApiCommand::Read(Read::List(list)))
+            .await
+            .unwrap()
+        else {
+            todo!()
+        };
+        let mut names = list
+            .items()
+            .iter()
+            .map(|item| item.name().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["apples", "rice"]);
+    }
+}
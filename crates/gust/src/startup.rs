@@ -1,5 +1,6 @@
-use crate::{cli, command::UserCommand, CliError};
+use crate::{cli, command::Invocation, shop, CliError};
 use api::{Api, ApiError};
+use common::{input::TerminalPrompt, unit::UnitSystem};
 use tracing::instrument;
 
 #[instrument]
@@ -15,11 +16,34 @@ pub async fn run() -> Result<(), CliError> {
     )
     .await?;
 
-    let command: UserCommand = matches.try_into()?;
+    let units = matches
+        .get_one::<String>("units")
+        .map(|units| units.parse::<UnitSystem>())
+        .transpose()?;
 
-    let response = api.dispatch(command.into()).await?;
+    let json = matches
+        .subcommand_matches("read")
+        .and_then(|matches| matches.subcommand_matches("expiring"))
+        .is_some_and(|matches| matches.get_flag("json"));
 
-    println!("{response}");
+    let command = match matches.try_into()? {
+        Invocation::Command(command) => command,
+        Invocation::PrepareShop(list) => {
+            println!("{}", shop::prepare(&api, list, &mut TerminalPrompt).await?);
+            return Ok(());
+        }
+    };
+
+    let mut response = api.dispatch(command.into()).await?;
+
+    if let Some(units) = units {
+        response = response.to_system(units);
+    }
+
+    match json.then(|| response.to_json()).transpose()?.flatten() {
+        Some(json) => println!("{json}"),
+        None => println!("{response}"),
+    }
 
     Ok(())
 }
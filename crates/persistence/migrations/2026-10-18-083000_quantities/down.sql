@@ -0,0 +1,17 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE list_items (
+    id INTEGER NOT NULL,
+    PRIMARY KEY (id),
+    FOREIGN KEY (id) REFERENCES items (id)
+);
+
+INSERT OR IGNORE INTO list_items (id) SELECT id FROM list;
+
+DROP TABLE list;
+
+ALTER TABLE list_items RENAME TO list;
+
+ALTER TABLE items_recipes DROP COLUMN preparation;
+ALTER TABLE items_recipes DROP COLUMN unit;
+ALTER TABLE items_recipes DROP COLUMN quantity_max;
+ALTER TABLE items_recipes DROP COLUMN quantity;
@@ -0,0 +1,21 @@
+ALTER TABLE items_recipes ADD COLUMN quantity DOUBLE;
+ALTER TABLE items_recipes ADD COLUMN quantity_max DOUBLE;
+ALTER TABLE items_recipes ADD COLUMN unit TEXT;
+ALTER TABLE items_recipes ADD COLUMN preparation TEXT;
+
+-- An item can be on the list once per unit, e.g. "100 g" and "2 tbsp" of butter.
+-- Unquantified items, and counts such as "2 eggs", use the empty unit.
+CREATE TABLE list_quantities (
+    id INTEGER NOT NULL,
+    unit TEXT NOT NULL DEFAULT '',
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    PRIMARY KEY (id, unit),
+    FOREIGN KEY (id) REFERENCES items (id)
+);
+
+INSERT INTO list_quantities (id) SELECT id FROM list;
+
+DROP TABLE list;
+
+ALTER TABLE list_quantities RENAME TO list;
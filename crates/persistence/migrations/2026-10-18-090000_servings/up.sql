@@ -0,0 +1,2 @@
+-- How many a recipe serves, used to scale its ingredient quantities.
+ALTER TABLE recipes ADD COLUMN servings INTEGER;
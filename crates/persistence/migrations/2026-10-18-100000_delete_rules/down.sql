@@ -0,0 +1,72 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE old_items_recipes (
+    item_id INTEGER NOT NULL,
+    recipe_id INTEGER NOT NULL,
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    unit TEXT,
+    preparation TEXT,
+    PRIMARY KEY (item_id, recipe_id),
+    FOREIGN KEY (item_id) REFERENCES items (id),
+    FOREIGN KEY (recipe_id) REFERENCES recipes (id)
+);
+
+INSERT INTO old_items_recipes
+SELECT item_id, recipe_id, quantity, quantity_max, unit, preparation FROM items_recipes;
+
+DROP TABLE items_recipes;
+
+ALTER TABLE old_items_recipes RENAME TO items_recipes;
+
+CREATE TABLE old_list_recipes (
+    id INTEGER NOT NULL,
+    PRIMARY KEY (id),
+    FOREIGN KEY (id) REFERENCES recipes (id)
+);
+
+INSERT INTO old_list_recipes SELECT id FROM list_recipes;
+
+DROP TABLE list_recipes;
+
+ALTER TABLE old_list_recipes RENAME TO list_recipes;
+
+CREATE TABLE old_list (
+    id INTEGER NOT NULL,
+    unit TEXT NOT NULL DEFAULT '',
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    PRIMARY KEY (id, unit),
+    FOREIGN KEY (id) REFERENCES items (id)
+);
+
+INSERT INTO old_list SELECT id, unit, quantity, quantity_max FROM list;
+
+DROP TABLE list;
+
+ALTER TABLE old_list RENAME TO list;
+
+CREATE TABLE old_checklist (
+    id INTEGER NOT NULL,
+    PRIMARY KEY (id),
+    FOREIGN KEY (id) REFERENCES items (id)
+);
+
+INSERT INTO old_checklist SELECT id FROM checklist;
+
+DROP TABLE checklist;
+
+ALTER TABLE old_checklist RENAME TO checklist;
+
+CREATE TABLE old_items_sections (
+    item_id INTEGER NOT NULL,
+    section_id INTEGER NOT NULL,
+    PRIMARY KEY (item_id, section_id),
+    FOREIGN KEY (item_id) REFERENCES items (id),
+    FOREIGN KEY (section_id) REFERENCES sections (id)
+);
+
+INSERT INTO old_items_sections SELECT item_id, section_id FROM items_sections;
+
+DROP TABLE items_sections;
+
+ALTER TABLE old_items_sections RENAME TO items_sections;
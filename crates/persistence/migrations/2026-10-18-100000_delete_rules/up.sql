@@ -0,0 +1,80 @@
+-- Deleting a recipe removes its ingredient links and its place on the list,
+-- while an item can't be deleted as long as anything still refers to it.
+-- Rows left pointing at deleted items or recipes are dropped on the way.
+CREATE TABLE new_items_recipes (
+    item_id INTEGER NOT NULL,
+    recipe_id INTEGER NOT NULL,
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    unit TEXT,
+    preparation TEXT,
+    PRIMARY KEY (item_id, recipe_id),
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE RESTRICT,
+    FOREIGN KEY (recipe_id) REFERENCES recipes (id) ON DELETE CASCADE
+);
+
+INSERT INTO new_items_recipes
+SELECT item_id, recipe_id, quantity, quantity_max, unit, preparation FROM items_recipes
+WHERE item_id IN (SELECT id FROM items) AND recipe_id IN (SELECT id FROM recipes);
+
+DROP TABLE items_recipes;
+
+ALTER TABLE new_items_recipes RENAME TO items_recipes;
+
+CREATE TABLE new_list_recipes (
+    id INTEGER NOT NULL,
+    PRIMARY KEY (id),
+    FOREIGN KEY (id) REFERENCES recipes (id) ON DELETE CASCADE
+);
+
+INSERT INTO new_list_recipes
+SELECT id FROM list_recipes WHERE id IN (SELECT id FROM recipes);
+
+DROP TABLE list_recipes;
+
+ALTER TABLE new_list_recipes RENAME TO list_recipes;
+
+CREATE TABLE new_list (
+    id INTEGER NOT NULL,
+    unit TEXT NOT NULL DEFAULT '',
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    PRIMARY KEY (id, unit),
+    FOREIGN KEY (id) REFERENCES items (id) ON DELETE RESTRICT
+);
+
+INSERT INTO new_list
+SELECT id, unit, quantity, quantity_max FROM list WHERE id IN (SELECT id FROM items);
+
+DROP TABLE list;
+
+ALTER TABLE new_list RENAME TO list;
+
+CREATE TABLE new_checklist (
+    id INTEGER NOT NULL,
+    PRIMARY KEY (id),
+    FOREIGN KEY (id) REFERENCES items (id) ON DELETE RESTRICT
+);
+
+INSERT INTO new_checklist
+SELECT id FROM checklist WHERE id IN (SELECT id FROM items);
+
+DROP TABLE checklist;
+
+ALTER TABLE new_checklist RENAME TO checklist;
+
+CREATE TABLE new_items_sections (
+    item_id INTEGER NOT NULL,
+    section_id INTEGER NOT NULL,
+    PRIMARY KEY (item_id, section_id),
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE RESTRICT,
+    FOREIGN KEY (section_id) REFERENCES sections (id) ON DELETE RESTRICT
+);
+
+INSERT INTO new_items_sections
+SELECT item_id, section_id FROM items_sections
+WHERE item_id IN (SELECT id FROM items) AND section_id IN (SELECT id FROM sections);
+
+DROP TABLE items_sections;
+
+ALTER TABLE new_items_sections RENAME TO items_sections;
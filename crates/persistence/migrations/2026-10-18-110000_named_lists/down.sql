@@ -0,0 +1,33 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE old_list (
+    id INTEGER NOT NULL,
+    unit TEXT NOT NULL DEFAULT '',
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    PRIMARY KEY (id, unit),
+    FOREIGN KEY (id) REFERENCES items (id) ON DELETE RESTRICT
+);
+
+INSERT INTO old_list
+SELECT list.id, list.unit, list.quantity, list.quantity_max
+FROM list JOIN lists ON lists.id = list.list_id WHERE lists.name = 'default';
+
+DROP TABLE list;
+
+ALTER TABLE old_list RENAME TO list;
+
+CREATE TABLE old_list_recipes (
+    id INTEGER NOT NULL,
+    PRIMARY KEY (id),
+    FOREIGN KEY (id) REFERENCES recipes (id) ON DELETE CASCADE
+);
+
+INSERT INTO old_list_recipes
+SELECT list_recipes.id
+FROM list_recipes JOIN lists ON lists.id = list_recipes.list_id WHERE lists.name = 'default';
+
+DROP TABLE list_recipes;
+
+ALTER TABLE old_list_recipes RENAME TO list_recipes;
+
+DROP TABLE lists;
@@ -0,0 +1,42 @@
+-- Named shopping lists. What was the one global list becomes 'default'.
+CREATE TABLE lists (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    archived BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+INSERT INTO lists (name) VALUES ('default');
+
+CREATE TABLE new_list (
+    list_id INTEGER NOT NULL,
+    id INTEGER NOT NULL,
+    unit TEXT NOT NULL DEFAULT '',
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    PRIMARY KEY (list_id, id, unit),
+    FOREIGN KEY (list_id) REFERENCES lists (id) ON DELETE CASCADE,
+    FOREIGN KEY (id) REFERENCES items (id) ON DELETE RESTRICT
+);
+
+INSERT INTO new_list
+SELECT lists.id, list.id, list.unit, list.quantity, list.quantity_max
+FROM list, lists WHERE lists.name = 'default';
+
+DROP TABLE list;
+
+ALTER TABLE new_list RENAME TO list;
+
+CREATE TABLE new_list_recipes (
+    list_id INTEGER NOT NULL,
+    id INTEGER NOT NULL,
+    PRIMARY KEY (list_id, id),
+    FOREIGN KEY (list_id) REFERENCES lists (id) ON DELETE CASCADE,
+    FOREIGN KEY (id) REFERENCES recipes (id) ON DELETE CASCADE
+);
+
+INSERT INTO new_list_recipes
+SELECT lists.id, list_recipes.id FROM list_recipes, lists WHERE lists.name = 'default';
+
+DROP TABLE list_recipes;
+
+ALTER TABLE new_list_recipes RENAME TO list_recipes;
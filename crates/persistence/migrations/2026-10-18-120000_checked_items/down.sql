@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE list DROP COLUMN checked_at;
+ALTER TABLE list DROP COLUMN checked;
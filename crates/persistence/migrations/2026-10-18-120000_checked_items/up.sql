@@ -0,0 +1,3 @@
+-- Whether an item on a list is already in the cart, and when that last changed.
+ALTER TABLE list ADD COLUMN checked BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE list ADD COLUMN checked_at TIMESTAMP;
@@ -0,0 +1,3 @@
+-- Where a section comes in the walk through the store. Sections without a
+-- position are shown after those with one.
+ALTER TABLE sections ADD COLUMN position INTEGER;
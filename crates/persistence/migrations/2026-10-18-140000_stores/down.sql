@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE items_aisles;
+DROP TABLE sections_aisles;
+DROP TABLE aisles;
+DROP TABLE stores;
@@ -0,0 +1,38 @@
+-- Stores and their layouts, so the list can be ordered for a walk through a
+-- particular store.
+CREATE TABLE stores (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL UNIQUE
+);
+
+-- A store's aisles, in the order they're walked.
+CREATE TABLE aisles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    store_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    UNIQUE (store_id, name),
+    FOREIGN KEY (store_id) REFERENCES stores (id) ON DELETE CASCADE
+);
+
+-- The aisle each section is found in at a store.
+CREATE TABLE sections_aisles (
+    store_id INTEGER NOT NULL,
+    section_id INTEGER NOT NULL,
+    aisle_id INTEGER NOT NULL,
+    PRIMARY KEY (store_id, section_id),
+    FOREIGN KEY (store_id) REFERENCES stores (id) ON DELETE CASCADE,
+    FOREIGN KEY (section_id) REFERENCES sections (id) ON DELETE CASCADE,
+    FOREIGN KEY (aisle_id) REFERENCES aisles (id) ON DELETE CASCADE
+);
+
+-- Items a store keeps somewhere other than their section's aisle.
+CREATE TABLE items_aisles (
+    store_id INTEGER NOT NULL,
+    item_id INTEGER NOT NULL,
+    aisle_id INTEGER NOT NULL,
+    PRIMARY KEY (store_id, item_id),
+    FOREIGN KEY (store_id) REFERENCES stores (id) ON DELETE CASCADE,
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE CASCADE,
+    FOREIGN KEY (aisle_id) REFERENCES aisles (id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- Items may have been put in the seeded sections since, so they're kept.
+SELECT 1;
@@ -0,0 +1,4 @@
+-- Start every library with the default sections rather than only those
+-- brought in by an import.
+INSERT OR IGNORE INTO sections (name)
+VALUES ('fresh'), ('pantry'), ('protein'), ('dairy'), ('freezer');
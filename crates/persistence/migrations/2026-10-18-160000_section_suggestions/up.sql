@@ -0,0 +1,7 @@
+-- Sections suggested for new items, waiting to be confirmed or corrected.
+CREATE TABLE section_suggestions (
+    item_id INTEGER PRIMARY KEY NOT NULL,
+    section_id INTEGER NOT NULL,
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE CASCADE,
+    FOREIGN KEY (section_id) REFERENCES sections (id) ON DELETE CASCADE
+);
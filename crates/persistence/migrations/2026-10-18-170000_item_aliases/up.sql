@@ -0,0 +1,7 @@
+-- Other names for items, such as "scallions" for "spring onions", so that
+-- each item is only stored once.
+CREATE TABLE item_aliases (
+    alias TEXT PRIMARY KEY NOT NULL,
+    item_id INTEGER NOT NULL,
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE CASCADE
+);
@@ -0,0 +1,13 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER items_search_delete;
+DROP TRIGGER items_search_update;
+DROP TRIGGER items_search_insert;
+DROP TRIGGER items_recipes_search_delete;
+DROP TRIGGER items_recipes_search_update;
+DROP TRIGGER items_recipes_search_insert;
+DROP TRIGGER recipes_search_delete;
+DROP TRIGGER recipes_search_update;
+DROP TRIGGER recipes_search_insert;
+DROP VIEW recipes_search_text;
+DROP TABLE items_search;
+DROP TABLE recipes_search;
@@ -0,0 +1,106 @@
+-- Full-text indexes over recipes, with their ingredients and preparation
+-- notes, and over items. Each row shares its id with the row it indexes, and
+-- triggers keep the indexes in step with the tables.
+CREATE VIRTUAL TABLE recipes_search USING fts5(
+    name,
+    ingredients,
+    notes,
+    tokenize = 'porter unicode61'
+);
+
+CREATE VIRTUAL TABLE items_search USING fts5(
+    name,
+    tokenize = 'porter unicode61'
+);
+
+-- The text indexed for each recipe's ingredients and notes.
+CREATE VIEW recipes_search_text AS
+SELECT
+    recipes.id AS recipe_id,
+    coalesce((
+        SELECT group_concat(items.name, ' ')
+        FROM items_recipes
+        INNER JOIN items ON items.id = items_recipes.item_id
+        WHERE items_recipes.recipe_id = recipes.id
+    ), '') AS ingredients,
+    coalesce((
+        SELECT group_concat(items_recipes.preparation, ' ')
+        FROM items_recipes
+        WHERE items_recipes.recipe_id = recipes.id
+    ), '') AS notes
+FROM recipes;
+
+INSERT INTO recipes_search (rowid, name, ingredients, notes)
+SELECT recipes.id, recipes.name, recipes_search_text.ingredients, recipes_search_text.notes
+FROM recipes
+INNER JOIN recipes_search_text ON recipes_search_text.recipe_id = recipes.id;
+
+INSERT INTO items_search (rowid, name)
+SELECT id, name FROM items;
+
+CREATE TRIGGER recipes_search_insert AFTER INSERT ON recipes
+BEGIN
+    INSERT INTO recipes_search (rowid, name, ingredients, notes)
+    VALUES (NEW.id, NEW.name, '', '');
+END;
+
+CREATE TRIGGER recipes_search_update AFTER UPDATE OF name ON recipes
+BEGIN
+    UPDATE recipes_search SET name = NEW.name WHERE rowid = NEW.id;
+END;
+
+CREATE TRIGGER recipes_search_delete AFTER DELETE ON recipes
+BEGIN
+    DELETE FROM recipes_search WHERE rowid = OLD.id;
+END;
+
+CREATE TRIGGER items_recipes_search_insert AFTER INSERT ON items_recipes
+BEGIN
+    UPDATE recipes_search
+    SET (ingredients, notes) = (
+        SELECT ingredients, notes FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid = NEW.recipe_id;
+END;
+
+CREATE TRIGGER items_recipes_search_update AFTER UPDATE ON items_recipes
+BEGIN
+    UPDATE recipes_search
+    SET (ingredients, notes) = (
+        SELECT ingredients, notes FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid IN (OLD.recipe_id, NEW.recipe_id);
+END;
+
+CREATE TRIGGER items_recipes_search_delete AFTER DELETE ON items_recipes
+BEGIN
+    UPDATE recipes_search
+    SET (ingredients, notes) = (
+        SELECT ingredients, notes FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid = OLD.recipe_id;
+END;
+
+CREATE TRIGGER items_search_insert AFTER INSERT ON items
+BEGIN
+    INSERT INTO items_search (rowid, name) VALUES (NEW.id, NEW.name);
+END;
+
+CREATE TRIGGER items_search_update AFTER UPDATE OF name ON items
+BEGIN
+    UPDATE items_search SET name = NEW.name WHERE rowid = NEW.id;
+    UPDATE recipes_search
+    SET ingredients = (
+        SELECT ingredients FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid IN (SELECT recipe_id FROM items_recipes WHERE item_id = NEW.id);
+END;
+
+CREATE TRIGGER items_search_delete AFTER DELETE ON items
+BEGIN
+    DELETE FROM items_search WHERE rowid = OLD.id;
+END;
@@ -0,0 +1,24 @@
+-- What's at home. As on the list, an item has a row for each unit its amounts
+-- can't be combined across, with the empty unit for counts and for items kept
+-- without an amount. Like the list and the checklist, the pantry keeps an item
+-- from being deleted while it's stocked.
+CREATE TABLE pantry (
+    item_id INTEGER NOT NULL,
+    unit TEXT NOT NULL DEFAULT '',
+    quantity DOUBLE,
+    best_before DATE,
+    PRIMARY KEY (item_id, unit),
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE RESTRICT
+);
+
+-- Amounts of the pantry counted on by the recipes added to a list, in the
+-- pantry's units, so that two recipes don't both count on the same stock.
+CREATE TABLE list_pantry (
+    list_id INTEGER NOT NULL,
+    item_id INTEGER NOT NULL,
+    unit TEXT NOT NULL DEFAULT '',
+    quantity DOUBLE NOT NULL,
+    PRIMARY KEY (list_id, item_id, unit),
+    FOREIGN KEY (list_id) REFERENCES lists (id) ON DELETE CASCADE,
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE CASCADE
+);
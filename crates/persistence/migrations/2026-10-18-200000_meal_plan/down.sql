@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE list_plans;
+DROP TABLE meal_plan;
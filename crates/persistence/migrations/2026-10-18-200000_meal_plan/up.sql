@@ -0,0 +1,16 @@
+-- The recipe planned for each meal, with at most one recipe a meal.
+CREATE TABLE meal_plan (
+    date DATE NOT NULL,
+    meal TEXT NOT NULL,
+    recipe_id INTEGER NOT NULL,
+    PRIMARY KEY (date, meal),
+    FOREIGN KEY (recipe_id) REFERENCES recipes (id) ON DELETE CASCADE
+);
+
+-- The days of the meal plan a list was made from.
+CREATE TABLE list_plans (
+    list_id INTEGER PRIMARY KEY NOT NULL,
+    start DATE NOT NULL,
+    end DATE NOT NULL,
+    FOREIGN KEY (list_id) REFERENCES lists (id) ON DELETE CASCADE
+);
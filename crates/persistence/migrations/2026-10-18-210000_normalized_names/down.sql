@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX item_aliases_normalized;
+ALTER TABLE item_aliases DROP COLUMN normalized;
+DROP INDEX items_normalized;
+ALTER TABLE items DROP COLUMN normalized;
@@ -0,0 +1,9 @@
+-- Item names and aliases reduced to the form their spelling variants share,
+-- so that "eggs" finds "egg" through an index. The store fills these in from
+-- `common::item::normalize` when it writes a name, and brings existing rows up
+-- to date when it opens the database.
+ALTER TABLE items ADD COLUMN normalized TEXT NOT NULL DEFAULT '';
+CREATE INDEX items_normalized ON items (normalized);
+
+ALTER TABLE item_aliases ADD COLUMN normalized TEXT NOT NULL DEFAULT '';
+CREATE INDEX item_aliases_normalized ON item_aliases (normalized);
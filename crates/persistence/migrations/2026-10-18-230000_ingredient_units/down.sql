@@ -0,0 +1,79 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE old_items_recipes (
+    item_id INTEGER NOT NULL,
+    recipe_id INTEGER NOT NULL,
+    quantity DOUBLE,
+    quantity_max DOUBLE,
+    unit TEXT,
+    preparation TEXT,
+    PRIMARY KEY (item_id, recipe_id),
+    FOREIGN KEY (item_id) REFERENCES items (id) ON DELETE RESTRICT,
+    FOREIGN KEY (recipe_id) REFERENCES recipes (id) ON DELETE CASCADE
+);
+
+INSERT OR IGNORE INTO old_items_recipes
+SELECT item_id, recipe_id, quantity, quantity_max, nullif(unit, ''), preparation
+FROM items_recipes;
+
+DROP TRIGGER items_search_update;
+DROP VIEW recipes_search_text;
+DROP TABLE items_recipes;
+
+ALTER TABLE old_items_recipes RENAME TO items_recipes;
+
+CREATE VIEW recipes_search_text AS
+SELECT
+    recipes.id AS recipe_id,
+    coalesce((
+        SELECT group_concat(items.name, ' ')
+        FROM items_recipes
+        INNER JOIN items ON items.id = items_recipes.item_id
+        WHERE items_recipes.recipe_id = recipes.id
+    ), '') AS ingredients,
+    coalesce((
+        SELECT group_concat(items_recipes.preparation, ' ')
+        FROM items_recipes
+        WHERE items_recipes.recipe_id = recipes.id
+    ), '') AS notes
+FROM recipes;
+
+CREATE TRIGGER items_recipes_search_insert AFTER INSERT ON items_recipes
+BEGIN
+    UPDATE recipes_search
+    SET (ingredients, notes) = (
+        SELECT ingredients, notes FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid = NEW.recipe_id;
+END;
+
+CREATE TRIGGER items_recipes_search_update AFTER UPDATE ON items_recipes
+BEGIN
+    UPDATE recipes_search
+    SET (ingredients, notes) = (
+        SELECT ingredients, notes FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid IN (OLD.recipe_id, NEW.recipe_id);
+END;
+
+CREATE TRIGGER items_recipes_search_delete AFTER DELETE ON items_recipes
+BEGIN
+    UPDATE recipes_search
+    SET (ingredients, notes) = (
+        SELECT ingredients, notes FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid = OLD.recipe_id;
+END;
+
+CREATE TRIGGER items_search_update AFTER UPDATE OF name ON items
+BEGIN
+    UPDATE items_search SET name = NEW.name WHERE rowid = NEW.id;
+    UPDATE recipes_search
+    SET ingredients = (
+        SELECT ingredients FROM recipes_search_text
+        WHERE recipe_id = recipes_search.rowid
+    )
+    WHERE rowid IN (SELECT recipe_id FROM items_recipes WHERE item_id = NEW.id);
+END;
@@ -1,7 +1,15 @@
 use crate::schema::{
-    checklist, items, items_recipes, items_sections, list, list_recipes, recipes, sections,
+    aisles, checklist, item_aliases, items, items_aisles, items_recipes, items_sections, list,
+    list_pantry, list_plans, list_recipes, lists, meal_plan, pantry, recipes, section_suggestions,
+    sections, sections_aisles, stores,
+};
+use common::{
+    date::{parse_date, Date},
+    ingredient::{Measure, Quantity},
+    plan::{DateRange, Meal, MealSlot},
+    recipes::{Recipe, Servings},
+    unit::Unit,
 };
-use common::recipes::Recipe;
 use diesel::prelude::*;
 
 pub trait ItemInfo {
@@ -13,6 +21,7 @@ pub trait ItemInfo {
 pub struct Item {
     pub id: i32,
     pub name: String,
+    pub normalized: String,
 }
 
 impl From<Item> for common::item::Item {
@@ -31,6 +40,7 @@ impl ItemInfo for Item {
 #[diesel(table_name = items)]
 pub struct NewItem<'a> {
     pub name: &'a str,
+    pub normalized: String,
 }
 
 #[derive(Insertable)]
@@ -44,6 +54,15 @@ pub struct NewRecipe<'a> {
 pub struct RecipeModel {
     pub id: i32,
     pub name: String,
+    pub servings: Option<i32>,
+}
+
+impl RecipeModel {
+    pub fn servings(&self) -> Option<Servings> {
+        self.servings
+            .and_then(|servings| u32::try_from(servings).ok())
+            .map(Servings::new)
+    }
 }
 
 impl From<RecipeModel> for Recipe {
@@ -69,6 +88,7 @@ pub struct NewSection<'a> {
 pub struct Section {
     pub id: i32,
     pub name: String,
+    pub position: Option<i32>,
 }
 
 impl ItemInfo for Section {
@@ -98,32 +118,190 @@ pub struct NewChecklistItem {
 #[derive(Queryable)]
 #[diesel(table_name = list)]
 pub struct ListItem {
+    pub list_id: i32,
     pub id: i32,
+    pub unit: String,
+    pub quantity: Option<f64>,
+    pub quantity_max: Option<f64>,
+    pub checked: bool,
+    pub checked_at: Option<String>,
+}
+
+impl ListItem {
+    pub fn measure(&self) -> Option<Measure> {
+        self.quantity.map(|quantity| {
+            Measure::new(
+                Quantity::new(quantity, self.quantity_max),
+                (!self.unit.is_empty()).then(|| Unit::from(self.unit.as_str())),
+            )
+        })
+    }
 }
 
 #[derive(Insertable)]
 #[diesel(table_name = list)]
 pub struct NewListItem {
+    pub list_id: i32,
     pub id: i32,
+    pub unit: String,
+    pub quantity: Option<f64>,
+    pub quantity_max: Option<f64>,
+    pub checked: bool,
+    pub checked_at: Option<String>,
+}
+
+impl NewListItem {
+    pub fn new(list_id: i32, id: i32, measure: Option<&Measure>) -> Self {
+        Self {
+            list_id,
+            id,
+            unit: measure
+                .and_then(Measure::unit)
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            quantity: measure.map(|measure| measure.quantity().low()),
+            quantity_max: measure.and_then(|measure| measure.quantity().high()),
+            checked: false,
+            checked_at: None,
+        }
+    }
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = pantry)]
+pub struct PantryRow {
+    pub item_id: i32,
+    pub unit: String,
+    pub quantity: Option<f64>,
+    pub best_before: Option<String>,
+}
+
+impl PantryRow {
+    pub fn new(item_id: i32, measure: Option<&Measure>, best_before: Option<Date>) -> Self {
+        Self {
+            item_id,
+            unit: measure
+                .and_then(Measure::unit)
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            quantity: measure.map(|measure| measure.quantity().low()),
+            best_before: best_before.map(|date| date.to_string()),
+        }
+    }
+
+    pub fn measure(&self) -> Option<Measure> {
+        self.quantity.map(|quantity| {
+            Measure::new(
+                Quantity::Exact(quantity),
+                (!self.unit.is_empty()).then(|| Unit::from(self.unit.as_str())),
+            )
+        })
+    }
+
+    pub fn best_before(&self) -> Option<Date> {
+        self.best_before
+            .as_deref()
+            .and_then(|date| parse_date(date).ok())
+    }
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = list_pantry)]
+pub struct ListPantryRow {
+    pub list_id: i32,
+    pub item_id: i32,
+    pub unit: String,
+    pub quantity: f64,
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = meal_plan)]
+pub struct MealPlanRow {
+    pub date: String,
+    pub meal: String,
+    pub recipe_id: i32,
+}
+
+impl MealPlanRow {
+    pub fn new(slot: MealSlot, recipe_id: i32) -> Self {
+        Self {
+            date: slot.date().to_string(),
+            meal: slot.meal().to_string(),
+            recipe_id,
+        }
+    }
+
+    // Rows that don't read as a slot are skipped rather than failing the
+    // whole plan.
+    pub fn slot(&self) -> Option<MealSlot> {
+        let date = parse_date(&self.date).ok()?;
+        let meal = self.meal.parse::<Meal>().ok()?;
+        Some(MealSlot::new(date, meal))
+    }
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = list_plans)]
+pub struct ListPlanRow {
+    pub list_id: i32,
+    pub start: String,
+    pub end: String,
+}
+
+impl ListPlanRow {
+    pub fn new(list_id: i32, range: DateRange) -> Self {
+        Self {
+            list_id,
+            start: range.start().to_string(),
+            end: range.end().to_string(),
+        }
+    }
+
+    pub fn range(&self) -> Option<DateRange> {
+        Some(DateRange::new(
+            parse_date(&self.start).ok()?,
+            parse_date(&self.end).ok()?,
+        ))
+    }
 }
 
 #[derive(Queryable)]
 #[diesel(table_name = list_recipes)]
 pub struct ListRecipe {
+    pub list_id: i32,
     pub id: i32,
 }
 
 #[derive(Insertable)]
 #[diesel(table_name = list_recipes)]
 pub struct NewListRecipe {
+    pub list_id: i32,
     pub id: i32,
 }
 
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = lists)]
+pub struct ListModel {
+    pub id: i32,
+    pub name: String,
+    pub archived: bool,
+}
+
 #[derive(Insertable)]
+#[diesel(table_name = lists)]
+pub struct NewList<'a> {
+    pub name: &'a str,
+}
+
+#[derive(Insertable, Default)]
 #[diesel(table_name = items_recipes)]
-pub struct NewItemRecipe {
+pub struct NewItemRecipe<'a> {
     pub item_id: i32,
     pub recipe_id: i32,
+    pub quantity: Option<f64>,
+    pub quantity_max: Option<f64>,
+    pub unit: String,
+    pub preparation: Option<&'a str>,
 }
 
 #[derive(Queryable)]
@@ -131,6 +309,26 @@ pub struct NewItemRecipe {
 pub struct ItemRecipe {
     pub item_id: i32,
     pub recipe_id: i32,
+    pub quantity: Option<f64>,
+    pub quantity_max: Option<f64>,
+    pub unit: String,
+    pub preparation: Option<String>,
+}
+
+impl ItemRecipe {
+    pub fn ingredient(&self, name: &str) -> common::ingredient::Ingredient {
+        let mut ingredient = common::ingredient::Ingredient::from(common::item::Name::from(name));
+        if let Some(quantity) = self.quantity {
+            ingredient = ingredient.with_quantity(Quantity::new(quantity, self.quantity_max));
+        }
+        if !self.unit.is_empty() {
+            ingredient = ingredient.with_unit(Unit::from(self.unit.as_str()));
+        }
+        if let Some(preparation) = &self.preparation {
+            ingredient = ingredient.with_preparation(preparation);
+        }
+        ingredient
+    }
 }
 
 #[derive(Insertable)]
@@ -140,9 +338,77 @@ pub struct NewItemSection {
     pub section_id: i32,
 }
 
+#[derive(Insertable)]
+#[diesel(table_name = item_aliases)]
+pub struct NewItemAlias<'a> {
+    pub alias: &'a str,
+    pub item_id: i32,
+    pub normalized: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = section_suggestions)]
+pub struct NewSectionSuggestion {
+    pub item_id: i32,
+    pub section_id: i32,
+}
+
 #[derive(Queryable)]
 #[diesel(table_name = items_sections)]
 pub struct ItemSection {
     pub item_id: i32,
     pub section_id: i32,
 }
+
+/// A name found through one of the full-text search indexes.
+#[derive(QueryableByName)]
+pub struct SearchHit {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = stores)]
+pub struct StoreModel {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = stores)]
+pub struct NewStore<'a> {
+    pub name: &'a str,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = aisles)]
+pub struct AisleModel {
+    pub id: i32,
+    pub store_id: i32,
+    pub name: String,
+    pub position: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = aisles)]
+pub struct NewAisle<'a> {
+    pub store_id: i32,
+    pub name: &'a str,
+    pub position: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = sections_aisles)]
+pub struct NewSectionAisle {
+    pub store_id: i32,
+    pub section_id: i32,
+    pub aisle_id: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = items_aisles)]
+pub struct NewItemAisle {
+    pub store_id: i32,
+    pub item_id: i32,
+    pub aisle_id: i32,
+}
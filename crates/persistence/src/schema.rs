@@ -1,22 +1,52 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    aisles (id) {
+        id -> Integer,
+        store_id -> Integer,
+        name -> Text,
+        position -> Integer,
+    }
+}
+
 diesel::table! {
     checklist (id) {
         id -> Integer,
     }
 }
 
+diesel::table! {
+    item_aliases (alias) {
+        alias -> Text,
+        item_id -> Integer,
+        normalized -> Text,
+    }
+}
+
 diesel::table! {
     items (id) {
         id -> Integer,
         name -> Text,
+        normalized -> Text,
     }
 }
 
 diesel::table! {
-    items_recipes (item_id, recipe_id) {
+    items_aisles (store_id, item_id) {
+        store_id -> Integer,
+        item_id -> Integer,
+        aisle_id -> Integer,
+    }
+}
+
+diesel::table! {
+    items_recipes (item_id, recipe_id, unit) {
         item_id -> Integer,
         recipe_id -> Integer,
+        quantity -> Nullable<Double>,
+        quantity_max -> Nullable<Double>,
+        unit -> Text,
+        preparation -> Nullable<Text>,
     }
 }
 
@@ -28,14 +58,63 @@ diesel::table! {
 }
 
 diesel::table! {
-    list (id) {
+    list (list_id, id, unit) {
+        list_id -> Integer,
+        id -> Integer,
+        unit -> Text,
+        quantity -> Nullable<Double>,
+        quantity_max -> Nullable<Double>,
+        checked -> Bool,
+        checked_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    list_pantry (list_id, item_id, unit) {
+        list_id -> Integer,
+        item_id -> Integer,
+        unit -> Text,
+        quantity -> Double,
+    }
+}
+
+diesel::table! {
+    list_plans (list_id) {
+        list_id -> Integer,
+        start -> Text,
+        end -> Text,
+    }
+}
+
+diesel::table! {
+    list_recipes (list_id, id) {
+        list_id -> Integer,
         id -> Integer,
     }
 }
 
 diesel::table! {
-    list_recipes (id) {
+    lists (id) {
         id -> Integer,
+        name -> Text,
+        archived -> Bool,
+    }
+}
+
+diesel::table! {
+    meal_plan (date, meal) {
+        date -> Text,
+        meal -> Text,
+        recipe_id -> Integer,
+    }
+}
+
+diesel::table! {
+    pantry (item_id, unit) {
+        item_id -> Integer,
+        unit -> Text,
+        quantity -> Nullable<Double>,
+        best_before -> Nullable<Text>,
     }
 }
 
@@ -43,6 +122,22 @@ diesel::table! {
     recipes (id) {
         id -> Integer,
         name -> Text,
+        servings -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    section_suggestions (item_id) {
+        item_id -> Integer,
+        section_id -> Integer,
+    }
+}
+
+diesel::table! {
+    sections_aisles (store_id, section_id) {
+        store_id -> Integer,
+        section_id -> Integer,
+        aisle_id -> Integer,
     }
 }
 
@@ -50,24 +145,60 @@ diesel::table! {
     sections (id) {
         id -> Integer,
         name -> Text,
+        position -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    stores (id) {
+        id -> Integer,
+        name -> Text,
     }
 }
 
+diesel::joinable!(aisles -> stores (store_id));
 diesel::joinable!(checklist -> items (id));
+diesel::joinable!(item_aliases -> items (item_id));
+diesel::joinable!(items_aisles -> aisles (aisle_id));
+diesel::joinable!(items_aisles -> items (item_id));
+diesel::joinable!(items_aisles -> stores (store_id));
 diesel::joinable!(items_recipes -> items (item_id));
 diesel::joinable!(items_recipes -> recipes (recipe_id));
 diesel::joinable!(items_sections -> items (item_id));
 diesel::joinable!(items_sections -> sections (section_id));
 diesel::joinable!(list -> items (id));
+diesel::joinable!(list -> lists (list_id));
+diesel::joinable!(list_pantry -> items (item_id));
+diesel::joinable!(list_pantry -> lists (list_id));
+diesel::joinable!(list_plans -> lists (list_id));
+diesel::joinable!(list_recipes -> lists (list_id));
 diesel::joinable!(list_recipes -> recipes (id));
+diesel::joinable!(meal_plan -> recipes (recipe_id));
+diesel::joinable!(pantry -> items (item_id));
+diesel::joinable!(section_suggestions -> items (item_id));
+diesel::joinable!(section_suggestions -> sections (section_id));
+diesel::joinable!(sections_aisles -> aisles (aisle_id));
+diesel::joinable!(sections_aisles -> sections (section_id));
+diesel::joinable!(sections_aisles -> stores (store_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    aisles,
     checklist,
+    item_aliases,
     items,
+    items_aisles,
     items_recipes,
     items_sections,
     list,
+    list_pantry,
+    list_plans,
     list_recipes,
+    lists,
+    meal_plan,
+    pantry,
     recipes,
+    section_suggestions,
     sections,
+    sections_aisles,
+    stores,
 );
@@ -1,6 +1,10 @@
 use std::{env, ops::Deref};
 
-use diesel::{r2d2::ConnectionManager, SqliteConnection};
+use diesel::{
+    connection::SimpleConnection,
+    r2d2::{ConnectionManager, CustomizeConnection},
+    SqliteConnection,
+};
 use r2d2::Pool;
 
 use crate::store::StoreError;
@@ -43,6 +47,18 @@ impl DbUri {
 
 pub type ConnectionPool = Pool<ConnectionManager<SqliteConnection>>;
 
+// SQLite leaves foreign key constraints off unless asked, per connection.
+#[derive(Debug)]
+struct ForeignKeys;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ForeignKeys {
+    fn on_acquire(&self, connection: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        connection
+            .batch_execute("PRAGMA foreign_keys = ON;")
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
 pub(crate) trait Connection {
     async fn try_connect(&self) -> Result<ConnectionPool, StoreError>;
 }
@@ -61,10 +77,10 @@ impl Connection for DatabaseConnector {
     async fn try_connect(&self) -> Result<ConnectionPool, StoreError> {
         use diesel::Connection;
         SqliteConnection::establish(&self.db_uri)?;
-        Ok(
-            Pool::builder().build(ConnectionManager::<SqliteConnection>::new(
+        Ok(Pool::builder()
+            .connection_customizer(Box::new(ForeignKeys))
+            .build(ConnectionManager::<SqliteConnection>::new(
                 self.db_uri.deref(),
-            ))?,
-        )
+            ))?)
     }
 }
@@ -1,8 +1,9 @@
 use common::{items::Items, section::SECTIONS};
 use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
 
+use super::SqliteStore;
 use crate::{
-    models::{self, NewItem, NewItemRecipe, NewItemSection, NewRecipe, NewSection},
+    models::{self, NewItemRecipe, NewItemSection, NewRecipe, NewSection},
     schema,
     store::StoreError,
 };
@@ -25,29 +26,12 @@ pub fn import_sections(connection: &mut SqliteConnection) -> Result<(), StoreErr
 }
 
 pub fn import_items(connection: &mut SqliteConnection, items: Items) -> Result<(), StoreError> {
-    let items_table = schema::items::table;
     let recipes_table = schema::recipes::table;
     let sections_table = schema::sections::table;
 
     for item in items.collection_iter() {
-        // add the item to the item table
-        let new_item = NewItem {
-            name: item.name().as_str(),
-        };
-
-        diesel::insert_into(items_table)
-            .values(&new_item)
-            .on_conflict_do_nothing()
-            .execute(connection)?;
-
-        // get the item's item_id
-        let results = items_table
-            .filter(schema::items::dsl::name.eq(item.name().to_string()))
-            .load::<models::Item>(connection)?;
-
-        assert_eq!(results.len(), 1);
-
-        let item_id = results[0].id;
+        // add the item to the item table, or find the item it's an alias of
+        let item_id = SqliteStore::get_or_insert_item(connection, item.name().as_str())?;
 
         if let Some(item_recipes) = item.recipes() {
             // log the item_id in items_recipes
@@ -70,7 +54,11 @@ pub fn import_items(connection: &mut SqliteConnection, items: Items) -> Result<(
 
                 let recipe_id = results[0].id;
 
-                let new_item_recipe = NewItemRecipe { item_id, recipe_id };
+                let new_item_recipe = NewItemRecipe {
+                    item_id,
+                    recipe_id,
+                    ..Default::default()
+                };
 
                 diesel::insert_into(schema::items_recipes::table)
                     .values(&new_item_recipe)
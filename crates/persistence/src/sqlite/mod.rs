@@ -3,11 +3,21 @@ mod import;
 mod migrations;
 
 use common::{
+    classify::SectionClassifier,
+    commands::Available,
+    date::Date,
     export::{YamlSerializable, ITEMS_YAML_PATH, LIST_YAML_PATH},
-    item::Name,
+    fuzzy::{MatchKind, SearchMatch},
+    ingredient::{Ingredient, Measure, Quantity},
+    item::{normalize, Name},
     items::Items,
-    list::List,
-    recipes::{Ingredients, Recipe},
+    layout::{Aisle, StoreLayout, StoreName},
+    list::{List, ListItem, ListName, Measures},
+    pantry::{draw, used_up, ExpiryReport, PantryItem},
+    plan::{DateRange, MealPlan, MealSlot, PlannedMeal},
+    query::{Query, Term},
+    recipes::{Ingredients, Recipe, RecipeCoverage, Servings},
+    unit::Density,
 };
 use diesel::{prelude::*, r2d2::ConnectionManager, SqliteConnection};
 use r2d2::PooledConnection;
@@ -15,8 +25,11 @@ use r2d2::PooledConnection;
 use crate::{
     import_store::ImportStore,
     models::{
-        self, Item, ItemInfo, NewChecklistItem, NewItem, NewItemRecipe, NewItemSection,
-        NewListItem, NewListRecipe, NewRecipe, NewSection, RecipeModel, Section,
+        self, AisleModel, Item, ItemInfo, ListModel, ListPantryRow, ListPlanRow, MealPlanRow,
+        NewAisle, NewChecklistItem, NewItem, NewItemAisle, NewItemAlias, NewItemRecipe,
+        NewItemSection, NewList, NewListItem, NewListRecipe, NewRecipe, NewSection,
+        NewSectionAisle, NewSectionSuggestion, NewStore, PantryRow, RecipeModel, SearchHit,
+        Section, StoreModel,
     },
     schema,
     store::{Storage, StoreError, StoreResponse},
@@ -43,7 +56,41 @@ impl SqliteStore {
 
     pub(crate) fn run_migrations(&self) -> Result<(), StoreError> {
         let mut connection = self.connection()?;
-        connection.immediate_transaction(run_migrations)
+        connection.immediate_transaction(|connection| {
+            run_migrations(connection)?;
+            Self::renormalize_names(connection)
+        })
+    }
+
+    // Brings the stored normalized names up to date with `normalize`, for
+    // rows written before the column existed or under older rules.
+    fn renormalize_names(connection: &mut SqliteConnection) -> Result<(), StoreError> {
+        use crate::schema::{item_aliases, items};
+
+        let names = items::table
+            .select((items::id, items::name, items::normalized))
+            .load::<(i32, String, String)>(connection)?;
+        for (id, name, stored) in names {
+            let normalized = normalize(&name);
+            if normalized != stored {
+                diesel::update(items::table.find(id))
+                    .set(items::normalized.eq(normalized))
+                    .execute(connection)?;
+            }
+        }
+
+        let aliases = item_aliases::table
+            .select((item_aliases::alias, item_aliases::normalized))
+            .load::<(String, String)>(connection)?;
+        for (alias, stored) in aliases {
+            let normalized = normalize(&alias);
+            if normalized != stored {
+                diesel::update(item_aliases::table.find(&alias))
+                    .set(item_aliases::normalized.eq(normalized))
+                    .execute(connection)?;
+            }
+        }
+        Ok(())
     }
 
     pub(crate) fn connection(
@@ -52,12 +99,124 @@ impl SqliteStore {
         self.pool.get()
     }
 
-    fn get_or_insert_item(
+    // Looks an item up by its name or any of its aliases, and failing those
+    // by a spelling variant of either, such as "eggs" for "egg".
+    fn get_item_id(
+        connection: &mut SqliteConnection,
+        item: &str,
+    ) -> Result<Option<i32>, StoreError> {
+        use crate::schema::{item_aliases, items};
+
+        let id = items::table
+            .filter(items::name.eq(item))
+            .select(items::id)
+            .first(connection)
+            .optional()?;
+        if id.is_some() {
+            return Ok(id);
+        }
+        let id = item_aliases::table
+            .find(item)
+            .select(item_aliases::item_id)
+            .first(connection)
+            .optional()?;
+        if id.is_some() {
+            return Ok(id);
+        }
+
+        let key = normalize(item);
+        let id = items::table
+            .filter(items::normalized.eq(&key))
+            .order_by(items::id)
+            .select(items::id)
+            .first(connection)
+            .optional()?;
+        if id.is_some() {
+            return Ok(id);
+        }
+        Ok(item_aliases::table
+            .filter(item_aliases::normalized.eq(&key))
+            .select(item_aliases::item_id)
+            .first(connection)
+            .optional()?)
+    }
+
+    fn existing_item_id(connection: &mut SqliteConnection, item: &Name) -> Result<i32, StoreError> {
+        Self::get_item_id(connection, item.as_str())?
+            .ok_or_else(|| StoreError::ItemNotFound(item.to_string()))
+    }
+
+    // Looks an item up by exactly its own name, for deleting or merging away
+    // the item itself, so that an alias or spelling variant never stands in
+    // for the item it resolves to.
+    fn exact_item_id(connection: &mut SqliteConnection, item: &Name) -> Result<i32, StoreError> {
+        use crate::schema::{item_aliases, items};
+
+        let id = items::table
+            .filter(items::name.eq(item.as_str()))
+            .select(items::id)
+            .first(connection)
+            .optional()?;
+        if let Some(id) = id {
+            return Ok(id);
+        }
+        let aliased = item_aliases::table
+            .find(item.as_str())
+            .inner_join(items::table)
+            .select(items::name)
+            .first::<String>(connection)
+            .optional()?;
+        Err(match aliased {
+            Some(name) => StoreError::ItemIsAlias {
+                alias: item.to_string(),
+                item: name,
+            },
+            None => StoreError::ItemNotFound(item.to_string()),
+        })
+    }
+
+    // Adds a recipe and its ingredients to a list, leaving off what the
+    // pantry can cover.
+    fn add_recipe_to_list(
+        connection: &mut SqliteConnection,
+        list_id: i32,
+        recipe: &Recipe,
+        ingredients: &Ingredients,
+    ) -> Result<(), StoreError> {
+        let id = Self::get_or_insert_recipe(connection, recipe.as_str())?;
+        diesel::insert_into(schema::list_recipes::table)
+            .values(NewListRecipe { list_id, id })
+            .on_conflict_do_nothing()
+            .execute(connection)?;
+        for ingredient in ingredients.iter() {
+            let item_id = Self::get_or_insert_item(connection, ingredient.name().as_str())?;
+            // Only what the pantry can't cover goes on the list.
+            if let Some(shortfall) = Self::draw_from_pantry(
+                connection,
+                list_id,
+                item_id,
+                ingredient.name(),
+                ingredient.measure(),
+            )? {
+                Self::add_to_list(connection, list_id, ingredient.name(), shortfall)?;
+            }
+            Self::insert_item_recipe(connection, item_id, id, ingredient)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn get_or_insert_item(
         connection: &mut SqliteConnection,
         name: &str,
     ) -> Result<i32, StoreError> {
+        if let Some(id) = Self::get_item_id(connection, name)? {
+            return Ok(id);
+        }
         diesel::insert_into(schema::items::table)
-            .values(NewItem { name })
+            .values(NewItem {
+                name,
+                normalized: normalize(name),
+            })
             .on_conflict_do_nothing()
             .execute(connection)?;
 
@@ -103,747 +262,5134 @@ impl SqliteStore {
         connection: &mut SqliteConnection,
         item_id: i32,
         recipe_id: i32,
+        ingredient: &Ingredient,
     ) -> Result<(), StoreError> {
+        let quantity = ingredient.quantity();
         diesel::insert_into(schema::items_recipes::table)
-            .values(NewItemRecipe { item_id, recipe_id })
+            .values(NewItemRecipe {
+                item_id,
+                recipe_id,
+                quantity: quantity.map(|quantity| quantity.low()),
+                quantity_max: quantity.and_then(|quantity| quantity.high()),
+                unit: ingredient
+                    .unit()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                preparation: ingredient.preparation(),
+            })
             .on_conflict_do_nothing()
             .execute(connection)?;
         Ok(())
     }
 
-    fn get_section_id(
+    // Links an ingredient to a recipe, replacing any amount already linked.
+    fn replace_item_recipe(
         connection: &mut SqliteConnection,
-        section: &str,
-    ) -> Result<Option<i32>, StoreError> {
-        Ok(schema::sections::table
-            .filter(schema::sections::dsl::name.eq(section))
-            .select(schema::sections::dsl::id)
-            .first(connection)
-            .optional()?)
+        recipe_id: i32,
+        ingredient: &Ingredient,
+    ) -> Result<(), StoreError> {
+        let item_id = Self::get_or_insert_item(connection, ingredient.name().as_str())?;
+        Self::delete_item_recipe(connection, item_id, recipe_id)?;
+        Self::insert_item_recipe(connection, item_id, recipe_id, ingredient)?;
+        Self::suggest_sections(connection, &[item_id])
     }
 
-    fn get_or_insert_section(
+    fn delete_item_recipe(
         connection: &mut SqliteConnection,
-        section: &str,
-    ) -> Result<i32, StoreError> {
-        match Self::get_section_id(connection, section)? {
-            Some(id) => Ok(id),
-            None => {
-                diesel::insert_into(schema::sections::table)
-                    .values(NewSection { name: section })
-                    .on_conflict_do_nothing()
-                    .execute(connection)?;
+        item_id: i32,
+        recipe_id: i32,
+    ) -> Result<usize, StoreError> {
+        use crate::schema::items_recipes;
 
-                Ok(schema::sections::table
-                    .filter(schema::sections::dsl::name.eq(section))
-                    .select(schema::sections::dsl::id)
-                    .first(connection)?)
-            }
-        }
+        Ok(diesel::delete(
+            items_recipes::table
+                .filter(items_recipes::item_id.eq(item_id))
+                .filter(items_recipes::recipe_id.eq(recipe_id)),
+        )
+        .execute(connection)?)
     }
 
-    fn insert_item_section(
+    fn remove_ingredient(
         connection: &mut SqliteConnection,
-        item_id: i32,
-        section_id: i32,
+        recipe_id: i32,
+        ingredient: &Name,
     ) -> Result<(), StoreError> {
-        diesel::insert_into(schema::items_sections::table)
-            .values(NewItemSection {
-                item_id,
-                section_id,
-            })
-            .on_conflict_do_nothing()
-            .execute(connection)?;
+        let deleted = match Self::get_item_id(connection, ingredient.as_str())? {
+            Some(item_id) => Self::delete_item_recipe(connection, item_id, recipe_id)?,
+            None => 0,
+        };
+        if deleted == 0 {
+            return Err(StoreError::IngredientNotFound(ingredient.to_string()));
+        }
         Ok(())
     }
 
-    async fn get_list(&self) -> Result<List, StoreError> {
-        let store = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                Ok(schema::items::table
-                    .filter(
-                        schema::items::dsl::id
-                            .eq_any(schema::list::table.select(schema::list::dsl::id)),
-                    )
-                    .load::<Item>(connection)?
-                    .into_iter()
-                    .map(Into::into)
-                    .collect::<List>())
-            })
-        })
-        .await?
-    }
+    // Whether a recipe, a list, the checklist, the pantry, a store's aisle or
+    // an alias still uses the item, in which case it must be kept. Having a
+    // section doesn't count, as nearly every item has one.
+    fn item_is_referenced(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+    ) -> Result<bool, StoreError> {
+        use crate::schema::{checklist, item_aliases, items_aisles, items_recipes, list, pantry};
+        use diesel::dsl::exists;
 
-    async fn get_list_recipes(&self) -> Result<Vec<Recipe>, StoreError> {
-        let store = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                Ok(schema::recipes::table
-                    .filter(
-                        schema::recipes::dsl::id.eq_any(
-                            schema::list_recipes::table.select(schema::list_recipes::dsl::id),
-                        ),
-                    )
-                    .load::<RecipeModel>(connection)?
-                    .into_iter()
-                    .map(Into::into)
-                    .collect())
-            })
-        })
-        .await?
+        Ok(diesel::select(
+            exists(items_recipes::table.filter(items_recipes::item_id.eq(item_id)))
+                .or(exists(list::table.filter(list::id.eq(item_id))))
+                .or(exists(checklist::table.filter(checklist::id.eq(item_id))))
+                .or(exists(pantry::table.filter(pantry::item_id.eq(item_id))))
+                .or(exists(
+                    items_aisles::table.filter(items_aisles::item_id.eq(item_id)),
+                ))
+                .or(exists(
+                    item_aliases::table.filter(item_aliases::item_id.eq(item_id)),
+                )),
+        )
+        .get_result(connection)?)
     }
 
-    fn load_item(connection: &mut SqliteConnection, item_id: i32) -> Result<Vec<Item>, StoreError> {
-        Ok(schema::items::table
-            .filter(schema::items::dsl::id.eq(&item_id))
-            .load::<Item>(connection)?)
+    fn existing_recipe_id(
+        connection: &mut SqliteConnection,
+        recipe: &Recipe,
+    ) -> Result<i32, StoreError> {
+        Self::get_recipe_id(connection, recipe.as_str())?
+            .ok_or_else(|| StoreError::RecipeNotFound(recipe.to_string()))
     }
 
-    fn get_recipe_model_for_recipe(
+    fn updated_recipe(
         connection: &mut SqliteConnection,
-        recipe: &str,
-    ) -> Result<Option<Vec<RecipeModel>>, StoreError> {
-        Ok(schema::recipes::table
-            .filter(schema::recipes::dsl::name.eq(recipe))
-            .load::<models::RecipeModel>(connection)
-            .optional()?)
+        recipe_id: i32,
+        recipe: Recipe,
+    ) -> Result<StoreResponse, StoreError> {
+        Ok(StoreResponse::UpdatedRecipe(
+            recipe,
+            Self::load_recipe_ingredients(connection, recipe_id)?,
+        ))
     }
 
-    fn get_section_model_for_item(
+    fn get_list_model(
         connection: &mut SqliteConnection,
-        item_id: i32,
-    ) -> Result<Option<Section>, StoreError> {
-        use crate::schema::{items_sections, sections};
-
-        Ok(items_sections::table
-            .filter(items_sections::item_id.eq(item_id))
-            .inner_join(sections::table.on(sections::id.eq(items_sections::section_id)))
-            .select(Section::as_select())
+        list: &ListName,
+    ) -> Result<Option<ListModel>, StoreError> {
+        Ok(schema::lists::table
+            .filter(schema::lists::dsl::name.eq(list.as_str()))
             .first(connection)
             .optional()?)
     }
 
-    fn get_recipe_models_for_item(
+    fn existing_list_id(
         connection: &mut SqliteConnection,
-        item_id: i32,
-    ) -> Result<Option<Vec<RecipeModel>>, StoreError> {
-        use crate::schema::{items_recipes, recipes};
+        list: &ListName,
+    ) -> Result<i32, StoreError> {
+        Self::get_list_model(connection, list)?
+            .map(|model| model.id)
+            .ok_or_else(|| StoreError::ListNotFound(list.to_string()))
+    }
 
-        Ok(items_recipes::table
-            .filter(items_recipes::item_id.eq(item_id))
-            .inner_join(recipes::table.on(recipes::id.eq(items_recipes::recipe_id)))
-            .select(RecipeModel::as_select())
-            .load(connection)
-            .optional()?)
+    // The id of a list that can be changed, creating the list if needed.
+    fn writable_list_id(
+        connection: &mut SqliteConnection,
+        list: &ListName,
+    ) -> Result<i32, StoreError> {
+        match Self::get_list_model(connection, list)? {
+            Some(model) if model.archived => Err(StoreError::ListArchived(list.to_string())),
+            Some(model) => Ok(model.id),
+            None => Self::insert_list(connection, list),
+        }
     }
-}
 
-impl Storage for SqliteStore {
-    async fn add_checklist_item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        let item = item.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let id = Self::get_or_insert_item(connection, item.as_str())?;
-                let query = {
-                    diesel::insert_into(schema::checklist::table)
-                        .values(NewChecklistItem { id })
-                        .on_conflict_do_nothing()
-                };
-                query.execute(connection)?;
-                Ok(StoreResponse::AddedChecklistItem(item))
-            })
-        })
-        .await?
+    // The id of an existing list that can be changed, for changes that only
+    // make sense on a list that's already there.
+    fn existing_writable_list_id(
+        connection: &mut SqliteConnection,
+        list: &ListName,
+    ) -> Result<i32, StoreError> {
+        match Self::get_list_model(connection, list)? {
+            Some(model) if model.archived => Err(StoreError::ListArchived(list.to_string())),
+            Some(model) => Ok(model.id),
+            None => Err(StoreError::ListNotFound(list.to_string())),
+        }
     }
 
-    async fn add_item(
-        &self,
-        item: &Name,
-        section: &Option<common::section::Section>,
-    ) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        let item = item.clone();
-        let section = section.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let item_name = item.to_string();
-                let item_id = Self::get_or_insert_item(connection, &item_name)?;
-                if let Some(section) = section {
-                    let section_id = Self::get_or_insert_section(connection, section.as_str())?;
-                    Self::insert_item_section(connection, item_id, section_id)?;
-                }
-                Ok(StoreResponse::AddedItem(item))
+    fn insert_list(connection: &mut SqliteConnection, list: &ListName) -> Result<i32, StoreError> {
+        if Self::get_list_model(connection, list)?.is_some() {
+            return Err(StoreError::ListExists(list.to_string()));
+        }
+        diesel::insert_into(schema::lists::table)
+            .values(NewList {
+                name: list.as_str(),
             })
-        })
-        .await?
+            .execute(connection)?;
+        Self::existing_list_id(connection, list)
     }
 
-    async fn add_list_item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        let item = item.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let id = Self::get_or_insert_item(connection, item.as_str())?;
-                let query = diesel::insert_into(schema::list::table)
-                    .values(NewListItem { id })
-                    .on_conflict_do_nothing();
-                query.execute(connection)?;
-                Ok(StoreResponse::AddedListItem(item))
-            })
-        })
-        .await?
+    // Lookups by list go through the primary key index, which would otherwise
+    // return an item's measures sorted by unit rather than as they were added.
+    fn insertion_order() -> diesel::expression::SqlLiteral<diesel::sql_types::Integer> {
+        diesel::dsl::sql("rowid")
     }
 
-    async fn add_list_recipe(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError> {
-        let StoreResponse::RecipeIngredients(Some(ingredients)) =
-            self.recipe_ingredients(recipe).await?
-        else {
-            // TODO:
-            return Err(StoreError::RecipeIngredients(recipe.to_string()));
+    // Adds an item to the list, summing `measure` into whatever amount of the
+    // item is already there. Needing more of an item puts it back on the list
+    // if it had been checked off.
+    fn add_to_list(
+        connection: &mut SqliteConnection,
+        list_id: i32,
+        item: &Name,
+        measure: Option<Measure>,
+    ) -> Result<(), StoreError> {
+        use crate::schema::list;
+
+        let item_id = Self::get_or_insert_item(connection, item.as_str())?;
+
+        let mut measures = list::table
+            .filter(list::list_id.eq(list_id))
+            .filter(list::id.eq(item_id))
+            .order_by(Self::insertion_order())
+            .load::<models::ListItem>(connection)?
+            .iter()
+            .filter_map(models::ListItem::measure)
+            .collect::<Measures>();
+
+        if let Some(measure) = measure {
+            measures.add_with_density(measure, Density::for_item(item));
+        }
+
+        let rows = if measures.is_empty() {
+            vec![NewListItem::new(list_id, item_id, None)]
+        } else {
+            measures
+                .iter()
+                .map(|measure| NewListItem::new(list_id, item_id, Some(measure)))
+                .collect()
         };
 
-        let store = self.clone();
-        let recipe = recipe.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let id = Self::get_or_insert_recipe(connection, recipe.as_str())?;
-                diesel::insert_into(schema::list_recipes::table)
-                    .values(NewListRecipe { id })
-                    .on_conflict_do_nothing()
-                    .execute(connection)?;
-                for item in ingredients.iter() {
-                    let item_id = Self::get_or_insert_item(connection, item.as_str())?;
-                    let query = diesel::insert_into(schema::list::table)
-                        .values(NewListItem { id: item_id })
-                        .on_conflict_do_nothing();
-                    query.execute(connection)?;
-
-                    let new_item_recipe = NewItemRecipe {
-                        item_id,
-                        recipe_id: id,
-                    };
-                    diesel::insert_into(schema::items_recipes::table)
-                        .values(&new_item_recipe)
-                        .on_conflict_do_nothing()
-                        .execute(connection)?;
-                }
-                Ok(StoreResponse::AddedListRecipe(recipe))
-            })
-        })
-        .await?
+        diesel::delete(
+            list::table
+                .filter(list::list_id.eq(list_id))
+                .filter(list::id.eq(item_id)),
+        )
+        .execute(connection)?;
+        diesel::insert_into(list::table)
+            .values(&rows)
+            .execute(connection)?;
+        Ok(())
     }
 
-    async fn add_recipe(
-        &self,
-        recipe: &Recipe,
-        ingredients: &Ingredients,
-    ) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        let recipe = recipe.clone();
-        let ingredients = ingredients.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection: PooledConnection<ConnectionManager<SqliteConnection>> =
-                store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let recipe_id = Self::get_or_insert_recipe(connection, recipe.as_str())?;
-                let item_ids = ingredients
-                    .iter()
-                    .map(|ingredient| Self::get_or_insert_item(connection, ingredient.as_str()))
-                    .collect::<Result<Vec<i32>, _>>()?;
+    fn load_item_name(connection: &mut SqliteConnection, item_id: i32) -> Result<Name, StoreError> {
+        Ok(Name::from(
+            schema::items::table
+                .find(item_id)
+                .select(schema::items::name)
+                .first::<String>(connection)?
+                .as_str(),
+        ))
+    }
 
-                for item_id in item_ids {
-                    Self::insert_item_recipe(connection, item_id, recipe_id)?;
-                }
-                Ok(StoreResponse::AddedRecipe(recipe))
-            })
-        })
-        .await?
+    fn load_pantry_rows(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+    ) -> Result<Vec<PantryRow>, StoreError> {
+        use crate::schema::pantry;
+
+        Ok(pantry::table
+            .filter(pantry::item_id.eq(item_id))
+            .order_by(Self::insertion_order())
+            .load::<PantryRow>(connection)?)
     }
 
-    async fn checklist(&self) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                Ok(StoreResponse::Checklist(
-                    schema::items::table
-                        .filter(
-                            schema::items::dsl::id.eq_any(
-                                schema::checklist::table.select(schema::checklist::dsl::id),
-                            ),
-                        )
-                        .load::<Item>(connection)?
-                        .into_iter()
-                        .map(Into::into)
-                        .collect(),
-                ))
-            })
-        })
-        .await?
+    // What the pantry holds of an item, if anything.
+    fn load_pantry_item(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+    ) -> Result<Option<PantryItem>, StoreError> {
+        let rows = Self::load_pantry_rows(connection, item_id)?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let measures = rows.iter().filter_map(PantryRow::measure).collect();
+        let best_before = rows.iter().filter_map(PantryRow::best_before).min();
+        Ok(Some(
+            PantryItem::new(Self::load_item_name(connection, item_id)?)
+                .with_measures(measures)
+                .with_best_before(best_before),
+        ))
     }
 
-    async fn list(&self) -> Result<StoreResponse, StoreError> {
-        let mut list = self.get_list().await?;
-        list = list.with_recipes(self.get_list_recipes().await?);
-        let StoreResponse::Checklist(checklist) = self.checklist().await? else {
-            todo!()
+    // Everything in the pantry, by name.
+    fn load_pantry(connection: &mut SqliteConnection) -> Result<Vec<PantryItem>, StoreError> {
+        use crate::schema::{items, pantry};
+
+        let item_ids = pantry::table
+            .inner_join(items::table)
+            .select(items::id)
+            .distinct()
+            .order_by(items::name)
+            .load::<i32>(connection)?;
+        let mut pantry = Vec::new();
+        for item_id in item_ids {
+            pantry.extend(Self::load_pantry_item(connection, item_id)?);
+        }
+        Ok(pantry)
+    }
+
+    // Replaces the pantry's rows for an item, keeping a row without an amount
+    // when there are no measures.
+    fn replace_pantry_rows(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+        measures: &Measures,
+        best_before: Option<Date>,
+    ) -> Result<(), StoreError> {
+        use crate::schema::pantry;
+
+        let rows = if measures.is_empty() {
+            vec![PantryRow::new(item_id, None, best_before)]
+        } else {
+            measures
+                .iter()
+                .map(|measure| PantryRow::new(item_id, Some(measure), best_before))
+                .collect()
         };
-        list = list.with_checklist(checklist);
-        Ok(StoreResponse::List(list))
+
+        diesel::delete(pantry::table.filter(pantry::item_id.eq(item_id))).execute(connection)?;
+        diesel::insert_into(pantry::table)
+            .values(&rows)
+            .execute(connection)?;
+        Ok(())
     }
 
-    async fn delete_checklist_item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        let item = item.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                diesel::delete(
-                    schema::checklist::table.filter(
-                        schema::checklist::dsl::id.eq_any(
-                            schema::items::table
-                                .select(schema::items::dsl::id)
-                                .filter(schema::items::dsl::name.eq(item.as_str())),
-                        ),
-                    ),
-                )
-                .execute(connection)?;
-                Ok(StoreResponse::DeletedChecklistItem(item))
-            })
-        })
-        .await?
+    // Adds `measure` of an item to the pantry, summing it into what's there
+    // the way amounts are summed on the list.
+    fn stock_pantry(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+        measure: Option<Measure>,
+        best_before: Option<Date>,
+    ) -> Result<(), StoreError> {
+        let item = Self::load_item_name(connection, item_id)?;
+        let rows = Self::load_pantry_rows(connection, item_id)?;
+        let mut measures = rows
+            .iter()
+            .filter_map(PantryRow::measure)
+            .collect::<Measures>();
+        if let Some(measure) = measure {
+            measures.add_with_density(measure, Density::for_item(&item));
+        }
+        let best_before = rows
+            .iter()
+            .filter_map(PantryRow::best_before)
+            .chain(best_before)
+            .min();
+        Self::replace_pantry_rows(connection, item_id, &measures, best_before)
     }
 
-    async fn delete_recipe(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        let recipe = recipe.clone();
-        let StoreResponse::RecipeIngredients(ingredients) =
-            self.recipe_ingredients(&recipe).await?
-        else {
-            todo!()
+    // Counts on the pantry for an ingredient of a recipe being added to a
+    // list. Returns the amount still to buy, or `None` when the pantry has
+    // enough. Stock already counted on by the list's other recipes isn't
+    // counted on again.
+    fn draw_from_pantry(
+        connection: &mut SqliteConnection,
+        list_id: i32,
+        item_id: i32,
+        item: &Name,
+        measure: Option<Measure>,
+    ) -> Result<Option<Option<Measure>>, StoreError> {
+        use crate::schema::list_pantry;
+
+        let rows = Self::load_pantry_rows(connection, item_id)?;
+        if rows.is_empty() {
+            return Ok(Some(measure));
+        }
+        // Having an item with no amount, or needing one without an amount,
+        // is taken to be enough.
+        let Some(need) = measure else {
+            return Ok(None);
         };
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let name = recipe.to_string();
-                diesel::delete(
-                    schema::items_recipes::table.filter(
-                        schema::items_recipes::dsl::recipe_id.eq_any(
-                            schema::recipes::table
-                                .select(schema::recipes::dsl::id)
-                                .filter(schema::recipes::dsl::name.eq(&name)),
-                        ),
-                    ),
-                )
-                .execute(connection)?;
-                diesel::delete(schema::recipes::table.filter(schema::recipes::dsl::name.eq(name)))
-                    .execute(connection)?;
-                if let Some(ingredients) = ingredients {
-                    for item in ingredients.iter() {
-                        diesel::delete(
-                            schema::items::table.filter(schema::items::dsl::name.eq(item.as_str())),
-                        )
-                        .execute(connection)?;
-                    }
-                }
-                Ok(StoreResponse::DeletedRecipe(recipe))
-            })
-        })
-        .await?
-    }
+        if rows.iter().any(|row| row.quantity.is_none()) {
+            return Ok(None);
+        }
 
-    async fn export(&self) -> Result<StoreResponse, StoreError> {
-        let items = self.items().await?;
-        let StoreResponse::List(list) = self.list().await? else {
-            todo!()
+        let counted = list_pantry::table
+            .filter(list_pantry::list_id.eq(list_id))
+            .filter(list_pantry::item_id.eq(item_id))
+            .load::<ListPantryRow>(connection)?;
+        let counted_in = |unit: &str| {
+            counted
+                .iter()
+                .find(|row| row.unit == unit)
+                .map_or(0.0, |row| row.quantity)
         };
 
-        let items = items.collection().to_vec();
+        let available = rows
+            .iter()
+            .filter_map(|row| {
+                let measure = row.measure()?;
+                let left = (measure.quantity().low() - counted_in(&row.unit)).max(0.0);
+                Some(Measure::new(Quantity::Exact(left), measure.unit().cloned()))
+            })
+            .collect::<Vec<_>>();
+        let mut stock = available.clone();
+        let shortfall = draw(&need, &mut stock, Density::for_item(item));
 
-        items.serialize_to_yaml_and_write(ITEMS_YAML_PATH)?;
-        list.serialize_to_yaml_and_write(LIST_YAML_PATH)?;
+        for ((row, before), after) in rows.iter().zip(&available).zip(&stock) {
+            let taken = before.quantity().low() - after.quantity().low();
+            if taken > 0.0 {
+                diesel::replace_into(list_pantry::table)
+                    .values(ListPantryRow {
+                        list_id,
+                        item_id,
+                        unit: row.unit.clone(),
+                        quantity: counted_in(&row.unit) + taken,
+                    })
+                    .execute(connection)?;
+            }
+        }
 
-        Ok(StoreResponse::Exported(items, list))
+        Ok(shortfall.map(Some))
     }
 
-    async fn import_from_json(&self) -> Result<StoreResponse, StoreError> {
-        let import_store = ImportStore::default();
-        let mut connection = self.connection()?;
-        let items = import_store.items()?;
-        tokio::task::spawn_blocking(move || {
-            connection.immediate_transaction(|connection| {
-                import_sections(connection)?;
-                import_items(connection, items)?;
-                Ok(StoreResponse::ImportToSqlite)
-            })
-        })
-        .await?
+    // Gives back the pantry stock a list was counting on for items taken off
+    // it, so that adding them again counts on it afresh.
+    fn release_pantry(
+        connection: &mut SqliteConnection,
+        list_id: i32,
+        item_ids: &[i32],
+    ) -> Result<(), StoreError> {
+        use crate::schema::list_pantry;
+
+        diesel::delete(
+            list_pantry::table
+                .filter(list_pantry::list_id.eq(list_id))
+                .filter(list_pantry::item_id.eq_any(item_ids)),
+        )
+        .execute(connection)?;
+        Ok(())
     }
 
-    async fn items(&self) -> Result<Items, StoreError> {
-        use crate::schema::items;
+    // The meals planned between two dates, which compare as text since
+    // they're stored as YYYY-MM-DD.
+    fn load_meal_plan(
+        connection: &mut SqliteConnection,
+        range: DateRange,
+    ) -> Result<MealPlan, StoreError> {
+        use crate::schema::{meal_plan, recipes};
 
-        let store = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let all_items: Vec<Item> = items::dsl::items.load::<Item>(connection)?;
+        let rows = meal_plan::table
+            .inner_join(recipes::table)
+            .filter(meal_plan::date.between(range.start().to_string(), range.end().to_string()))
+            .select((meal_plan::all_columns, recipes::name))
+            .load::<(MealPlanRow, String)>(connection)?;
+        let meals = rows.into_iter().filter_map(|(row, recipe)| {
+            Some(PlannedMeal::new(row.slot()?, Recipe::new_unchecked(recipe)))
+        });
+        Ok(MealPlan::new(range, meals))
+    }
 
-                all_items
-                    .into_iter()
-                    .map(|item| {
-                        let section = Self::get_section_model_for_item(connection, item.id)?;
-                        let item_recipes = Self::get_recipe_models_for_item(connection, item.id)?;
+    fn load_planned_meal(
+        connection: &mut SqliteConnection,
+        slot: MealSlot,
+    ) -> Result<Option<PlannedMeal>, StoreError> {
+        Ok(
+            Self::load_meal_plan(connection, DateRange::new(slot.date(), slot.date()))?
+                .meals()
+                .iter()
+                .find(|meal| meal.slot() == slot)
+                .cloned(),
+        )
+    }
 
-                        let mut item: common::item::Item = item.into();
+    fn delete_meal_row(
+        connection: &mut SqliteConnection,
+        slot: MealSlot,
+    ) -> Result<(), StoreError> {
+        use crate::schema::meal_plan;
 
-                        if let Some(section) = section {
-                            item = item.with_section(section.name());
-                        }
+        diesel::delete(
+            meal_plan::table
+                .filter(meal_plan::date.eq(slot.date().to_string()))
+                .filter(meal_plan::meal.eq(slot.meal().to_string())),
+        )
+        .execute(connection)?;
+        Ok(())
+    }
 
-                        if let Some(item_recipes) = item_recipes {
-                            item = item.with_recipes(
-                                item_recipes
-                                    .into_iter()
-                                    .map(Into::into)
-                                    .collect::<Vec<Recipe>>()
-                                    .as_slice(),
-                            );
-                        }
+    fn plan_meal(
+        connection: &mut SqliteConnection,
+        slot: MealSlot,
+        recipe: &Recipe,
+    ) -> Result<PlannedMeal, StoreError> {
+        let recipe_id = Self::existing_recipe_id(connection, recipe)?;
+        diesel::replace_into(schema::meal_plan::table)
+            .values(MealPlanRow::new(slot, recipe_id))
+            .execute(connection)?;
+        Ok(PlannedMeal::new(slot, recipe.clone()))
+    }
 
-                        Ok(item)
-                    })
-                    .collect::<Result<_, _>>()
-            })
-        })
-        .await?
+    fn load_list_plan(
+        connection: &mut SqliteConnection,
+        list_id: i32,
+    ) -> Result<Option<DateRange>, StoreError> {
+        Ok(schema::list_plans::table
+            .find(list_id)
+            .first::<ListPlanRow>(connection)
+            .optional()?
+            .and_then(|row| row.range()))
     }
 
-    async fn refresh_list(&self) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                diesel::delete(schema::list::table).execute(connection)?;
-                Ok(StoreResponse::RefreshList)
+    // Writes a query in FTS5's syntax. Every term is quoted so that nothing
+    // in it is read as an operator.
+    fn match_expression(query: &Query) -> String {
+        let term = |term: &Term| match term {
+            Term::Word(word) => format!("\"{word}\""),
+            Term::Prefix(prefix) => format!("\"{prefix}\"*"),
+            Term::Phrase(phrase) => format!("\"{phrase}\""),
+        };
+        let terms = |terms: &[Term], operator: &str| {
+            terms.iter().map(term).collect::<Vec<_>>().join(operator)
+        };
+
+        query
+            .clauses()
+            .iter()
+            .map(|clause| {
+                let include = terms(clause.include(), " AND ");
+                if clause.exclude().is_empty() {
+                    format!("({include})")
+                } else {
+                    format!("(({include}) NOT ({}))", terms(clause.exclude(), " OR "))
+                }
             })
-        })
-        .await?
+            .collect::<Vec<_>>()
+            .join(" OR ")
     }
 
-    async fn recipe_ingredients(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError> {
-        let store = self.clone();
-        let recipe = recipe.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                let Some(results) = Self::get_recipe_model_for_recipe(connection, recipe.as_str())?
-                else {
-                    return Ok(StoreResponse::RecipeIngredients(None));
-                };
+    // Items grouped by their normalized names, leaving out those with no
+    // duplicates. Each group is ordered oldest first.
+    fn find_duplicates(
+        connection: &mut SqliteConnection,
+    ) -> Result<Vec<Vec<(i32, Name)>>, StoreError> {
+        use crate::schema::items;
 
-                let mut v = Vec::<Ingredients>::with_capacity(results.len());
+        let mut groups: Vec<(String, Vec<(i32, Name)>)> = Vec::new();
+        for (id, name) in items::table
+            .order_by(items::id)
+            .select((items::id, items::name))
+            .load::<(i32, String)>(connection)?
+        {
+            let key = normalize(&name);
+            let name = Name::from(name.as_str());
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.push((id, name)),
+                None => groups.push((key, vec![(id, name)])),
+            }
+        }
+        Ok(groups
+            .into_iter()
+            .map(|(_, group)| group)
+            .filter(|group| group.len() > 1)
+            .collect())
+    }
 
-                for recipe in results {
-                    let recipe_id = recipe.id;
+    // Folds one item into another: its recipes, section, aisles, list and
+    // pantry amounts and aliases move across, and its name becomes an alias.
+    fn merge_item(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+        into_id: i32,
+    ) -> Result<Name, StoreError> {
+        use crate::schema::{
+            checklist, item_aliases, items, items_aisles, items_recipes, items_sections, list,
+            pantry,
+        };
 
-                    let results = schema::items_recipes::table
-                        .filter(schema::items_recipes::dsl::recipe_id.eq(&recipe_id))
-                        .load::<models::ItemRecipe>(connection)?;
+        let into_name = Name::from(
+            items::table
+                .find(into_id)
+                .select(items::name)
+                .first::<String>(connection)?
+                .as_str(),
+        );
 
-                    let ingredients = results
-                        .iter()
-                        .map(|item_recipe| Self::load_item(connection, item_recipe.item_id))
-                        .collect::<Result<Vec<Vec<Item>>, _>>()?
-                        .into_iter()
-                        .flatten()
-                        .map(|item| Name::from(item.name.as_str()))
-                        .collect::<Ingredients>();
+        // Where both items have a recipe, section or aisle, the one
+        // merged into is kept.
+        let into_recipes = items_recipes::table
+            .filter(items_recipes::item_id.eq(into_id))
+            .select(items_recipes::recipe_id)
+            .load::<i32>(connection)?;
+        diesel::update(
+            items_recipes::table
+                .filter(items_recipes::item_id.eq(item_id))
+                .filter(items_recipes::recipe_id.ne_all(&into_recipes)),
+        )
+        .set(items_recipes::item_id.eq(into_id))
+        .execute(connection)?;
 
-                    v.push(ingredients);
-                }
+        if diesel::select(diesel::dsl::not(diesel::dsl::exists(
+            items_sections::table.filter(items_sections::item_id.eq(into_id)),
+        )))
+        .get_result::<bool>(connection)?
+        {
+            diesel::update(items_sections::table.filter(items_sections::item_id.eq(item_id)))
+                .set(items_sections::item_id.eq(into_id))
+                .execute(connection)?;
+            diesel::delete(schema::section_suggestions::table.find(into_id)).execute(connection)?;
+        }
 
-                Ok(StoreResponse::RecipeIngredients(
-                    v.into_iter().take(1).next(),
-                ))
-            })
-        })
-        .await?
-    }
+        let into_stores = items_aisles::table
+            .filter(items_aisles::item_id.eq(into_id))
+            .select(items_aisles::store_id)
+            .load::<i32>(connection)?;
+        diesel::update(
+            items_aisles::table
+                .filter(items_aisles::item_id.eq(item_id))
+                .filter(items_aisles::store_id.ne_all(&into_stores)),
+        )
+        .set(items_aisles::item_id.eq(into_id))
+        .execute(connection)?;
 
-    async fn sections(&self) -> Result<StoreResponse, StoreError> {
-        use schema::sections::dsl::sections;
-        let store = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                Ok(StoreResponse::Sections(
-                    sections
-                        .load::<Section>(connection)?
-                        .into_iter()
-                        .map(|sec| sec.name().into())
-                        .collect::<Vec<common::section::Section>>(),
-                ))
+        // List amounts are summed into the other item's.
+        let rows = list::table
+            .filter(list::id.eq(item_id))
+            .order_by(Self::insertion_order())
+            .load::<models::ListItem>(connection)?;
+        diesel::delete(list::table.filter(list::id.eq(item_id))).execute(connection)?;
+        for row in &rows {
+            Self::add_to_list(connection, row.list_id, &into_name, row.measure())?;
+        }
+
+        for row in Self::load_pantry_rows(connection, item_id)? {
+            Self::stock_pantry(connection, into_id, row.measure(), row.best_before())?;
+        }
+
+        if diesel::delete(checklist::table.find(item_id)).execute(connection)? > 0 {
+            diesel::insert_into(checklist::table)
+                .values(NewChecklistItem { id: into_id })
+                .on_conflict_do_nothing()
+                .execute(connection)?;
+        }
+
+        diesel::update(item_aliases::table.filter(item_aliases::item_id.eq(item_id)))
+            .set(item_aliases::item_id.eq(into_id))
+            .execute(connection)?;
+        let item_name = items::table
+            .find(item_id)
+            .select(items::name)
+            .first::<String>(connection)?;
+
+        // Whatever wasn't moved belongs to the item being removed.
+        diesel::delete(items_recipes::table.filter(items_recipes::item_id.eq(item_id)))
+            .execute(connection)?;
+        diesel::delete(items_sections::table.filter(items_sections::item_id.eq(item_id)))
+            .execute(connection)?;
+        diesel::delete(pantry::table.filter(pantry::item_id.eq(item_id))).execute(connection)?;
+        diesel::delete(items::table.find(item_id)).execute(connection)?;
+        diesel::insert_into(item_aliases::table)
+            .values(NewItemAlias {
+                alias: &item_name,
+                item_id: into_id,
+                normalized: normalize(&item_name),
             })
-        })
-        .await?
+            .execute(connection)?;
+
+        Ok(into_name)
     }
 
-    async fn recipes(&self) -> Result<StoreResponse, StoreError> {
-        use schema::recipes::dsl::recipes;
-        let store = self.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = store.connection()?;
-            connection.immediate_transaction(|connection| {
-                Ok(StoreResponse::Recipes(
-                    recipes
-                        .load::<models::RecipeModel>(connection)?
-                        .into_iter()
-                        .map(Into::into)
-                        .collect(),
-                ))
-            })
-        })
-        .await?
+    fn get_section_id(
+        connection: &mut SqliteConnection,
+        section: &str,
+    ) -> Result<Option<i32>, StoreError> {
+        Ok(schema::sections::table
+            .filter(schema::sections::dsl::name.eq(section))
+            .select(schema::sections::dsl::id)
+            .first(connection)
+            .optional()?)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use common::{item::Name, recipes::Ingredients};
+    fn get_or_insert_section(
+        connection: &mut SqliteConnection,
+        section: &str,
+    ) -> Result<i32, StoreError> {
+        match Self::get_section_id(connection, section)? {
+            Some(id) => Ok(id),
+            None => {
+                diesel::insert_into(schema::sections::table)
+                    .values(NewSection { name: section })
+                    .on_conflict_do_nothing()
+                    .execute(connection)?;
+
+                Ok(schema::sections::table
+                    .filter(schema::sections::dsl::name.eq(section))
+                    .select(schema::sections::dsl::id)
+                    .first(connection)?)
+            }
+        }
+    }
+
+    fn existing_section_id(
+        connection: &mut SqliteConnection,
+        section: &common::section::Section,
+    ) -> Result<i32, StoreError> {
+        if let Some(id) = Self::get_section_id(connection, section.as_str())? {
+            return Ok(id);
+        }
+        let sections = schema::sections::table
+            .select(schema::sections::dsl::name)
+            .load::<String>(connection)?;
+        Err(StoreError::section_not_found(
+            section,
+            sections.iter().map(String::as_str),
+        ))
+    }
+
+    fn insert_item_section(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+        section_id: i32,
+    ) -> Result<(), StoreError> {
+        diesel::insert_into(schema::items_sections::table)
+            .values(NewItemSection {
+                item_id,
+                section_id,
+            })
+            .on_conflict_do_nothing()
+            .execute(connection)?;
+        // An item with a section has no need of a suggested one.
+        diesel::delete(schema::section_suggestions::table.find(item_id)).execute(connection)?;
+        Ok(())
+    }
+
+    // Suggests sections for those of `item_ids` that have none, learning
+    // from the sections already given to other items.
+    fn suggest_sections(
+        connection: &mut SqliteConnection,
+        item_ids: &[i32],
+    ) -> Result<(), StoreError> {
+        use crate::schema::{items, items_sections, section_suggestions, sections};
+
+        let unsorted = items::table
+            .filter(items::id.eq_any(item_ids))
+            .filter(diesel::dsl::not(diesel::dsl::exists(
+                items_sections::table.filter(items_sections::item_id.eq(items::id)),
+            )))
+            .filter(diesel::dsl::not(diesel::dsl::exists(
+                section_suggestions::table.filter(section_suggestions::item_id.eq(items::id)),
+            )))
+            .select((items::id, items::name))
+            .load::<(i32, String)>(connection)?;
+        if unsorted.is_empty() {
+            return Ok(());
+        }
+
+        let section_ids = sections::table
+            .select((sections::name, sections::id))
+            .load::<(String, i32)>(connection)?;
+        let assignments = items_sections::table
+            .inner_join(items::table)
+            .inner_join(sections::table)
+            .select((items::name, sections::name))
+            .load::<(String, String)>(connection)?;
+        let classifier = SectionClassifier::new(
+            section_ids
+                .iter()
+                .map(|(name, _)| common::section::Section::from(name.as_str()))
+                .collect(),
+        )
+        .with_assignments(assignments.iter().map(|(item, section)| {
+            (
+                Name::from(item.as_str()),
+                common::section::Section::from(section.as_str()),
+            )
+        }));
+
+        let suggestions = unsorted
+            .iter()
+            .filter_map(|(item_id, name)| {
+                let section = classifier.suggest(&Name::from(name.as_str()))?;
+                let (_, section_id) = section_ids
+                    .iter()
+                    .find(|(name, _)| name == section.as_str())?;
+                Some(NewSectionSuggestion {
+                    item_id: *item_id,
+                    section_id: *section_id,
+                })
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_into(section_suggestions::table)
+            .values(&suggestions)
+            .execute(connection)?;
+        Ok(())
+    }
+
+    fn load_suggestions(
+        connection: &mut SqliteConnection,
+    ) -> Result<Vec<(Name, common::section::Section)>, StoreError> {
+        use crate::schema::{items, section_suggestions, sections};
+
+        Ok(section_suggestions::table
+            .inner_join(items::table)
+            .inner_join(sections::table)
+            .order_by(items::name)
+            .select((items::name, sections::name))
+            .load::<(String, String)>(connection)?
+            .into_iter()
+            .map(|(item, section)| {
+                (
+                    Name::from(item.as_str()),
+                    common::section::Section::from(section),
+                )
+            })
+            .collect())
+    }
+
+    // Sections in the order set with `order_sections`.
+    fn load_section_order(
+        connection: &mut SqliteConnection,
+    ) -> Result<Vec<common::section::Section>, StoreError> {
+        use crate::schema::sections;
+
+        Ok(sections::table
+            .filter(sections::position.is_not_null())
+            .order_by(sections::position)
+            .select(sections::name)
+            .load::<String>(connection)?
+            .into_iter()
+            .map(common::section::Section::from)
+            .collect())
+    }
+
+    // The sections of those of `item_ids` that have one.
+    fn load_item_sections(
+        connection: &mut SqliteConnection,
+        item_ids: impl IntoIterator<Item = i32>,
+    ) -> Result<Vec<(i32, String)>, StoreError> {
+        use crate::schema::{items_sections, sections};
+
+        Ok(items_sections::table
+            .inner_join(sections::table.on(sections::id.eq(items_sections::section_id)))
+            .filter(items_sections::item_id.eq_any(item_ids.into_iter().collect::<Vec<_>>()))
+            .select((items_sections::item_id, sections::name))
+            .load::<(i32, String)>(connection)?)
+    }
+
+    fn load_list(connection: &mut SqliteConnection, list_id: i32) -> Result<List, StoreError> {
+        use crate::schema::list;
+
+        let rows = list::table
+            .filter(list::list_id.eq(list_id))
+            .order_by(Self::insertion_order())
+            .load::<models::ListItem>(connection)?;
+
+        let item_sections = Self::load_item_sections(connection, rows.iter().map(|row| row.id))?;
+
+        Ok(schema::items::table
+            .filter(
+                schema::items::dsl::id.eq_any(
+                    list::table
+                        .filter(list::list_id.eq(list_id))
+                        .select(list::id),
+                ),
+            )
+            .load::<Item>(connection)?
+            .into_iter()
+            .map(|item| {
+                let rows = rows.iter().filter(|row| row.id == item.id);
+                let (checked, checked_at) = rows
+                    .clone()
+                    .next()
+                    .map(|row| (row.checked, row.checked_at.clone()))
+                    .unwrap_or_default();
+                let measures = rows.filter_map(models::ListItem::measure).collect();
+                let section = item_sections
+                    .iter()
+                    .find(|(item_id, _)| *item_id == item.id)
+                    .map(|(_, section)| section.clone());
+                let mut item: common::item::Item = item.into();
+                if let Some(section) = section {
+                    item = item.with_section(&section);
+                }
+                ListItem::new(item)
+                    .with_measures(measures)
+                    .with_checked(checked, checked_at)
+            })
+            .collect::<List>())
+    }
+
+    fn load_list_recipes(
+        connection: &mut SqliteConnection,
+        list_id: i32,
+    ) -> Result<Vec<Recipe>, StoreError> {
+        use crate::schema::list_recipes;
+
+        Ok(schema::recipes::table
+            .filter(
+                schema::recipes::dsl::id.eq_any(
+                    list_recipes::table
+                        .filter(list_recipes::list_id.eq(list_id))
+                        .select(list_recipes::id),
+                ),
+            )
+            .load::<RecipeModel>(connection)?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    // The checklist's items along with their sections.
+    fn load_checklist(
+        connection: &mut SqliteConnection,
+    ) -> Result<Vec<common::item::Item>, StoreError> {
+        let items = schema::items::table
+            .filter(
+                schema::items::dsl::id
+                    .eq_any(schema::checklist::table.select(schema::checklist::dsl::id)),
+            )
+            .load::<Item>(connection)?;
+        let item_sections = Self::load_item_sections(connection, items.iter().map(|item| item.id))?;
+
+        Ok(items
+            .into_iter()
+            .map(|item| {
+                let section = item_sections
+                    .iter()
+                    .find(|(item_id, _)| *item_id == item.id)
+                    .map(|(_, section)| section.clone());
+                let item: common::item::Item = item.into();
+                match section {
+                    Some(section) => item.with_section(&section),
+                    None => item,
+                }
+            })
+            .collect())
+    }
+
+    fn load_item(connection: &mut SqliteConnection, item_id: i32) -> Result<Vec<Item>, StoreError> {
+        Ok(schema::items::table
+            .filter(schema::items::dsl::id.eq(&item_id))
+            .load::<Item>(connection)?)
+    }
+
+    fn load_recipe_ingredients(
+        connection: &mut SqliteConnection,
+        recipe_id: i32,
+    ) -> Result<Ingredients, StoreError> {
+        let results = schema::items_recipes::table
+            .filter(schema::items_recipes::dsl::recipe_id.eq(&recipe_id))
+            .load::<models::ItemRecipe>(connection)?;
+
+        Ok(results
+            .iter()
+            .map(|item_recipe| {
+                Ok(Self::load_item(connection, item_recipe.item_id)?
+                    .into_iter()
+                    .map(|item| item_recipe.ingredient(&item.name)))
+            })
+            .collect::<Result<Vec<_>, StoreError>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Ingredients>())
+    }
+
+    fn get_recipe_model_for_recipe(
+        connection: &mut SqliteConnection,
+        recipe: &str,
+    ) -> Result<Option<Vec<RecipeModel>>, StoreError> {
+        Ok(schema::recipes::table
+            .filter(schema::recipes::dsl::name.eq(recipe))
+            .load::<models::RecipeModel>(connection)
+            .optional()?)
+    }
+
+    fn get_section_model_for_item(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+    ) -> Result<Option<Section>, StoreError> {
+        use crate::schema::{items_sections, sections};
+
+        Ok(items_sections::table
+            .filter(items_sections::item_id.eq(item_id))
+            .inner_join(sections::table.on(sections::id.eq(items_sections::section_id)))
+            .select(Section::as_select())
+            .first(connection)
+            .optional()?)
+    }
+
+    // The item along with its section and the recipes that use it.
+    fn item_details(
+        connection: &mut SqliteConnection,
+        item: Item,
+    ) -> Result<common::item::Item, StoreError> {
+        let section = Self::get_section_model_for_item(connection, item.id)?;
+        let item_recipes = Self::get_recipe_models_for_item(connection, item.id)?;
+
+        let mut item: common::item::Item = item.into();
+
+        if let Some(section) = section {
+            item = item.with_section(section.name());
+        }
+
+        if let Some(item_recipes) = item_recipes {
+            item = item.with_recipes(
+                item_recipes
+                    .into_iter()
+                    .map(Into::into)
+                    .collect::<Vec<Recipe>>()
+                    .as_slice(),
+            );
+        }
+
+        Ok(item)
+    }
+
+    fn get_recipe_models_for_item(
+        connection: &mut SqliteConnection,
+        item_id: i32,
+    ) -> Result<Option<Vec<RecipeModel>>, StoreError> {
+        use crate::schema::{items_recipes, recipes};
+
+        Ok(items_recipes::table
+            .filter(items_recipes::item_id.eq(item_id))
+            .inner_join(recipes::table.on(recipes::id.eq(items_recipes::recipe_id)))
+            .select(RecipeModel::as_select())
+            .distinct()
+            .load(connection)
+            .optional()?)
+    }
+
+    fn get_store_id(
+        connection: &mut SqliteConnection,
+        store: &StoreName,
+    ) -> Result<Option<i32>, StoreError> {
+        Ok(schema::stores::table
+            .filter(schema::stores::dsl::name.eq(store.as_str()))
+            .select(schema::stores::dsl::id)
+            .first(connection)
+            .optional()?)
+    }
+
+    fn existing_store_id(
+        connection: &mut SqliteConnection,
+        store: &StoreName,
+    ) -> Result<i32, StoreError> {
+        Self::get_store_id(connection, store)?
+            .ok_or_else(|| StoreError::StoreNotFound(store.to_string()))
+    }
+
+    fn existing_aisle_id(
+        connection: &mut SqliteConnection,
+        store_id: i32,
+        aisle: &Aisle,
+    ) -> Result<i32, StoreError> {
+        use crate::schema::aisles;
+
+        aisles::table
+            .filter(aisles::store_id.eq(store_id))
+            .filter(aisles::name.eq(aisle.as_str()))
+            .select(aisles::id)
+            .first(connection)
+            .optional()?
+            .ok_or_else(|| StoreError::AisleNotFound(aisle.to_string()))
+    }
+
+    // Puts a store's aisles in the order given, dropping any aisles not named
+    // along with whatever was placed in them.
+    fn replace_aisles(
+        connection: &mut SqliteConnection,
+        store_id: i32,
+        aisles: &[Aisle],
+    ) -> Result<(), StoreError> {
+        use crate::schema::aisles as table;
+
+        diesel::delete(
+            table::table
+                .filter(table::store_id.eq(store_id))
+                .filter(table::name.ne_all(aisles.iter().map(Aisle::as_str))),
+        )
+        .execute(connection)?;
+
+        for (position, aisle) in (1..).zip(aisles) {
+            let updated = diesel::update(
+                table::table
+                    .filter(table::store_id.eq(store_id))
+                    .filter(table::name.eq(aisle.as_str())),
+            )
+            .set(table::position.eq(position))
+            .execute(connection)?;
+            if updated == 0 {
+                diesel::insert_into(table::table)
+                    .values(NewAisle {
+                        store_id,
+                        name: aisle.as_str(),
+                        position,
+                    })
+                    .execute(connection)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn load_layout(
+        connection: &mut SqliteConnection,
+        store_id: i32,
+        store: StoreName,
+    ) -> Result<StoreLayout, StoreError> {
+        use crate::schema::{aisles, items, items_aisles, sections, sections_aisles};
+
+        let aisle_models = aisles::table
+            .filter(aisles::store_id.eq(store_id))
+            .order_by(aisles::position)
+            .load::<AisleModel>(connection)?;
+        let aisle = |aisle_id: i32| {
+            aisle_models
+                .iter()
+                .find(|aisle| aisle.id == aisle_id)
+                .map(|aisle| Aisle::from(aisle.name.as_str()))
+                .unwrap_or_default()
+        };
+
+        let mut layout = StoreLayout::new(store).with_aisles(
+            aisle_models
+                .iter()
+                .map(|aisle| Aisle::from(aisle.name.as_str()))
+                .collect(),
+        );
+
+        for (section, aisle_id) in sections_aisles::table
+            .filter(sections_aisles::store_id.eq(store_id))
+            .inner_join(sections::table)
+            .select((sections::name, sections_aisles::aisle_id))
+            .load::<(String, i32)>(connection)?
+        {
+            layout = layout.with_section_aisle(section.into(), aisle(aisle_id));
+        }
+
+        for (item, aisle_id) in items_aisles::table
+            .filter(items_aisles::store_id.eq(store_id))
+            .inner_join(items::table)
+            .select((items::name, items_aisles::aisle_id))
+            .load::<(String, i32)>(connection)?
+        {
+            layout = layout.with_item_aisle(Name::from(item.as_str()), aisle(aisle_id));
+        }
+
+        Ok(layout)
+    }
+}
+
+impl Storage for SqliteStore {
+    async fn add_checklist_item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let id = Self::get_or_insert_item(connection, item.as_str())?;
+                let query = {
+                    diesel::insert_into(schema::checklist::table)
+                        .values(NewChecklistItem { id })
+                        .on_conflict_do_nothing()
+                };
+                query.execute(connection)?;
+                Ok(StoreResponse::AddedChecklistItem(item))
+            })
+        })
+        .await?
+    }
+
+    async fn add_item_alias(&self, item: &Name, alias: &Name) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        let alias = alias.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let item_id = Self::existing_item_id(connection, &item)?;
+                if Self::get_item_id(connection, alias.as_str())?.is_some() {
+                    return Err(StoreError::AliasExists(alias.to_string()));
+                }
+                diesel::insert_into(schema::item_aliases::table)
+                    .values(NewItemAlias {
+                        alias: alias.as_str(),
+                        item_id,
+                        normalized: alias.normalized(),
+                    })
+                    .execute(connection)?;
+                Ok(StoreResponse::AddedItemAlias { item, alias })
+            })
+        })
+        .await?
+    }
+
+    async fn add_item(
+        &self,
+        item: &Name,
+        section: &Option<common::section::Section>,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        let section = section.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let item_name = item.to_string();
+                let item_id = Self::get_or_insert_item(connection, &item_name)?;
+                if let Some(section) = section {
+                    let section_id = Self::get_or_insert_section(connection, section.as_str())?;
+                    Self::insert_item_section(connection, item_id, section_id)?;
+                }
+                Ok(StoreResponse::AddedItem(item))
+            })
+        })
+        .await?
+    }
+
+    async fn add_list(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Self::insert_list(connection, &list)?;
+                Ok(StoreResponse::AddedList(list))
+            })
+        })
+        .await?
+    }
+
+    async fn add_list_item(
+        &self,
+        list: &ListName,
+        item: &Name,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::writable_list_id(connection, &list)?;
+                Self::add_to_list(connection, list_id, &item, None)?;
+                Ok(StoreResponse::AddedListItem(item))
+            })
+        })
+        .await?
+    }
+
+    async fn add_list_recipe(
+        &self,
+        list: &ListName,
+        recipe: &Recipe,
+        servings: Option<Servings>,
+    ) -> Result<StoreResponse, StoreError> {
+        let StoreResponse::RecipeIngredients(Some(ingredients)) =
+            self.scaled_recipe_ingredients(recipe, servings).await?
+        else {
+            // TODO:
+            return Err(StoreError::RecipeIngredients(recipe.to_string()));
+        };
+
+        let store = self.clone();
+        let list = list.clone();
+        let recipe = recipe.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::writable_list_id(connection, &list)?;
+                Self::add_recipe_to_list(connection, list_id, &recipe, &ingredients)?;
+                Ok(StoreResponse::AddedListRecipe(recipe))
+            })
+        })
+        .await?
+    }
+
+    async fn add_list_from_plan(
+        &self,
+        list: &ListName,
+        range: DateRange,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let plan = Self::load_meal_plan(connection, range)?;
+                if plan.meals().is_empty() {
+                    return Err(StoreError::MealNotPlanned(range.to_string()));
+                }
+
+                let list_id = Self::writable_list_id(connection, &list)?;
+                let mut recipes: Vec<Recipe> = Vec::new();
+                for meal in plan.meals() {
+                    let recipe_id = Self::existing_recipe_id(connection, meal.recipe())?;
+                    let ingredients = Self::load_recipe_ingredients(connection, recipe_id)?;
+                    Self::add_recipe_to_list(connection, list_id, meal.recipe(), &ingredients)?;
+                    if !recipes.contains(meal.recipe()) {
+                        recipes.push(meal.recipe().clone());
+                    }
+                }
+                diesel::replace_into(schema::list_plans::table)
+                    .values(ListPlanRow::new(list_id, range))
+                    .execute(connection)?;
+
+                Ok(StoreResponse::AddedListFromPlan {
+                    list,
+                    range,
+                    recipes,
+                })
+            })
+        })
+        .await?
+    }
+
+    async fn add_pantry_item(
+        &self,
+        item: &Name,
+        measure: Option<&Measure>,
+        best_before: Option<Date>,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        let measure = measure.cloned();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let item_id = Self::get_or_insert_item(connection, item.as_str())?;
+                Self::stock_pantry(connection, item_id, measure, best_before)?;
+                let stocked = Self::load_pantry_item(connection, item_id)?
+                    .ok_or_else(|| StoreError::PantryItemNotFound(item.to_string()))?;
+                Ok(StoreResponse::AddedPantryItem(stocked))
+            })
+        })
+        .await?
+    }
+
+    async fn add_planned_meal(
+        &self,
+        slot: MealSlot,
+        recipe: &Recipe,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::PlannedMeal(Self::plan_meal(
+                    connection, slot, &recipe,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn add_recipe(
+        &self,
+        recipe: &Recipe,
+        ingredients: &Ingredients,
+        servings: Option<Servings>,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        let ingredients = ingredients.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection: PooledConnection<ConnectionManager<SqliteConnection>> =
+                store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let recipe_id = Self::get_or_insert_recipe(connection, recipe.as_str())?;
+                if let Some(servings) = servings {
+                    diesel::update(schema::recipes::table.find(recipe_id))
+                        .set(schema::recipes::dsl::servings.eq(servings.get() as i32))
+                        .execute(connection)?;
+                }
+                let mut item_ids = Vec::new();
+                for ingredient in ingredients.iter() {
+                    let item_id = Self::get_or_insert_item(connection, ingredient.name().as_str())?;
+                    Self::insert_item_recipe(connection, item_id, recipe_id, ingredient)?;
+                    item_ids.push(item_id);
+                }
+                Self::suggest_sections(connection, &item_ids)?;
+                Ok(StoreResponse::AddedRecipe(recipe))
+            })
+        })
+        .await?
+    }
+
+    async fn add_recipe_ingredient(
+        &self,
+        recipe: &Recipe,
+        ingredient: &Ingredient,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        let ingredient = ingredient.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let recipe_id = Self::existing_recipe_id(connection, &recipe)?;
+                Self::replace_item_recipe(connection, recipe_id, &ingredient)?;
+                Self::updated_recipe(connection, recipe_id, recipe)
+            })
+        })
+        .await?
+    }
+
+    async fn add_section(
+        &self,
+        section: &common::section::Section,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let section = section.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                if Self::get_section_id(connection, section.as_str())?.is_some() {
+                    return Err(StoreError::SectionExists(section.to_string()));
+                }
+                diesel::insert_into(schema::sections::table)
+                    .values(NewSection {
+                        name: section.as_str(),
+                    })
+                    .execute(connection)?;
+                Ok(StoreResponse::AddedSection(section))
+            })
+        })
+        .await?
+    }
+
+    async fn checklist(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::Checklist(Self::load_checklist(connection)?))
+            })
+        })
+        .await?
+    }
+
+    async fn cookable_recipes(&self, available: &Available) -> Result<StoreResponse, StoreError> {
+        use crate::schema::{checklist, items, items_recipes, pantry, recipes};
+
+        let store = self.clone();
+        let available = available.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let have = match available {
+                    Available::Checklist => checklist::table
+                        .select(checklist::id)
+                        .load::<i32>(connection)?,
+                    Available::Items(names) => names
+                        .iter()
+                        .filter_map(|name| Self::get_item_id(connection, name.as_str()).transpose())
+                        .collect::<Result<Vec<_>, _>>()?,
+                    Available::Pantry => pantry::table
+                        .select(pantry::item_id)
+                        .distinct()
+                        .load::<i32>(connection)?,
+                };
+
+                let mut recipes: Vec<(i32, RecipeCoverage)> = Vec::new();
+                for (recipe_id, recipe, item_id, item) in items_recipes::table
+                    .inner_join(recipes::table)
+                    .inner_join(items::table)
+                    .order_by((recipes::id, items::name))
+                    .select((recipes::id, recipes::name, items::id, items::name))
+                    .distinct()
+                    .load::<(i32, String, i32, String)>(connection)?
+                {
+                    if recipes.last().map(|(id, _)| *id) != Some(recipe_id) {
+                        let recipe = Recipe::new_unchecked(recipe);
+                        recipes.push((recipe_id, RecipeCoverage::new(recipe)));
+                    }
+                    let (_, coverage) = recipes.last_mut().expect("recipe just added");
+                    coverage.add_ingredient(Name::from(item.as_str()), have.contains(&item_id));
+                }
+
+                let mut recipes = recipes
+                    .into_iter()
+                    .map(|(_, coverage)| coverage)
+                    .filter(|coverage| !coverage.have().is_empty())
+                    .collect::<Vec<_>>();
+                recipes.sort_by(|a, b| {
+                    b.have()
+                        .len()
+                        .cmp(&a.have().len())
+                        .then(a.missing().len().cmp(&b.missing().len()))
+                        .then_with(|| a.recipe().as_str().cmp(b.recipe().as_str()))
+                });
+                Ok(StoreResponse::RecipeCoverage(recipes))
+            })
+        })
+        .await?
+    }
+
+    async fn copy_list(
+        &self,
+        list: &ListName,
+        new_name: &ListName,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::{list, list_recipes};
+
+        let store = self.clone();
+        let from = list.clone();
+        let new_name = new_name.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let from_id = Self::existing_list_id(connection, &from)?;
+                let to_id = Self::insert_list(connection, &new_name)?;
+
+                let rows = list::table
+                    .filter(list::list_id.eq(from_id))
+                    .load::<models::ListItem>(connection)?
+                    .into_iter()
+                    .map(|row| NewListItem {
+                        list_id: to_id,
+                        id: row.id,
+                        unit: row.unit,
+                        quantity: row.quantity,
+                        quantity_max: row.quantity_max,
+                        checked: row.checked,
+                        checked_at: row.checked_at,
+                    })
+                    .collect::<Vec<_>>();
+                diesel::insert_into(list::table)
+                    .values(&rows)
+                    .execute(connection)?;
+
+                let recipes = list_recipes::table
+                    .filter(list_recipes::list_id.eq(from_id))
+                    .load::<models::ListRecipe>(connection)?
+                    .into_iter()
+                    .map(|row| NewListRecipe {
+                        list_id: to_id,
+                        id: row.id,
+                    })
+                    .collect::<Vec<_>>();
+                diesel::insert_into(list_recipes::table)
+                    .values(&recipes)
+                    .execute(connection)?;
+
+                Ok(StoreResponse::AddedList(new_name))
+            })
+        })
+        .await?
+    }
+
+    async fn item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let id = Self::existing_item_id(connection, &item)?;
+                let model = schema::items::table.find(id).first::<Item>(connection)?;
+                Ok(StoreResponse::Item(Self::item_details(connection, model)?))
+            })
+        })
+        .await?
+    }
+
+    async fn item_aliases(&self) -> Result<StoreResponse, StoreError> {
+        use crate::schema::{item_aliases, items};
+
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::ItemAliases(
+                    item_aliases::table
+                        .inner_join(items::table)
+                        .order_by((items::name, item_aliases::alias))
+                        .select((item_aliases::alias, items::name))
+                        .load::<(String, String)>(connection)?
+                        .into_iter()
+                        .map(|(alias, item)| {
+                            (Name::from(alias.as_str()), Name::from(item.as_str()))
+                        })
+                        .collect(),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn duplicate_items(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::DuplicateItems(
+                    Self::find_duplicates(connection)?
+                        .into_iter()
+                        .map(|group| group.into_iter().map(|(_, name)| name).collect())
+                        .collect(),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn full_text_search(&self, query: &Query) -> Result<StoreResponse, StoreError> {
+        use diesel::sql_types::Text;
+
+        let store = self.clone();
+        let expression = Self::match_expression(query);
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let recipes = diesel::sql_query(
+                    "SELECT name FROM recipes_search WHERE recipes_search MATCH ? ORDER BY rank",
+                )
+                .bind::<Text, _>(&expression)
+                .load::<SearchHit>(connection)?;
+                let items = diesel::sql_query(
+                    "SELECT name FROM items_search WHERE items_search MATCH ? ORDER BY rank",
+                )
+                .bind::<Text, _>(&expression)
+                .load::<SearchHit>(connection)?;
+
+                Ok(StoreResponse::SearchResults(
+                    recipes
+                        .into_iter()
+                        .map(|hit| SearchMatch::new(MatchKind::Recipe, hit.name))
+                        .chain(
+                            items
+                                .into_iter()
+                                .map(|hit| SearchMatch::new(MatchKind::Item, hit.name)),
+                        )
+                        .collect(),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn list(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let name = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_list_id(connection, &name)?;
+                let recipes = Self::load_list_recipes(connection, list_id)?;
+                let checklist = Self::load_checklist(connection)?;
+                let section_order = Self::load_section_order(connection)?;
+                let plan = Self::load_list_plan(connection, list_id)?;
+                Ok(StoreResponse::List(
+                    Self::load_list(connection, list_id)?
+                        .with_name(name)
+                        .with_section_order(section_order)
+                        .with_recipes(recipes)
+                        .with_checklist(checklist)
+                        .with_plan(plan),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn list_recipes(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_list_id(connection, &list)?;
+                Ok(StoreResponse::Recipes(Self::load_list_recipes(
+                    connection, list_id,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn lists(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let (archived, active): (Vec<_>, Vec<_>) = schema::lists::table
+                    .load::<ListModel>(connection)?
+                    .into_iter()
+                    .partition(|model| model.archived);
+                let names = |models: Vec<ListModel>| {
+                    models
+                        .into_iter()
+                        .map(|model| ListName::from(model.name))
+                        .collect()
+                };
+                Ok(StoreResponse::Lists {
+                    active: names(active),
+                    archived: names(archived),
+                })
+            })
+        })
+        .await?
+    }
+
+    async fn meal_plan(&self, range: DateRange) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::MealPlan(Self::load_meal_plan(
+                    connection, range,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn pantry(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::Pantry(Self::load_pantry(connection)?))
+            })
+        })
+        .await?
+    }
+
+    async fn expiring(&self, today: Date, within_days: u32) -> Result<StoreResponse, StoreError> {
+        use crate::schema::{items, items_recipes, recipes};
+
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let pantry = Self::load_pantry(connection)?;
+
+                let mut ingredients: Vec<(Recipe, Vec<Name>)> = Vec::new();
+                for (recipe, item) in items_recipes::table
+                    .inner_join(recipes::table)
+                    .inner_join(items::table)
+                    .order_by(recipes::name)
+                    .select((recipes::name, items::name))
+                    .distinct()
+                    .load::<(String, String)>(connection)?
+                {
+                    let recipe = Recipe::new_unchecked(recipe);
+                    let item = Name::from(item.as_str());
+                    match ingredients.last_mut() {
+                        Some((last, items)) if *last == recipe => items.push(item),
+                        _ => ingredients.push((recipe, vec![item])),
+                    }
+                }
+
+                Ok(StoreResponse::Expiring(ExpiryReport::new(
+                    today,
+                    within_days,
+                    pantry,
+                    ingredients,
+                )))
+            })
+        })
+        .await?
+    }
+
+    async fn clear_checklist(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                diesel::delete(schema::checklist::table).execute(connection)?;
+                Ok(StoreResponse::ClearedChecklist)
+            })
+        })
+        .await?
+    }
+
+    async fn clear_list(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        use crate::schema::list_recipes;
+
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_writable_list_id(connection, &list)?;
+                diesel::delete(schema::list::table.filter(schema::list::list_id.eq(list_id)))
+                    .execute(connection)?;
+                diesel::delete(list_recipes::table.filter(list_recipes::list_id.eq(list_id)))
+                    .execute(connection)?;
+                diesel::delete(
+                    schema::list_pantry::table.filter(schema::list_pantry::list_id.eq(list_id)),
+                )
+                .execute(connection)?;
+                diesel::delete(schema::list_plans::table.find(list_id)).execute(connection)?;
+                Ok(StoreResponse::ClearedList(list))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let id = Self::exact_item_id(connection, &item)?;
+                diesel::delete(
+                    schema::items_recipes::table.filter(schema::items_recipes::dsl::item_id.eq(id)),
+                )
+                .execute(connection)?;
+                diesel::delete(
+                    schema::items_sections::table
+                        .filter(schema::items_sections::dsl::item_id.eq(id)),
+                )
+                .execute(connection)?;
+                diesel::delete(schema::list::table.filter(schema::list::dsl::id.eq(id)))
+                    .execute(connection)?;
+                diesel::delete(schema::checklist::table.filter(schema::checklist::dsl::id.eq(id)))
+                    .execute(connection)?;
+                diesel::delete(schema::pantry::table.filter(schema::pantry::dsl::item_id.eq(id)))
+                    .execute(connection)?;
+                diesel::delete(schema::items::table.find(id)).execute(connection)?;
+                Ok(StoreResponse::DeletedItem(item))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_item_alias(&self, alias: &Name) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let alias = alias.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let deleted = diesel::delete(schema::item_aliases::table.find(alias.as_str()))
+                    .execute(connection)?;
+                if deleted == 0 {
+                    return Err(StoreError::AliasNotFound(alias.to_string()));
+                }
+                Ok(StoreResponse::DeletedItemAlias(alias))
+            })
+        })
+        .await?
+    }
+
+    async fn clear_checked_list_items(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        use crate::schema::list;
+
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_writable_list_id(connection, &list)?;
+                let checked = list::table
+                    .filter(list::list_id.eq(list_id))
+                    .filter(list::checked.eq(true));
+                let ids = checked.select(list::id).load::<i32>(connection)?;
+                let items = schema::items::table
+                    .filter(schema::items::dsl::id.eq_any(&ids))
+                    .select(schema::items::dsl::name)
+                    .load::<String>(connection)?
+                    .into_iter()
+                    .map(|name| Name::from(name.as_str()))
+                    .collect();
+                diesel::delete(checked).execute(connection)?;
+                Self::release_pantry(connection, list_id, &ids)?;
+                Ok(StoreResponse::ClearedCheckedListItems(items))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_list(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_list_id(connection, &list)?;
+                diesel::delete(schema::lists::table.find(list_id)).execute(connection)?;
+                Ok(StoreResponse::DeletedList(list))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_list_item(
+        &self,
+        list: &ListName,
+        item: &Name,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::list;
+
+        let store = self.clone();
+        let list = list.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_writable_list_id(connection, &list)?;
+                let id = Self::existing_item_id(connection, &item)?;
+                let deleted = diesel::delete(
+                    list::table
+                        .filter(list::list_id.eq(list_id))
+                        .filter(list::id.eq(id)),
+                )
+                .execute(connection)?;
+                if deleted == 0 {
+                    return Err(StoreError::ListItemNotFound(item.to_string()));
+                }
+                Self::release_pantry(connection, list_id, &[id])?;
+                Ok(StoreResponse::DeletedListItem(item))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_pantry_item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
+        use crate::schema::pantry;
+
+        let store = self.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let deleted = match Self::get_item_id(connection, item.as_str())? {
+                    Some(item_id) => {
+                        diesel::delete(pantry::table.filter(pantry::item_id.eq(item_id)))
+                            .execute(connection)?
+                    }
+                    None => 0,
+                };
+                if deleted == 0 {
+                    return Err(StoreError::PantryItemNotFound(item.to_string()));
+                }
+                Ok(StoreResponse::DeletedPantryItem(item))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_planned_meal(&self, slot: MealSlot) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let meal = Self::load_planned_meal(connection, slot)?
+                    .ok_or_else(|| StoreError::MealNotPlanned(slot.to_string()))?;
+                Self::delete_meal_row(connection, slot)?;
+                Ok(StoreResponse::DeletedPlannedMeal(meal))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_recipe_ingredient(
+        &self,
+        recipe: &Recipe,
+        ingredient: &Name,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        let ingredient = ingredient.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let recipe_id = Self::existing_recipe_id(connection, &recipe)?;
+                Self::remove_ingredient(connection, recipe_id, &ingredient)?;
+                Self::updated_recipe(connection, recipe_id, recipe)
+            })
+        })
+        .await?
+    }
+
+    async fn edit_recipe_ingredient(
+        &self,
+        recipe: &Recipe,
+        ingredient: &Name,
+        replacement: &Ingredient,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        let ingredient = ingredient.clone();
+        let replacement = replacement.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let recipe_id = Self::existing_recipe_id(connection, &recipe)?;
+                Self::remove_ingredient(connection, recipe_id, &ingredient)?;
+                Self::replace_item_recipe(connection, recipe_id, &replacement)?;
+                Self::updated_recipe(connection, recipe_id, recipe)
+            })
+        })
+        .await?
+    }
+
+    async fn delete_checklist_item(&self, item: &Name) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                diesel::delete(
+                    schema::checklist::table.filter(
+                        schema::checklist::dsl::id.eq_any(
+                            schema::items::table
+                                .select(schema::items::dsl::id)
+                                .filter(schema::items::dsl::name.eq(item.as_str())),
+                        ),
+                    ),
+                )
+                .execute(connection)?;
+                Ok(StoreResponse::DeletedChecklistItem(item))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_recipe(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError> {
+        use crate::schema::{
+            items, items_recipes, items_sections, list_recipes, meal_plan, recipes,
+        };
+
+        let store = self.clone();
+        let recipe = recipe.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let recipe_id = Self::existing_recipe_id(connection, &recipe)?;
+                let ingredients = items_recipes::table
+                    .filter(items_recipes::recipe_id.eq(recipe_id))
+                    .inner_join(items::table.on(items::id.eq(items_recipes::item_id)))
+                    .select(items::all_columns)
+                    .distinct()
+                    .load::<Item>(connection)?;
+                // The meals the recipe was planned for go along with it.
+                let mut unplanned = meal_plan::table
+                    .filter(meal_plan::recipe_id.eq(recipe_id))
+                    .load::<MealPlanRow>(connection)?
+                    .iter()
+                    .filter_map(MealPlanRow::slot)
+                    .collect::<Vec<_>>();
+                unplanned.sort_by_key(|slot| (slot.date(), slot.meal()));
+
+                diesel::delete(items_recipes::table.filter(items_recipes::recipe_id.eq(recipe_id)))
+                    .execute(connection)?;
+                diesel::delete(list_recipes::table.filter(list_recipes::id.eq(recipe_id)))
+                    .execute(connection)?;
+                diesel::delete(recipes::table.find(recipe_id)).execute(connection)?;
+
+                let mut kept = Vec::new();
+                let mut removed = Vec::new();
+                for item in ingredients {
+                    if Self::item_is_referenced(connection, item.id)? {
+                        kept.push(Name::from(item.name.as_str()));
+                    } else {
+                        diesel::delete(
+                            items_sections::table.filter(items_sections::item_id.eq(item.id)),
+                        )
+                        .execute(connection)?;
+                        diesel::delete(items::table.find(item.id)).execute(connection)?;
+                        removed.push(Name::from(item.name.as_str()));
+                    }
+                }
+
+                Ok(StoreResponse::DeletedRecipe {
+                    recipe,
+                    kept,
+                    removed,
+                    unplanned,
+                })
+            })
+        })
+        .await?
+    }
+
+    async fn export(&self) -> Result<StoreResponse, StoreError> {
+        let items = self.items().await?;
+        let StoreResponse::List(list) = self.list(&ListName::default()).await? else {
+            todo!()
+        };
+
+        let items = items.collection().to_vec();
+
+        items.serialize_to_yaml_and_write(ITEMS_YAML_PATH)?;
+        list.serialize_to_yaml_and_write(LIST_YAML_PATH)?;
+
+        Ok(StoreResponse::Exported(items, list))
+    }
+
+    async fn import_from_json(&self) -> Result<StoreResponse, StoreError> {
+        let import_store = ImportStore::default();
+        let mut connection = self.connection()?;
+        let items = import_store.items()?;
+        tokio::task::spawn_blocking(move || {
+            connection.immediate_transaction(|connection| {
+                import_sections(connection)?;
+                import_items(connection, items)?;
+                Ok(StoreResponse::ImportToSqlite)
+            })
+        })
+        .await?
+    }
+
+    async fn items(&self) -> Result<Items, StoreError> {
+        use crate::schema::items;
+
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let all_items: Vec<Item> = items::dsl::items.load::<Item>(connection)?;
+
+                all_items
+                    .into_iter()
+                    .map(|item| Self::item_details(connection, item))
+                    .collect::<Result<_, _>>()
+            })
+        })
+        .await?
+    }
+
+    async fn archive_list(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_list_id(connection, &list)?;
+                diesel::update(schema::lists::table.find(list_id))
+                    .set(schema::lists::dsl::archived.eq(true))
+                    .execute(connection)?;
+                Ok(StoreResponse::ArchivedList(list))
+            })
+        })
+        .await?
+    }
+
+    async fn check_list_item(
+        &self,
+        list: &ListName,
+        item: &Name,
+        checked: bool,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::list;
+
+        let store = self.clone();
+        let list_name = list.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_writable_list_id(connection, &list_name)?;
+                let Some(id) = Self::get_item_id(connection, item.as_str())? else {
+                    return Err(StoreError::ItemNotFound(item.to_string()));
+                };
+                let updated = diesel::update(
+                    list::table
+                        .filter(list::list_id.eq(list_id))
+                        .filter(list::id.eq(id)),
+                )
+                .set((
+                    list::checked.eq(checked),
+                    list::checked_at.eq(diesel::dsl::sql("CURRENT_TIMESTAMP")),
+                ))
+                .execute(connection)?;
+                if updated == 0 {
+                    return Err(StoreError::ListItemNotFound(item.to_string()));
+                }
+                Ok(if checked {
+                    StoreResponse::CheckedListItem(item)
+                } else {
+                    StoreResponse::UncheckedListItem(item)
+                })
+            })
+        })
+        .await?
+    }
+
+    async fn refresh_list(&self, list: &ListName) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_writable_list_id(connection, &list)?;
+                diesel::delete(schema::list::table.filter(schema::list::list_id.eq(list_id)))
+                    .execute(connection)?;
+                diesel::delete(
+                    schema::list_pantry::table.filter(schema::list_pantry::list_id.eq(list_id)),
+                )
+                .execute(connection)?;
+                Ok(StoreResponse::RefreshList)
+            })
+        })
+        .await?
+    }
+
+    async fn rename_list(
+        &self,
+        list: &ListName,
+        new_name: &ListName,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let list = list.clone();
+        let new_name = new_name.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let list_id = Self::existing_list_id(connection, &list)?;
+                if new_name != list {
+                    if Self::get_list_model(connection, &new_name)?.is_some() {
+                        return Err(StoreError::ListExists(new_name.to_string()));
+                    }
+                    diesel::update(schema::lists::table.find(list_id))
+                        .set(schema::lists::dsl::name.eq(new_name.as_str()))
+                        .execute(connection)?;
+                }
+                Ok(StoreResponse::RenamedList { list, new_name })
+            })
+        })
+        .await?
+    }
+
+    async fn rename_recipe(
+        &self,
+        recipe: &Recipe,
+        new_name: &Recipe,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        let new_name = new_name.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let recipe_id = Self::existing_recipe_id(connection, &recipe)?;
+                if new_name != recipe {
+                    if Self::get_recipe_id(connection, new_name.as_str())?.is_some() {
+                        return Err(StoreError::RecipeExists(new_name.to_string()));
+                    }
+                    diesel::update(schema::recipes::table.find(recipe_id))
+                        .set(schema::recipes::dsl::name.eq(new_name.as_str()))
+                        .execute(connection)?;
+                }
+                Self::updated_recipe(connection, recipe_id, new_name)
+            })
+        })
+        .await?
+    }
+
+    async fn merge_section(
+        &self,
+        section: &common::section::Section,
+        into: &common::section::Section,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::{items_sections, sections_aisles};
+
+        let store = self.clone();
+        let section = section.clone();
+        let into = into.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let section_id = Self::existing_section_id(connection, &section)?;
+                let into_id = Self::existing_section_id(connection, &into)?;
+                if section_id != into_id {
+                    // Items and store aisles already set for `into` win.
+                    for (item_id, _) in items_sections::table
+                        .filter(items_sections::section_id.eq(section_id))
+                        .load::<(i32, i32)>(connection)?
+                    {
+                        Self::insert_item_section(connection, item_id, into_id)?;
+                    }
+                    for (store_id, _, aisle_id) in sections_aisles::table
+                        .filter(sections_aisles::section_id.eq(section_id))
+                        .load::<(i32, i32, i32)>(connection)?
+                    {
+                        diesel::insert_into(sections_aisles::table)
+                            .values(NewSectionAisle {
+                                store_id,
+                                section_id: into_id,
+                                aisle_id,
+                            })
+                            .on_conflict_do_nothing()
+                            .execute(connection)?;
+                    }
+                    diesel::delete(
+                        items_sections::table.filter(items_sections::section_id.eq(section_id)),
+                    )
+                    .execute(connection)?;
+                    diesel::delete(schema::sections::table.find(section_id)).execute(connection)?;
+                }
+                Ok(StoreResponse::MergedSection { section, into })
+            })
+        })
+        .await?
+    }
+
+    async fn rename_section(
+        &self,
+        section: &common::section::Section,
+        new_name: &common::section::Section,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let section = section.clone();
+        let new_name = new_name.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let section_id = Self::existing_section_id(connection, &section)?;
+                if new_name != section {
+                    if Self::get_section_id(connection, new_name.as_str())?.is_some() {
+                        return Err(StoreError::SectionExists(new_name.to_string()));
+                    }
+                    diesel::update(schema::sections::table.find(section_id))
+                        .set(schema::sections::dsl::name.eq(new_name.as_str()))
+                        .execute(connection)?;
+                }
+                Ok(StoreResponse::RenamedSection { section, new_name })
+            })
+        })
+        .await?
+    }
+
+    async fn confirm_suggestions(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let suggestions = Self::load_suggestions(connection)?;
+                for (item, section) in &suggestions {
+                    let item_id = Self::existing_item_id(connection, item)?;
+                    let section_id = Self::existing_section_id(connection, section)?;
+                    Self::insert_item_section(connection, item_id, section_id)?;
+                }
+                Ok(StoreResponse::ReviewedSuggestions(suggestions))
+            })
+        })
+        .await?
+    }
+
+    async fn review_suggestion(
+        &self,
+        item: &Name,
+        section: Option<&common::section::Section>,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::{items_sections, section_suggestions, sections};
+
+        let store = self.clone();
+        let item = item.clone();
+        let section = section.cloned();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let item_id = Self::existing_item_id(connection, &item)?;
+                let Some(suggested_id) = section_suggestions::table
+                    .find(item_id)
+                    .select(section_suggestions::section_id)
+                    .first::<i32>(connection)
+                    .optional()?
+                else {
+                    return Err(StoreError::SuggestionNotFound(item.to_string()));
+                };
+                let (section_id, section) = match section {
+                    Some(section) => (Self::existing_section_id(connection, &section)?, section),
+                    None => (
+                        suggested_id,
+                        common::section::Section::from(
+                            sections::table
+                                .find(suggested_id)
+                                .select(sections::name)
+                                .first::<String>(connection)?,
+                        ),
+                    ),
+                };
+                diesel::delete(items_sections::table.filter(items_sections::item_id.eq(item_id)))
+                    .execute(connection)?;
+                Self::insert_item_section(connection, item_id, section_id)?;
+                Ok(StoreResponse::ReviewedSuggestions(vec![(item, section)]))
+            })
+        })
+        .await?
+    }
+
+    async fn order_sections(
+        &self,
+        order: &[common::section::Section],
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::sections;
+
+        let store = self.clone();
+        let order = order.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                diesel::update(sections::table)
+                    .set(sections::position.eq(None::<i32>))
+                    .execute(connection)?;
+                for (position, section) in (1..).zip(&order) {
+                    let id = Self::existing_section_id(connection, section)?;
+                    diesel::update(sections::table.find(id))
+                        .set(sections::position.eq(position))
+                        .execute(connection)?;
+                }
+                Ok(StoreResponse::Sections(order))
+            })
+        })
+        .await?
+    }
+
+    async fn add_store(
+        &self,
+        store: &StoreName,
+        aisles: &[Aisle],
+    ) -> Result<StoreResponse, StoreError> {
+        let db = self.clone();
+        let store = store.clone();
+        let aisles = aisles.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = db.connection()?;
+            connection.immediate_transaction(|connection| {
+                if Self::get_store_id(connection, &store)?.is_some() {
+                    return Err(StoreError::StoreExists(store.to_string()));
+                }
+                diesel::insert_into(schema::stores::table)
+                    .values(NewStore {
+                        name: store.as_str(),
+                    })
+                    .execute(connection)?;
+                let store_id = Self::existing_store_id(connection, &store)?;
+                Self::replace_aisles(connection, store_id, &aisles)?;
+                Ok(StoreResponse::AddedStore(store))
+            })
+        })
+        .await?
+    }
+
+    async fn store_layout(&self, store: &StoreName) -> Result<StoreResponse, StoreError> {
+        let db = self.clone();
+        let store = store.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = db.connection()?;
+            connection.immediate_transaction(|connection| {
+                let store_id = Self::existing_store_id(connection, &store)?;
+                Ok(StoreResponse::StoreLayout(Self::load_layout(
+                    connection, store_id, store,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn stores(&self) -> Result<StoreResponse, StoreError> {
+        let db = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = db.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::Stores(
+                    schema::stores::table
+                        .load::<StoreModel>(connection)?
+                        .into_iter()
+                        .map(|store| StoreName::from(store.name))
+                        .collect(),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn set_store_aisles(
+        &self,
+        store: &StoreName,
+        aisles: &[Aisle],
+    ) -> Result<StoreResponse, StoreError> {
+        let db = self.clone();
+        let store = store.clone();
+        let aisles = aisles.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = db.connection()?;
+            connection.immediate_transaction(|connection| {
+                let store_id = Self::existing_store_id(connection, &store)?;
+                Self::replace_aisles(connection, store_id, &aisles)?;
+                Ok(StoreResponse::StoreLayout(Self::load_layout(
+                    connection, store_id, store,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn set_section_aisle(
+        &self,
+        store: &StoreName,
+        section: &common::section::Section,
+        aisle: &Aisle,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::sections_aisles;
+
+        let db = self.clone();
+        let store = store.clone();
+        let section = section.clone();
+        let aisle = aisle.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = db.connection()?;
+            connection.immediate_transaction(|connection| {
+                let store_id = Self::existing_store_id(connection, &store)?;
+                let aisle_id = Self::existing_aisle_id(connection, store_id, &aisle)?;
+                let section_id = Self::existing_section_id(connection, &section)?;
+                diesel::delete(
+                    sections_aisles::table
+                        .filter(sections_aisles::store_id.eq(store_id))
+                        .filter(sections_aisles::section_id.eq(section_id)),
+                )
+                .execute(connection)?;
+                diesel::insert_into(sections_aisles::table)
+                    .values(NewSectionAisle {
+                        store_id,
+                        section_id,
+                        aisle_id,
+                    })
+                    .execute(connection)?;
+                Ok(StoreResponse::StoreLayout(Self::load_layout(
+                    connection, store_id, store,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn set_item_aisle(
+        &self,
+        store: &StoreName,
+        item: &Name,
+        aisle: &Aisle,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::items_aisles;
+
+        let db = self.clone();
+        let store = store.clone();
+        let item = item.clone();
+        let aisle = aisle.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = db.connection()?;
+            connection.immediate_transaction(|connection| {
+                let store_id = Self::existing_store_id(connection, &store)?;
+                let aisle_id = Self::existing_aisle_id(connection, store_id, &aisle)?;
+                let Some(item_id) = Self::get_item_id(connection, item.as_str())? else {
+                    return Err(StoreError::ItemNotFound(item.to_string()));
+                };
+                diesel::delete(
+                    items_aisles::table
+                        .filter(items_aisles::store_id.eq(store_id))
+                        .filter(items_aisles::item_id.eq(item_id)),
+                )
+                .execute(connection)?;
+                diesel::insert_into(items_aisles::table)
+                    .values(NewItemAisle {
+                        store_id,
+                        item_id,
+                        aisle_id,
+                    })
+                    .execute(connection)?;
+                Ok(StoreResponse::StoreLayout(Self::load_layout(
+                    connection, store_id, store,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn update_item(
+        &self,
+        item: &Name,
+        new_name: Option<Name>,
+        section: Option<common::section::Section>,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let Some(id) = Self::get_item_id(connection, item.as_str())? else {
+                    return Err(StoreError::ItemNotFound(item.to_string()));
+                };
+                if let Some(new_name) = new_name.filter(|new_name| *new_name != item) {
+                    match Self::get_item_id(connection, new_name.as_str())? {
+                        // Taking one of its own aliases as its name.
+                        Some(existing) if existing == id => {
+                            diesel::delete(schema::item_aliases::table.find(new_name.as_str()))
+                                .execute(connection)?;
+                        }
+                        Some(_) => return Err(StoreError::ItemExists(new_name.to_string())),
+                        None => {}
+                    }
+                    diesel::update(schema::items::table.find(id))
+                        .set((
+                            schema::items::dsl::name.eq(new_name.as_str()),
+                            schema::items::dsl::normalized.eq(new_name.normalized()),
+                        ))
+                        .execute(connection)?;
+                }
+                if let Some(section) = section {
+                    let section_id = Self::existing_section_id(connection, &section)?;
+                    diesel::delete(
+                        schema::items_sections::table
+                            .filter(schema::items_sections::dsl::item_id.eq(id)),
+                    )
+                    .execute(connection)?;
+                    Self::insert_item_section(connection, id, section_id)?;
+                }
+                let model = schema::items::table.find(id).first::<Item>(connection)?;
+                Ok(StoreResponse::UpdatedItem(Self::item_details(
+                    connection, model,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn merge_duplicates(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let mut merged = Vec::new();
+                for group in Self::find_duplicates(connection)? {
+                    let (into_id, into) = &group[0];
+                    for (item_id, item) in &group[1..] {
+                        Self::merge_item(connection, *item_id, *into_id)?;
+                        merged.push((item.clone(), into.clone()));
+                    }
+                }
+                Ok(StoreResponse::MergedDuplicates(merged))
+            })
+        })
+        .await?
+    }
+
+    async fn consume_pantry_item(
+        &self,
+        item: &Name,
+        measure: Option<&Measure>,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::pantry;
+
+        let store = self.clone();
+        let item = item.clone();
+        let measure = measure.cloned();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let not_found = || StoreError::PantryItemNotFound(item.to_string());
+                let item_id =
+                    Self::get_item_id(connection, item.as_str())?.ok_or_else(not_found)?;
+                let Some(stocked) = Self::load_pantry_item(connection, item_id)? else {
+                    return Err(not_found());
+                };
+                let name = stocked.name().clone();
+
+                let delete = pantry::table.filter(pantry::item_id.eq(item_id));
+                match measure {
+                    None => {
+                        diesel::delete(delete).execute(connection)?;
+                    }
+                    // An item kept without an amount is only used up when no
+                    // amount is given.
+                    Some(_) if stocked.measures().is_empty() => {}
+                    Some(measure) => {
+                        let mut stock = stocked.measures().iter().cloned().collect::<Vec<_>>();
+                        draw(&measure, &mut stock, Density::for_item(&name));
+                        let left = stock
+                            .into_iter()
+                            .filter(|measure| !used_up(measure))
+                            .collect::<Measures>();
+                        if left.is_empty() {
+                            diesel::delete(delete).execute(connection)?;
+                        } else {
+                            Self::replace_pantry_rows(
+                                connection,
+                                item_id,
+                                &left,
+                                stocked.best_before(),
+                            )?;
+                        }
+                    }
+                }
+                Ok(StoreResponse::ConsumedPantryItem {
+                    item: name,
+                    left: Self::load_pantry_item(connection, item_id)?,
+                })
+            })
+        })
+        .await?
+    }
+
+    async fn audit_pantry_item(
+        &self,
+        item: &Name,
+        measure: Option<&Measure>,
+        best_before: Option<Date>,
+    ) -> Result<StoreResponse, StoreError> {
+        use crate::schema::pantry;
+
+        let store = self.clone();
+        let item = item.clone();
+        let measure = measure.cloned();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let item_id = Self::get_or_insert_item(connection, item.as_str())?;
+                if measure.as_ref().is_some_and(used_up) {
+                    diesel::delete(pantry::table.filter(pantry::item_id.eq(item_id)))
+                        .execute(connection)?;
+                    return Ok(StoreResponse::DeletedPantryItem(Self::load_item_name(
+                        connection, item_id,
+                    )?));
+                }
+
+                // The best-before date is kept unless a new one is given.
+                let best_before = best_before.or(Self::load_pantry_item(connection, item_id)?
+                    .and_then(|stocked| stocked.best_before()));
+                let measures = measure.into_iter().collect::<Measures>();
+                Self::replace_pantry_rows(connection, item_id, &measures, best_before)?;
+                let audited = Self::load_pantry_item(connection, item_id)?
+                    .ok_or_else(|| StoreError::PantryItemNotFound(item.to_string()))?;
+                Ok(StoreResponse::AuditedPantryItem(audited))
+            })
+        })
+        .await?
+    }
+
+    async fn move_meal(&self, from: MealSlot, to: MealSlot) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let meal = Self::load_planned_meal(connection, from)?
+                    .ok_or_else(|| StoreError::MealNotPlanned(from.to_string()))?;
+                if from == to {
+                    return Ok(StoreResponse::MovedMeal { meal, from });
+                }
+                if let Some(taken) = Self::load_planned_meal(connection, to)? {
+                    return Err(StoreError::MealSlotTaken(taken.to_string()));
+                }
+                Self::delete_meal_row(connection, from)?;
+                let meal = Self::plan_meal(connection, to, meal.recipe())?;
+                Ok(StoreResponse::MovedMeal { meal, from })
+            })
+        })
+        .await?
+    }
+
+    async fn swap_meals(
+        &self,
+        slot: MealSlot,
+        other: MealSlot,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let first = Self::load_planned_meal(connection, slot)?;
+                let second = Self::load_planned_meal(connection, other)?;
+                if first.is_none() && second.is_none() {
+                    return Err(StoreError::MealNotPlanned(format!("{slot} or {other}")));
+                }
+                if slot == other {
+                    return Ok(StoreResponse::SwappedMeals(first.into_iter().collect()));
+                }
+                Self::delete_meal_row(connection, slot)?;
+                Self::delete_meal_row(connection, other)?;
+
+                let mut swapped = Vec::new();
+                if let Some(meal) = second {
+                    swapped.push(Self::plan_meal(connection, slot, meal.recipe())?);
+                }
+                if let Some(meal) = first {
+                    swapped.push(Self::plan_meal(connection, other, meal.recipe())?);
+                }
+                Ok(StoreResponse::SwappedMeals(swapped))
+            })
+        })
+        .await?
+    }
+
+    async fn replace_meal_plan(&self, plan: &MealPlan) -> Result<(), StoreError> {
+        use crate::schema::meal_plan;
+
+        let store = self.clone();
+        let plan = plan.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let range = plan.range();
+                diesel::delete(meal_plan::table.filter(
+                    meal_plan::date.between(range.start().to_string(), range.end().to_string()),
+                ))
+                .execute(connection)?;
+                for meal in plan.meals() {
+                    Self::plan_meal(connection, meal.slot(), meal.recipe())?;
+                }
+                Ok(())
+            })
+        })
+        .await?
+    }
+
+    async fn merge_items(&self, item: &Name, into: &Name) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let item = item.clone();
+        let into = into.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let item_id = Self::exact_item_id(connection, &item)?;
+                let into_id = Self::existing_item_id(connection, &into)?;
+                if item_id == into_id {
+                    return Ok(StoreResponse::MergedItems { item, into });
+                }
+                let into_name = Self::merge_item(connection, item_id, into_id)?;
+                Ok(StoreResponse::MergedItems {
+                    item,
+                    into: into_name,
+                })
+            })
+        })
+        .await?
+    }
+
+    async fn recipe_ingredients(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let Some(results) = Self::get_recipe_model_for_recipe(connection, recipe.as_str())?
+                else {
+                    return Ok(StoreResponse::RecipeIngredients(None));
+                };
+
+                let mut v = Vec::<Ingredients>::with_capacity(results.len());
+
+                for recipe in results {
+                    v.push(Self::load_recipe_ingredients(connection, recipe.id)?);
+                }
+
+                Ok(StoreResponse::RecipeIngredients(
+                    v.into_iter().take(1).next(),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn sections(&self) -> Result<StoreResponse, StoreError> {
+        use schema::sections::dsl::{id, position, sections};
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::Sections(
+                    sections
+                        .order_by((position.is_null(), position, id))
+                        .load::<Section>(connection)?
+                        .into_iter()
+                        .map(|sec| sec.name().into())
+                        .collect::<Vec<common::section::Section>>(),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn section_suggestions(&self) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::SectionSuggestions(Self::load_suggestions(
+                    connection,
+                )?))
+            })
+        })
+        .await?
+    }
+
+    async fn recipe_servings(&self, recipe: &Recipe) -> Result<Option<Servings>, StoreError> {
+        let store = self.clone();
+        let recipe = recipe.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(schema::recipes::table
+                    .filter(schema::recipes::dsl::name.eq(recipe.as_str()))
+                    .load::<models::RecipeModel>(connection)?
+                    .first()
+                    .and_then(RecipeModel::servings))
+            })
+        })
+        .await?
+    }
+
+    async fn recipes(&self) -> Result<StoreResponse, StoreError> {
+        use schema::recipes::dsl::recipes;
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                Ok(StoreResponse::Recipes(
+                    recipes
+                        .load::<models::RecipeModel>(connection)?
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
+                ))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_section(
+        &self,
+        section: &common::section::Section,
+    ) -> Result<StoreResponse, StoreError> {
+        let store = self.clone();
+        let section = section.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = store.connection()?;
+            connection.immediate_transaction(|connection| {
+                let section_id = Self::existing_section_id(connection, &section)?;
+                diesel::delete(
+                    schema::items_sections::table
+                        .filter(schema::items_sections::dsl::section_id.eq(section_id)),
+                )
+                .execute(connection)?;
+                diesel::delete(schema::sections::table.find(section_id)).execute(connection)?;
+                Ok(StoreResponse::DeletedSection(section))
+            })
+        })
+        .await?
+    }
+
+    async fn delete_store(&self, store: &StoreName) -> Result<StoreResponse, StoreError> {
+        let db = self.clone();
+        let store = store.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = db.connection()?;
+            connection.immediate_transaction(|connection| {
+                let store_id = Self::existing_store_id(connection, &store)?;
+                diesel::delete(schema::stores::table.find(store_id)).execute(connection)?;
+                Ok(StoreResponse::DeletedStore(store))
+            })
+        })
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{item::Name, recipes::Ingredients};
+
+    async fn inmem_sqlite_store() -> SqliteStore {
+        // Set up a connection to an in-memory SQLite database for testing
+        let store = SqliteStore::new(DbUri::inmem()).await.unwrap();
+        let migrations_store = store.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = migrations_store.connection().unwrap();
+            connection.immediate_transaction(run_migrations).unwrap();
+        })
+        .await
+        .unwrap();
+        store
+    }
+
+    fn test_item_name() -> Name {
+        Name::from("test item")
+    }
+
+    #[tokio::test]
+    async fn test_add_checklist_item() {
+        let store = inmem_sqlite_store().await;
+
+        let item_name = test_item_name();
+        store.add_checklist_item(&item_name).await.unwrap();
+
+        let StoreResponse::Checklist(list) = store.checklist().await.unwrap() else {
+            todo!()
+        };
+
+        assert!(list.iter().any(|item| item.name() == &item_name));
+    }
+
+    #[tokio::test]
+    async fn test_add_item() {
+        let store = inmem_sqlite_store().await;
+
+        let item_name = test_item_name();
+        store.add_item(&item_name, &None).await.unwrap();
+
+        let items = store.items().await.unwrap();
+
+        assert!(items
+            .collection_iter()
+            .any(|item| item.name() == &item_name));
+    }
+
+    #[tokio::test]
+    async fn test_add_list_item() {
+        let store = inmem_sqlite_store().await;
+
+        let item_name = test_item_name();
+        store
+            .add_list_item(&ListName::default(), &item_name)
+            .await
+            .unwrap();
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+
+        let item_in_list = list.items().iter().any(|item| item.name() == &item_name);
+
+        assert!(item_in_list);
+    }
+
+    #[tokio::test]
+    async fn test_add_list_recipe() {
+        let store = inmem_sqlite_store().await;
+
+        let ingredients =
+            Ingredients::from_iter(vec![Name::from("ingredient 1"), Name::from("ingredient 2")]);
+
+        let recipe = Recipe::new("test recipe");
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
+
+        store
+            .add_list_recipe(&ListName::default(), &recipe, None)
+            .await
+            .unwrap();
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(list, @r###"
+        List {
+            name: ListName(
+                "default",
+            ),
+            checklist: [],
+            recipes: [
+                Recipe(
+                    "test recipe",
+                ),
+            ],
+            items: [
+                ListItem {
+                    item: Item {
+                        name: Name(
+                            "ingredient 1",
+                        ),
+                        section: None,
+                        recipes: None,
+                    },
+                    measures: Measures(
+                        [],
+                    ),
+                    checked: false,
+                    checked_at: None,
+                },
+                ListItem {
+                    item: Item {
+                        name: Name(
+                            "ingredient 2",
+                        ),
+                        section: None,
+                        recipes: None,
+                    },
+                    measures: Measures(
+                        [],
+                    ),
+                    checked: false,
+                    checked_at: None,
+                },
+            ],
+            section_order: [],
+            layout: None,
+            plan: None,
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn test_add_list_recipes_sums_quantities() {
+        let store = inmem_sqlite_store().await;
+
+        let pancakes = Recipe::new("pancakes");
+        let ingredients = Ingredients::from_input_string("1 tbsp butter, 2 eggs, 100g flour");
+        store
+            .add_recipe(&pancakes, &ingredients, None)
+            .await
+            .unwrap();
+
+        let cake = Recipe::new("cake");
+        let ingredients = Ingredients::from_input_string("2 tbsp butter, 3 eggs, 2 tbsp parmesan");
+        store.add_recipe(&cake, &ingredients, None).await.unwrap();
+
+        let shortbread = Recipe::new("shortbread");
+        let ingredients = Ingredients::from_input_string("1 tsp butter, 50g parmesan");
+        store
+            .add_recipe(&shortbread, &ingredients, None)
+            .await
+            .unwrap();
+
+        store
+            .add_list_item(&ListName::default(), &Name::from("flour"))
+            .await
+            .unwrap();
+        store
+            .add_list_recipe(&ListName::default(), &pancakes, None)
+            .await
+            .unwrap();
+        store
+            .add_list_recipe(&ListName::default(), &cake, None)
+            .await
+            .unwrap();
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+
+        insta::assert_debug_snapshot!(
+            list.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "butter (3 tbsp)",
+            "eggs (5)",
+            "flour (100 g)",
+            "parmesan (2 tbsp)",
+        ]
+        "###
+        );
+
+        store
+            .add_list_recipe(&ListName::default(), &shortbread, None)
+            .await
+            .unwrap();
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+
+        insta::assert_debug_snapshot!(
+            list.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "butter (3.33 tbsp)",
+            "eggs (5)",
+            "flour (100 g)",
+            "parmesan (2 tbsp, 50 g)",
+        ]
+        "###
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_list_recipe_for_servings() {
+        let store = inmem_sqlite_store().await;
+
+        let pancakes = Recipe::new("pancakes");
+        let ingredients = Ingredients::from_input_string("100g flour, 1-2 tbsp sugar, salt");
+        store
+            .add_recipe(&pancakes, &ingredients, Some(Servings::new(2)))
+            .await
+            .unwrap();
+
+        let StoreResponse::RecipeIngredients(Some(scaled)) = store
+            .scaled_recipe_ingredients(&pancakes, Some(Servings::new(3)))
+            .await
+            .unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(
+            scaled.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["150 g flour", "1.5-3 tbsp sugar", "salt"]
+        );
+
+        store
+            .add_list_recipe(&ListName::default(), &pancakes, Some(Servings::new(4)))
+            .await
+            .unwrap();
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(
+            list.items()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["flour (200 g)", "sugar (2-4 tbsp)", "salt"]
+        );
+
+        let cake = Recipe::new("cake");
+        store.add_recipe(&cake, &ingredients, None).await.unwrap();
+        assert!(matches!(
+            store
+                .add_list_recipe(&ListName::default(), &cake, Some(Servings::new(4)))
+                .await,
+            Err(StoreError::RecipeServings(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_add_recipe() {
+        let store = inmem_sqlite_store().await;
+
+        let ingredients =
+            Ingredients::from_iter(vec![Name::from("ingredient 1"), Name::from("ingredient 2")]);
+
+        let recipe = Recipe::new("test recipe");
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
+
+        let StoreResponse::Recipes(recipes) = store.recipes().await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(recipes.len(), 1);
+
+        let added_recipe = &recipes[0];
+        assert_eq!(added_recipe.as_str(), "test recipe");
+
+        let StoreResponse::RecipeIngredients(Some(recipe_ingredients)) =
+            store.recipe_ingredients(&recipe).await.unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(recipe_ingredients, ingredients);
+    }
+
+    #[tokio::test]
+    async fn test_edit_recipe() {
+        let store = inmem_sqlite_store().await;
+
+        let recipe = Recipe::new("pancakes");
+        let ingredients = Ingredients::from_input_string("2 eggs, 100g flour, 1 tbsp sugar");
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
+
+        store
+            .add_recipe_ingredient(&recipe, &Ingredient::parse("300ml milk"))
+            .await
+            .unwrap();
+        store
+            .delete_recipe_ingredient(&recipe, &Name::from("sugar"))
+            .await
+            .unwrap();
+        store
+            .edit_recipe_ingredient(
+                &recipe,
+                &Name::from("eggs"),
+                &Ingredient::parse("3 large eggs"),
+            )
+            .await
+            .unwrap();
+
+        let StoreResponse::UpdatedRecipe(recipe, ingredients) = store
+            .rename_recipe(&recipe, &Recipe::new("crêpes"))
+            .await
+            .unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(recipe.as_str(), "crêpes");
+        insta::assert_snapshot!(
+            ingredients.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"),
+            @r###"
+        100 g flour
+        300 ml milk
+        3 eggs, large
+        "###
+        );
+
+        assert!(matches!(
+            store
+                .delete_recipe_ingredient(&recipe, &Name::from("sugar"))
+                .await,
+            Err(StoreError::IngredientNotFound(_))
+        ));
+        assert!(matches!(
+            store.rename_recipe(&Recipe::new("pancakes"), &recipe).await,
+            Err(StoreError::RecipeNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_list_items() {
+        let store = inmem_sqlite_store().await;
+        let list = ListName::default();
+
+        for item in ["flour", "eggs", "milk"] {
+            store.add_list_item(&list, &Name::from(item)).await.unwrap();
+        }
+        store
+            .check_list_item(&list, &Name::from("flour"), true)
+            .await
+            .unwrap();
+        store
+            .check_list_item(&list, &Name::from("milk"), true)
+            .await
+            .unwrap();
+        store
+            .check_list_item(&list, &Name::from("milk"), false)
+            .await
+            .unwrap();
+        assert!(matches!(
+            store
+                .check_list_item(&list, &Name::from("sugar"), true)
+                .await,
+            Err(StoreError::ItemNotFound(_))
+        ));
+
+        let StoreResponse::List(shopping) = store.list(&list).await.unwrap() else {
+            todo!()
+        };
+        assert!(shopping
+            .items()
+            .iter()
+            .all(|item| item.checked_at().is_some() == (item.name().as_str() != "eggs")));
+        insta::assert_debug_snapshot!(
+            shopping.remaining().items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "eggs",
+            "milk",
+        ]
+        "###
+        );
+
+        let StoreResponse::ClearedCheckedListItems(cleared) =
+            store.clear_checked_list_items(&list).await.unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(cleared, [Name::from("flour")]);
+
+        let StoreResponse::List(shopping) = store.list(&list).await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            shopping.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "eggs",
+            "milk",
+        ]
+        "###
+        );
+        assert!(matches!(
+            store
+                .check_list_item(&list, &Name::from("flour"), true)
+                .await,
+            Err(StoreError::ListItemNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_clear_checklist_and_list() {
+        let store = inmem_sqlite_store().await;
+
+        let recipe = Recipe::new("test recipe");
+        let ingredients = Ingredients::from_input_string("2 eggs, 100g flour");
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
+        store
+            .add_list_recipe(&ListName::default(), &recipe, None)
+            .await
+            .unwrap();
+        store.add_checklist_item(&test_item_name()).await.unwrap();
+
+        store.clear_list(&ListName::default()).await.unwrap();
+        store.clear_checklist().await.unwrap();
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+        assert!(list.items().is_empty());
+        assert!(matches!(
+            store.list_recipes(&ListName::default()).await.unwrap(),
+            StoreResponse::Recipes(recipes) if recipes.is_empty()
+        ));
+
+        let StoreResponse::Checklist(checklist) = store.checklist().await.unwrap() else {
+            todo!()
+        };
+        assert!(checklist.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_checklist_item() {
+        let store = inmem_sqlite_store().await;
+
+        let item_name = test_item_name();
+        store.add_checklist_item(&item_name).await.unwrap();
+
+        let StoreResponse::Checklist(checklist) = store.checklist().await.unwrap() else {
+            todo!()
+        };
+
+        assert!(checklist.iter().any(|item| item.name() == &item_name));
+
+        store.delete_checklist_item(&item_name).await.unwrap();
+
+        let StoreResponse::Checklist(checklist) = store.checklist().await.unwrap() else {
+            todo!()
+        };
+
+        assert!(checklist.iter().all(|item| item.name() != &item_name));
+    }
+
+    #[tokio::test]
+    async fn test_delete_item() {
+        let store = inmem_sqlite_store().await;
+
+        let recipe = Recipe::new("test recipe");
+        let ingredients = Ingredients::from_input_string("2 eggs, 100g flour");
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
+        store
+            .add_list_recipe(&ListName::default(), &recipe, None)
+            .await
+            .unwrap();
+
+        let eggs = Name::from("eggs");
+        store.add_checklist_item(&eggs).await.unwrap();
+        store.delete_item(&eggs).await.unwrap();
+
+        let items = store.items().await.unwrap();
+        assert!(items.collection_iter().all(|item| item.name() != &eggs));
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+        assert!(list.items().iter().all(|item| item.name() != &eggs));
+
+        let StoreResponse::RecipeIngredients(Some(ingredients)) =
+            store.recipe_ingredients(&recipe).await.unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(
+            ingredients.names().map(Name::as_str).collect::<Vec<_>>(),
+            ["flour"]
+        );
+
+        assert!(matches!(
+            store.delete_item(&eggs).await,
+            Err(StoreError::ItemNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_list_item() {
+        let store = inmem_sqlite_store().await;
+
+        let item_name = test_item_name();
+        store
+            .add_list_item(&ListName::default(), &item_name)
+            .await
+            .unwrap();
+        store
+            .delete_list_item(&ListName::default(), &item_name)
+            .await
+            .unwrap();
+
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+        assert!(list.items().is_empty());
+        assert!(matches!(
+            store
+                .delete_list_item(&ListName::default(), &item_name)
+                .await,
+            Err(StoreError::ListItemNotFound(_))
+        ));
+
+        let items = store.items().await.unwrap();
+        assert!(items
+            .collection_iter()
+            .any(|item| item.name() == &item_name));
+    }
+
+    #[tokio::test]
+    async fn test_delete_recipe() {
+        let store = inmem_sqlite_store().await;
+
+        let ingredients =
+            Ingredients::from_iter(vec![Name::from("ingredient 1"), Name::from("ingredient 2")]);
+
+        let recipe = Recipe::new("test recipe");
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
+
+        let StoreResponse::Recipes(recipes) = store.recipes().await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(recipes.len(), 1);
+
+        let added_recipe = &recipes[0];
+        assert_eq!(added_recipe.as_str(), "test recipe");
+
+        let StoreResponse::RecipeIngredients(Some(recipe_ingredients)) =
+            store.recipe_ingredients(&recipe).await.unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(recipe_ingredients, ingredients);
+
+        store.delete_recipe(&recipe).await.unwrap();
+
+        let StoreResponse::Recipes(recipes) = store.recipes().await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(recipes.len(), 0);
+
+        let StoreResponse::RecipeIngredients(recipe_ingredients) =
+            store.recipe_ingredients(&recipe).await.unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(recipe_ingredients, None);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_ingredients() {
+        let store = inmem_sqlite_store().await;
+
+        let curry = Recipe::new("curry");
+        let ingredients = Ingredients::from_input_string(
+            "2 cloves garlic, 1 onion, 1 tsp garlic, 1 clove garlic, 1 onion",
+        );
+        store.add_recipe(&curry, &ingredients, None).await.unwrap();
+
+        let StoreResponse::RecipeIngredients(Some(ingredients)) =
+            store.recipe_ingredients(&curry).await.unwrap()
+        else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            ingredients.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "3 clove garlic",
+            "2 onion",
+            "1 tsp garlic",
+        ]
+        "###
+        );
+
+        let list = ListName::default();
+        store.add_list_recipe(&list, &curry, None).await.unwrap();
+        let StoreResponse::List(list) = store.list(&list).await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            list.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "garlic (3 clove, 1 tsp)",
+            "onion (2)",
+        ]
+        "###
+        );
+
+        let StoreResponse::DeletedRecipe { removed, .. } =
+            store.delete_recipe(&curry).await.unwrap()
+        else {
+            todo!()
+        };
+        assert!(removed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_recipe_keeps_shared_items() {
+        use common::{
+            date::parse_date,
+            plan::{DateRange, Meal, MealSlot},
+        };
+
+        let store = inmem_sqlite_store().await;
+
+        let pancakes = Recipe::new("pancakes");
+        let ingredients = Ingredients::from_input_string(
+            "2 eggs, 100g flour, 300ml milk, salt, 1 tsp baking powder",
+        );
+        store
+            .add_recipe(&pancakes, &ingredients, None)
+            .await
+            .unwrap();
+        store
+            .add_list_recipe(&ListName::default(), &pancakes, None)
+            .await
+            .unwrap();
+
+        let omelette = Recipe::new("omelette");
+        let ingredients = Ingredients::from_input_string("3 eggs, 1 tbsp butter");
+        store
+            .add_recipe(&omelette, &ingredients, None)
+            .await
+            .unwrap();
+
+        store.refresh_list(&ListName::default()).await.unwrap();
+        store
+            .add_list_item(&ListName::default(), &Name::from("flour"))
+            .await
+            .unwrap();
+        store.add_checklist_item(&Name::from("milk")).await.unwrap();
+        store
+            .add_pantry_item(&Name::from("baking powder"), None, None)
+            .await
+            .unwrap();
+        // A section alone doesn't keep an item.
+        store
+            .update_item(
+                &Name::from("salt"),
+                None,
+                Some(common::section::Section::from("pantry")),
+            )
+            .await
+            .unwrap();
+        let slot = |date: &str, meal| MealSlot::new(parse_date(date).unwrap(), meal);
+        for (date, meal) in [
+            ("2026-10-25", Meal::Breakfast),
+            ("2026-10-20", Meal::Dinner),
+        ] {
+            store
+                .add_planned_meal(slot(date, meal), &pancakes)
+                .await
+                .unwrap();
+        }
+
+        let StoreResponse::DeletedRecipe {
+            kept,
+            removed,
+            unplanned,
+            ..
+        } = store.delete_recipe(&pancakes).await.unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(
+            unplanned,
+            [
+                slot("2026-10-20", Meal::Dinner),
+                slot("2026-10-25", Meal::Breakfast)
+            ]
+        );
+        let StoreResponse::MealPlan(plan) = store
+            .meal_plan(DateRange::week_of(parse_date("2026-10-19").unwrap()))
+            .await
+            .unwrap()
+        else {
+            todo!()
+        };
+        assert!(plan.meals().is_empty());
+        assert_eq!(
+            kept.iter().map(Name::as_str).collect::<Vec<_>>(),
+            ["eggs", "flour", "milk", "baking powder"]
+        );
+        let StoreResponse::Pantry(pantry) = store.pantry().await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(pantry.len(), 1);
+        assert_eq!(
+            removed.iter().map(Name::as_str).collect::<Vec<_>>(),
+            ["salt"]
+        );
+        assert!(matches!(
+            store.list_recipes(&ListName::default()).await.unwrap(),
+            StoreResponse::Recipes(recipes) if recipes.is_empty()
+        ));
+
+        let StoreResponse::RecipeIngredients(Some(ingredients)) =
+            store.recipe_ingredients(&omelette).await.unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(
+            ingredients.names().map(Name::as_str).collect::<Vec<_>>(),
+            ["eggs", "butter"]
+        );
+
+        assert!(matches!(
+            store.delete_recipe(&pancakes).await,
+            Err(StoreError::RecipeNotFound(_))
+        ));
+
+        // Items still in use can't be deleted out from under the list.
+        let mut connection = store.connection().unwrap();
+        assert!(
+            diesel::delete(schema::items::table.filter(schema::items::dsl::name.eq("flour")))
+                .execute(&mut connection)
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_list() {
+        let store = inmem_sqlite_store().await;
+
+        let weekly = ListName::from("weekly shop");
+        let party = ListName::from("party");
+        let flour = Name::from("flour");
+        let eggs = Name::from("eggs");
+
+        store.add_list(&weekly).await.unwrap();
+        store.add_list_item(&weekly, &flour).await.unwrap();
+        store.add_list_item(&weekly, &eggs).await.unwrap();
+        store.check_list_item(&weekly, &flour, true).await.unwrap();
+
+        store.copy_list(&weekly, &party).await.unwrap();
+
+        let StoreResponse::List(original) = store.list(&weekly).await.unwrap() else {
+            todo!()
+        };
+        let StoreResponse::List(copy) = store.list(&party).await.unwrap() else {
+            todo!()
+        };
+        // A copy keeps which items were checked off, and when.
+        assert_eq!(copy.items(), original.items());
+        assert!(copy
+            .items()
+            .iter()
+            .all(|item| item.is_checked() == (item.name() == &flour)));
+    }
+
+    #[tokio::test]
+    async fn test_named_lists() {
+        let store = inmem_sqlite_store().await;
+
+        let weekly = ListName::from("Weekly Shop");
+        let party = ListName::from("party");
+        let flour = Name::from("flour");
+
+        store.add_list(&weekly).await.unwrap();
+        assert!(matches!(
+            store.add_list(&weekly).await,
+            Err(StoreError::ListExists(_))
+        ));
+        store.add_list_item(&weekly, &flour).await.unwrap();
+
+        // Lists are kept apart
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+        assert!(list.items().is_empty());
+
+        store.copy_list(&weekly, &party).await.unwrap();
+        store
+            .add_list_item(&party, &Name::from("balloons"))
+            .await
+            .unwrap();
+        store.archive_list(&weekly).await.unwrap();
+        assert!(matches!(
+            store.add_list_item(&weekly, &Name::from("eggs")).await,
+            Err(StoreError::ListArchived(_))
+        ));
+
+        let StoreResponse::List(list) = store.list(&weekly).await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(list.name().as_str(), "weekly shop");
+        insta::assert_debug_snapshot!(
+            list.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "flour",
+        ]
+        "###
+        );
+
+        let celebration = ListName::from("celebration");
+        store.rename_list(&party, &celebration).await.unwrap();
+        assert!(matches!(
+            store.list(&party).await,
+            Err(StoreError::ListNotFound(_))
+        ));
+
+        let StoreResponse::List(list) = store.list(&celebration).await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            list.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "flour",
+            "balloons",
+        ]
+        "###
+        );
+
+        store.delete_list(&celebration).await.unwrap();
+
+        // Changing a list that doesn't exist doesn't create it.
+        let typo = ListName::from("grocieries");
+        for result in [
+            store.clear_list(&typo).await,
+            store.clear_checked_list_items(&typo).await,
+            store.delete_list_item(&typo, &flour).await,
+            store.check_list_item(&typo, &flour, true).await,
+            store.refresh_list(&typo).await,
+        ] {
+            assert!(matches!(result, Err(StoreError::ListNotFound(_))));
+        }
+
+        let StoreResponse::Lists { active, archived } = store.lists().await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(active, [ListName::default()]);
+        assert_eq!(archived, [weekly]);
+    }
+
+    #[tokio::test]
+    async fn test_order_sections() {
+        use common::section::Section;
+
+        let store = inmem_sqlite_store().await;
+        let list = ListName::default();
+
+        for (item, section) in [("apples", "fresh"), ("milk", "dairy"), ("rice", "pantry")] {
+            let item = Name::from(item);
+            store
+                .add_item(&item, &Some(Section::from(section)))
+                .await
+                .unwrap();
+            store.add_list_item(&list, &item).await.unwrap();
+        }
+        store
+            .add_list_item(&list, &Name::from("batteries"))
+            .await
+            .unwrap();
+
+        store
+            .order_sections(&[Section::from("pantry"), Section::from("dairy")])
+            .await
+            .unwrap();
+
+        let StoreResponse::Sections(sections) = store.sections().await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            sections.iter().map(Section::as_str).collect::<Vec<_>>(),
+            @r###"
+        [
+            "pantry",
+            "dairy",
+            "fresh",
+            "protein",
+            "freezer",
+        ]
+        "###
+        );
+
+        let StoreResponse::List(list) = store.list(&list).await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            list.by_section()
+                .into_iter()
+                .map(|(section, items)| (
+                    section.map(Section::as_str),
+                    items.len()
+                ))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            (
+                Some(
+                    "pantry",
+                ),
+                1,
+            ),
+            (
+                Some(
+                    "dairy",
+                ),
+                1,
+            ),
+            (
+                Some(
+                    "fresh",
+                ),
+                1,
+            ),
+            (
+                None,
+                1,
+            ),
+        ]
+        "###
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manage_sections() {
+        use common::{commands::Add, section::Section};
+
+        let store = inmem_sqlite_store().await;
+        let milk = Name::from("milk");
+
+        let err = store
+            .add(Add::item_from_name_and_section(
+                milk.clone(),
+                Some(Section::from("diary")),
+            ))
+            .await
+            .unwrap_err();
+        insta::assert_snapshot!(err.to_string(), @"section not found: diary, did you mean 'dairy'?");
+
+        // Nor are sections made up by ordering, laying out or moving items
+        // into them.
+        store.add_item(&milk, &None).await.unwrap();
+        let err = store
+            .order_sections(&[Section::from("fresh"), Section::from("diary")])
+            .await
+            .unwrap_err();
+        insta::assert_snapshot!(err.to_string(), @"section not found: diary, did you mean 'dairy'?");
+        let err = store
+            .update_item(&milk, None, Some(Section::from("diary")))
+            .await
+            .unwrap_err();
+        insta::assert_snapshot!(err.to_string(), @"section not found: diary, did you mean 'dairy'?");
+        let shop = StoreName::from("corner shop");
+        store
+            .add_store(&shop, &[Aisle::from("fridges")])
+            .await
+            .unwrap();
+        let err = store
+            .set_section_aisle(&shop, &Section::from("diary"), &Aisle::from("fridges"))
+            .await
+            .unwrap_err();
+        insta::assert_snapshot!(err.to_string(), @"section not found: diary, did you mean 'dairy'?");
+        store.delete_store(&shop).await.unwrap();
+
+        store
+            .add(Add::item_with_new_section(
+                milk.clone(),
+                Section::from("chilled"),
+            ))
+            .await
+            .unwrap();
+        store
+            .add(Add::item_from_name_and_section(
+                Name::from("eggs"),
+                Some(Section::from("dairy")),
+            ))
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.add_section(&Section::from("dairy")).await,
+            Err(StoreError::SectionExists(_))
+        ));
+
+        store
+            .rename_section(&Section::from("chilled"), &Section::from("fridge"))
+            .await
+            .unwrap();
+        store
+            .merge_section(&Section::from("dairy"), &Section::from("fridge"))
+            .await
+            .unwrap();
+        store
+            .delete_section(&Section::from("freezer"))
+            .await
+            .unwrap();
+
+        let StoreResponse::Sections(sections) = store.sections().await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            sections.iter().map(Section::as_str).collect::<Vec<_>>(),
+            @r###"
+        [
+            "fresh",
+            "pantry",
+            "protein",
+            "fridge",
+        ]
+        "###
+        );
+
+        let items = store.items().await.unwrap();
+        insta::assert_debug_snapshot!(
+            items
+                .collection_iter()
+                .map(|item| (item.name().as_str(), item.section().map(Section::as_str)))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            (
+                "milk",
+                Some(
+                    "fridge",
+                ),
+            ),
+            (
+                "eggs",
+                Some(
+                    "fridge",
+                ),
+            ),
+        ]
+        "###
+        );
+
+        store
+            .delete_section(&Section::from("fridge"))
+            .await
+            .unwrap();
+        let items = store.items().await.unwrap();
+        assert!(items.collection_iter().all(|item| item.section().is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_section_suggestions() {
+        use common::section::Section;
+
+        let store = inmem_sqlite_store().await;
+        store
+            .add_item(&Name::from("red onion"), &Some(Section::from("pantry")))
+            .await
+            .unwrap();
+
+        let ingredients = Ingredients::from_input_string(
+            "2 chicken thighs, 1 white onion, 500ml chicken stock, 1 kitchen roll, red onion",
+        );
+        store
+            .add_recipe(&Recipe::new("soup"), &ingredients, None)
+            .await
+            .unwrap();
+
+        let suggestions = |store: SqliteStore| async move {
+            let StoreResponse::SectionSuggestions(suggestions) =
+                store.section_suggestions().await.unwrap()
+            else {
+                todo!()
+            };
+            suggestions
+                .iter()
+                .map(|(item, section)| format!("{item}: {section}"))
+                .collect::<Vec<_>>()
+        };
+        insta::assert_debug_snapshot!(suggestions(store.clone()).await, @r###"
+        [
+            "chicken stock: pantry",
+            "chicken thighs: protein",
+            "white onion: pantry",
+        ]
+        "###);
+
+        assert!(matches!(
+            store
+                .review_suggestion(&Name::from("red onion"), None)
+                .await,
+            Err(StoreError::SuggestionNotFound(_))
+        ));
+        store
+            .review_suggestion(
+                &Name::from("chicken stock"),
+                Some(&Section::from("protein")),
+            )
+            .await
+            .unwrap();
+        store.confirm_suggestions().await.unwrap();
+        assert!(suggestions(store.clone()).await.is_empty());
+
+        let items = store.items().await.unwrap();
+        insta::assert_debug_snapshot!(
+            items
+                .collection_iter()
+                .map(|item| format!(
+                    "{}: {}",
+                    item.name(),
+                    item.section().map_or("none", Section::as_str)
+                ))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            "red onion: pantry",
+            "chicken thighs: protein",
+            "white onion: pantry",
+            "chicken stock: protein",
+            "kitchen roll: none",
+        ]
+        "###
+        );
+    }
+
+    #[tokio::test]
+    async fn test_item_aliases() {
+        use common::section::Section;
+
+        let store = inmem_sqlite_store().await;
+        let list = ListName::default();
+        let spring_onions = Name::from("spring onions");
+        store
+            .add_item(&spring_onions, &Some(Section::from("fresh")))
+            .await
+            .unwrap();
+        store
+            .add_item_alias(&spring_onions, &Name::from("scallions"))
+            .await
+            .unwrap();
+
+        store
+            .add_recipe(
+                &Recipe::new("stir fry"),
+                &Ingredients::from_input_string("4 scallions, 1 tbsp soy sauce"),
+                None,
+            )
+            .await
+            .unwrap();
+        store
+            .add_recipe(
+                &Recipe::new("salad"),
+                &Ingredients::from_input_string("2 green onions, 1 lettuce"),
+                None,
+            )
+            .await
+            .unwrap();
+        store
+            .add_list_item(&list, &Name::from("green onions"))
+            .await
+            .unwrap();
+        store
+            .add_checklist_item(&Name::from("scallions"))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store
+                .add_item_alias(&spring_onions, &Name::from("green onions"))
+                .await,
+            Err(StoreError::AliasExists(_))
+        ));
+        store
+            .merge_items(&Name::from("green onions"), &Name::from("scallions"))
+            .await
+            .unwrap();
+
+        let StoreResponse::ItemAliases(aliases) = store.item_aliases().await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            aliases
+                .iter()
+                .map(|(alias, item)| format!("{alias} -> {item}"))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            "green onions -> spring onions",
+            "scallions -> spring onions",
+        ]
+        "###
+        );
+
+        let StoreResponse::Item(item) = store.item(&Name::from("green onions")).await.unwrap()
+        else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(item, @r###"
+        Item {
+            name: Name(
+                "spring onions",
+            ),
+            section: Some(
+                Section(
+                    "fresh",
+                ),
+            ),
+            recipes: Some(
+                [
+                    Recipe(
+                        "stir fry",
+                    ),
+                    Recipe(
+                        "salad",
+                    ),
+                ],
+            ),
+        }
+        "###);
+
+        let StoreResponse::List(list) = store.list(&list).await.unwrap() else {
+            todo!()
+        };
+        let StoreResponse::Checklist(checklist) = store.checklist().await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(list.items().len(), 1);
+        assert_eq!(list.items()[0].name(), &spring_onions);
+        assert_eq!(checklist.len(), 1);
+        assert_eq!(checklist[0].name(), &spring_onions);
 
-    async fn inmem_sqlite_store() -> SqliteStore {
-        // Set up a connection to an in-memory SQLite database for testing
-        let store = SqliteStore::new(DbUri::inmem()).await.unwrap();
-        let migrations_store = store.clone();
-        tokio::task::spawn_blocking(move || {
-            let mut connection = migrations_store.connection().unwrap();
-            connection.immediate_transaction(run_migrations).unwrap();
-        })
-        .await
-        .unwrap();
+        // Deleting by an alias or spelling variant leaves the item alone.
+        for name in ["scallions", "spring onion"] {
+            assert!(store.delete_item(&Name::from(name)).await.is_err());
+        }
+        assert!(matches!(
+            store.delete_item(&Name::from("scallions")).await,
+            Err(StoreError::ItemIsAlias { .. })
+        ));
+        assert!(store.item(&spring_onions).await.is_ok());
+
+        // Taking it off a list goes through the alias, as adding it does.
         store
-    }
+            .delete_list_item(&ListName::default(), &Name::from("scallions"))
+            .await
+            .unwrap();
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
+            todo!()
+        };
+        assert!(list.items().is_empty());
+        store
+            .add_list_item(&ListName::default(), &spring_onions)
+            .await
+            .unwrap();
 
-    fn test_item_name() -> Name {
-        Name::from("test item")
+        store
+            .delete_item_alias(&Name::from("scallions"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.delete_item_alias(&Name::from("scallions")).await,
+            Err(StoreError::AliasNotFound(_))
+        ));
     }
 
     #[tokio::test]
-    async fn test_add_checklist_item() {
+    async fn test_duplicate_items() {
+        use common::section::Section;
+
         let store = inmem_sqlite_store().await;
+        store
+            .add_item(&Name::from("egg"), &Some(Section::from("dairy")))
+            .await
+            .unwrap();
+        store.add_item(&Name::from("tomato"), &None).await.unwrap();
 
-        let item_name = test_item_name();
-        store.add_checklist_item(&item_name).await.unwrap();
+        // Variants are matched to the existing item when added.
+        store.add_item(&Name::from("Eggs"), &None).await.unwrap();
 
-        let StoreResponse::Checklist(list) = store.checklist().await.unwrap() else {
+        // Duplicates from before names were normalized.
+        let mut connection = store.connection().unwrap();
+        for name in ["eggs", "the tomatoes", "crème fraîche", "creme fraiche"] {
+            diesel::insert_into(schema::items::table)
+                .values(NewItem {
+                    name,
+                    normalized: normalize(name),
+                })
+                .execute(&mut connection)
+                .unwrap();
+        }
+        drop(connection);
+
+        let StoreResponse::DuplicateItems(duplicates) = store.duplicate_items().await.unwrap()
+        else {
             todo!()
         };
+        insta::assert_debug_snapshot!(
+            duplicates
+                .iter()
+                .map(|group| group.iter().map(Name::as_str).collect::<Vec<_>>().join(", "))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            "egg, eggs",
+            "tomato, the tomatoes",
+            "crème fraîche, creme fraiche",
+        ]
+        "###
+        );
 
-        assert!(list.iter().any(|item| item.name() == &item_name));
+        let StoreResponse::MergedDuplicates(merged) = store.merge_duplicates().await.unwrap()
+        else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(
+            merged
+                .iter()
+                .map(|(item, into)| format!("{item} -> {into}"))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            "eggs -> egg",
+            "the tomatoes -> tomato",
+            "creme fraiche -> crème fraîche",
+        ]
+        "###
+        );
+
+        let StoreResponse::DuplicateItems(duplicates) = store.duplicate_items().await.unwrap()
+        else {
+            todo!()
+        };
+        assert!(duplicates.is_empty());
+        let StoreResponse::Item(item) = store.item(&Name::from("eggs")).await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(item.name(), &Name::from("egg"));
     }
 
     #[tokio::test]
-    async fn test_add_item() {
+    async fn test_renormalize_names() {
         let store = inmem_sqlite_store().await;
 
-        let item_name = test_item_name();
-        store.add_item(&item_name, &None).await.unwrap();
+        // Rows from before the normalized column was filled in.
+        let mut connection = store.connection().unwrap();
+        diesel::insert_into(schema::items::table)
+            .values(NewItem {
+                name: "spring onions",
+                normalized: String::new(),
+            })
+            .execute(&mut connection)
+            .unwrap();
+        let item_id = schema::items::table
+            .select(schema::items::id)
+            .first::<i32>(&mut connection)
+            .unwrap();
+        diesel::insert_into(schema::item_aliases::table)
+            .values(NewItemAlias {
+                alias: "scallions",
+                item_id,
+                normalized: String::new(),
+            })
+            .execute(&mut connection)
+            .unwrap();
+        drop(connection);
+        assert!(matches!(
+            store.item(&Name::from("spring onion")).await,
+            Err(StoreError::ItemNotFound(_))
+        ));
 
-        let items = store.items().await.unwrap();
+        store.run_migrations().unwrap();
 
-        assert!(items
-            .collection_iter()
-            .any(|item| item.name() == &item_name));
+        for variant in ["spring onion", "Scallion"] {
+            let StoreResponse::Item(item) = store.item(&Name::from(variant)).await.unwrap() else {
+                todo!()
+            };
+            assert_eq!(item.name(), &Name::from("spring onions"));
+        }
     }
 
     #[tokio::test]
-    async fn test_add_list_item() {
-        let store = inmem_sqlite_store().await;
+    async fn test_search() {
+        use common::commands::Read;
 
-        let item_name = test_item_name();
-        store.add_list_item(&item_name).await.unwrap();
+        let store = inmem_sqlite_store().await;
+        store
+            .add_recipe(
+                &Recipe::new("tomato soup"),
+                &Ingredients::from_input_string("6 tomatoes, 1 onion, 500ml vegetable stock"),
+                None,
+            )
+            .await
+            .unwrap();
+        store
+            .add_recipe(
+                &Recipe::new("roast tomatoes"),
+                &Ingredients::from_input_string("6 tomatoes, 1 tbsp olive oil"),
+                None,
+            )
+            .await
+            .unwrap();
 
-        let StoreResponse::List(list) = store.list().await.unwrap() else {
+        let StoreResponse::SearchResults(matches) = store.search("tomatoe").await.unwrap() else {
             todo!()
         };
+        insta::assert_debug_snapshot!(
+            matches
+                .iter()
+                .map(|found| format!("{}: {}", found.kind(), found.name()))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            "item: tomatoes",
+            "recipe: roast tomatoes",
+            "recipe: tomato soup",
+        ]
+        "###
+        );
 
-        let item_in_list = list.items().iter().any(|item| item.name() == &item_name);
-
-        assert!(item_in_list);
+        let StoreResponse::RecipeNotFound {
+            recipe,
+            suggestions,
+        } = store
+            .read(Read::recipe_from_name(Recipe::new("tomato sop")))
+            .await
+            .unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(recipe, Recipe::new("tomato sop"));
+        assert_eq!(suggestions, vec![Recipe::new("tomato soup")]);
     }
 
     #[tokio::test]
-    async fn test_add_list_recipe() {
+    async fn test_full_text_search() {
+        use common::query::Query;
+
         let store = inmem_sqlite_store().await;
+        for (recipe, ingredients) in [
+            ("hummus", "400g chickpeas, drained, 2 tbsp tahini, 1 lemon"),
+            (
+                "chana masala",
+                "400g chickpeas, 1 onion, a handful coriander",
+            ),
+            (
+                "lentil soup",
+                "200g red lentils, 1 onion, 1 carrot, finely chopped",
+            ),
+        ] {
+            store
+                .add_recipe(
+                    &Recipe::new(recipe),
+                    &Ingredients::from_input_string(ingredients),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
 
-        let ingredients =
-            Ingredients::from_iter(vec![Name::from("ingredient 1"), Name::from("ingredient 2")]);
+        let search = |query: &str| {
+            let store = store.clone();
+            let query = Query::parse(query).unwrap();
+            async move {
+                let StoreResponse::SearchResults(matches) =
+                    store.full_text_search(&query).await.unwrap()
+                else {
+                    todo!()
+                };
+                matches
+                    .iter()
+                    .map(|found| format!("{} {}", found.kind(), found.name()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        };
 
-        let recipe = Recipe::new("test recipe");
-        store.add_recipe(&recipe, &ingredients).await.unwrap();
+        insta::assert_snapshot!(
+            [
+                search("chickpeas NOT coriander").await,
+                search("lentil OR tahini").await,
+                search("chop*").await,
+                search("onion AND NOT soup").await,
+            ]
+            .join("\n"),
+            @r###"
+        recipe hummus, item chickpeas
+        recipe lentil soup, recipe hummus, item tahini, item red lentils
+        recipe lentil soup
+        recipe chana masala, item onion
+        "###
+        );
 
-        store.add_list_recipe(&recipe).await.unwrap();
+        store
+            .update_item(
+                &Name::from("chickpeas"),
+                Some(Name::from("garbanzo beans")),
+                None,
+            )
+            .await
+            .unwrap();
+        store.delete_recipe(&Recipe::new("hummus")).await.unwrap();
+        insta::assert_snapshot!(
+            search("garbanzo").await,
+            @"recipe chana masala, item garbanzo beans"
+        );
+    }
 
-        let StoreResponse::List(list) = store.list().await.unwrap() else {
-            todo!()
-        };
-        insta::assert_debug_snapshot!(list, @r###"
-        List {
-            checklist: [],
-            recipes: [
-                Recipe(
-                    "test recipe",
-                ),
-            ],
-            items: [
-                Item {
-                    name: Name(
-                        "ingredient 1",
-                    ),
-                    section: None,
-                    recipes: None,
-                },
-                Item {
-                    name: Name(
-                        "ingredient 2",
-                    ),
-                    section: None,
-                    recipes: None,
-                },
-            ],
+    #[tokio::test]
+    async fn test_cookable_recipes() {
+        use common::commands::Available;
+
+        let store = inmem_sqlite_store().await;
+        for (recipe, ingredients) in [
+            ("pancakes", "100g flour, 2 eggs, 300ml milk"),
+            ("omelette", "3 eggs, 1 tbsp butter"),
+            ("hummus", "400g chickpeas, 2 tbsp tahini"),
+        ] {
+            store
+                .add_recipe(
+                    &Recipe::new(recipe),
+                    &Ingredients::from_input_string(ingredients),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+        store
+            .add_checklist_item(&Name::from("chickpeas"))
+            .await
+            .unwrap();
+        store
+            .add_checklist_item(&Name::from("tahini"))
+            .await
+            .unwrap();
+        for item in ["flour", "butter", "eggs"] {
+            store
+                .add_pantry_item(&Name::from(item), None, None)
+                .await
+                .unwrap();
         }
+
+        let cookable = |available: Available| {
+            let store = store.clone();
+            async move {
+                let StoreResponse::RecipeCoverage(recipes) =
+                    store.cookable_recipes(&available).await.unwrap()
+                else {
+                    todo!()
+                };
+                recipes
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        };
+
+        insta::assert_snapshot!(
+            cookable(Available::Items(vec![Name::from("egg"), Name::from("Milk")])).await,
+            @r###"
+        pancakes: 2 of 3 ingredients, missing flour
+        omelette: 1 of 2 ingredients, missing butter
+        "###
+        );
+        insta::assert_snapshot!(
+            cookable(Available::Checklist).await,
+            @"hummus: 2 of 2 ingredients"
+        );
+        insta::assert_snapshot!(cookable(Available::Pantry).await, @r###"
+        omelette: 2 of 2 ingredients
+        pancakes: 2 of 3 ingredients, missing milk
         "###);
     }
 
     #[tokio::test]
-    async fn test_add_recipe() {
+    async fn test_pantry() {
+        use common::date::parse_date;
+
         let store = inmem_sqlite_store().await;
+        let measure = |amount: &str| Measure::parse(amount).unwrap();
+        let date = |date: &str| Some(parse_date(date).unwrap());
 
-        let ingredients =
-            Ingredients::from_iter(vec![Name::from("ingredient 1"), Name::from("ingredient 2")]);
+        store
+            .add_pantry_item(
+                &Name::from("flour"),
+                Some(&measure("1 kg")),
+                date("2026-12-01"),
+            )
+            .await
+            .unwrap();
+        store
+            .add_pantry_item(
+                &Name::from("flour"),
+                Some(&measure("500 g")),
+                date("2026-11-01"),
+            )
+            .await
+            .unwrap();
+        store
+            .add_pantry_item(&Name::from("eggs"), Some(&measure("6")), None)
+            .await
+            .unwrap();
+        store
+            .add_pantry_item(&Name::from("salt"), None, None)
+            .await
+            .unwrap();
 
-        let recipe = Recipe::new("test recipe");
-        store.add_recipe(&recipe, &ingredients).await.unwrap();
+        let pantry = || {
+            let store = store.clone();
+            async move {
+                let StoreResponse::Pantry(pantry) = store.pantry().await.unwrap() else {
+                    todo!()
+                };
+                pantry
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        };
+        insta::assert_snapshot!(pantry().await, @r###"
+        eggs: 6
+        flour: 1.5 kg (best before 2026-11-01)
+        salt
+        "###);
 
-        let StoreResponse::Recipes(recipes) = store.recipes().await.unwrap() else {
+        // The second recipe only counts on what the first one left.
+        for (recipe, ingredients) in [
+            ("cake", "400g flour, 4 eggs, 100g sugar"),
+            ("bread", "1.2kg flour, 1 tsp salt"),
+        ] {
+            let recipe = Recipe::new(recipe);
+            store
+                .add_recipe(&recipe, &Ingredients::from_input_string(ingredients), None)
+                .await
+                .unwrap();
+            store
+                .add_list_recipe(&ListName::default(), &recipe, None)
+                .await
+                .unwrap();
+        }
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
             todo!()
         };
-        assert_eq!(recipes.len(), 1);
-
-        let added_recipe = &recipes[0];
-        assert_eq!(added_recipe.as_str(), "test recipe");
+        insta::assert_debug_snapshot!(
+            list.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "flour (0.1 kg)",
+            "sugar (100 g)",
+        ]
+        "###
+        );
 
-        let StoreResponse::RecipeIngredients(Some(recipe_ingredients)) =
-            store.recipe_ingredients(&recipe).await.unwrap()
+        let StoreResponse::ConsumedPantryItem { left, .. } = store
+            .consume_pantry_item(&Name::from("egg"), Some(&measure("4")))
+            .await
+            .unwrap()
         else {
             todo!()
         };
-        assert_eq!(recipe_ingredients, ingredients);
+        assert_eq!(left.unwrap().to_string(), "eggs: 2");
+        store
+            .audit_pantry_item(&Name::from("flour"), Some(&measure("250 g")), None)
+            .await
+            .unwrap();
+        store
+            .audit_pantry_item(&Name::from("eggs"), Some(&measure("0")), None)
+            .await
+            .unwrap();
+        store
+            .consume_pantry_item(&Name::from("salt"), None)
+            .await
+            .unwrap();
+        insta::assert_snapshot!(pantry().await, @"flour: 250 g (best before 2026-11-01)");
+
+        assert!(matches!(
+            store.delete_pantry_item(&Name::from("salt")).await,
+            Err(StoreError::PantryItemNotFound(_))
+        ));
     }
 
     #[tokio::test]
-    async fn test_delete_checklist_item() {
+    async fn test_pantry_released_from_list() {
         let store = inmem_sqlite_store().await;
+        let list = ListName::default();
+        let flour = Name::from("flour");
+        let (bread, cake) = (Recipe::new("bread"), Recipe::new("cake"));
 
-        let item_name = test_item_name();
-        store.add_checklist_item(&item_name).await.unwrap();
+        store
+            .add_pantry_item(&flour, Some(&Measure::parse("500 g").unwrap()), None)
+            .await
+            .unwrap();
+        for (recipe, ingredients) in [(&bread, "400g flour"), (&cake, "400g flour, 100g sugar")] {
+            store
+                .add_recipe(recipe, &Ingredients::from_input_string(ingredients), None)
+                .await
+                .unwrap();
+        }
+        let flour_needed = || {
+            let store = store.clone();
+            let list = list.clone();
+            async move {
+                let StoreResponse::List(list) = store.list(&list).await.unwrap() else {
+                    todo!()
+                };
+                list.items()
+                    .iter()
+                    .find(|item| item.name().as_str() == "flour")
+                    .map(ToString::to_string)
+            }
+        };
 
-        let StoreResponse::Checklist(checklist) = store.checklist().await.unwrap() else {
+        // Refreshing the list gives back the stock it counted on.
+        store.add_list_recipe(&list, &bread, None).await.unwrap();
+        assert_eq!(flour_needed().await, None);
+        store.refresh_list(&list).await.unwrap();
+        store.add_list_recipe(&list, &bread, None).await.unwrap();
+        assert_eq!(flour_needed().await, None);
+
+        // So does taking the item off the list.
+        store.add_list_recipe(&list, &cake, None).await.unwrap();
+        assert_eq!(flour_needed().await.as_deref(), Some("flour (300 g)"));
+        store.delete_list_item(&list, &flour).await.unwrap();
+        store.add_list_recipe(&list, &bread, None).await.unwrap();
+        assert_eq!(flour_needed().await, None);
+
+        // And clearing it once it's been checked off.
+        store.add_list_recipe(&list, &cake, None).await.unwrap();
+        assert_eq!(flour_needed().await.as_deref(), Some("flour (300 g)"));
+        store.check_list_item(&list, &flour, true).await.unwrap();
+        store.clear_checked_list_items(&list).await.unwrap();
+        store.add_list_recipe(&list, &bread, None).await.unwrap();
+        assert_eq!(flour_needed().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_expiring() {
+        use common::date::parse_date;
+
+        let store = inmem_sqlite_store().await;
+        let date = |date: &str| parse_date(date).unwrap();
+
+        for (item, best_before) in [
+            ("milk", "2026-10-19"),
+            ("spinach", "2026-10-20"),
+            ("eggs", "2026-11-01"),
+        ] {
+            store
+                .add_pantry_item(&Name::from(item), None, Some(date(best_before)))
+                .await
+                .unwrap();
+        }
+        for (recipe, ingredients) in [
+            ("pancakes", "100g flour, 2 eggs, 300ml milk"),
+            ("saag", "200g spinach, 1 onion, 2 tbsp milk"),
+        ] {
+            store
+                .add_recipe(
+                    &Recipe::new(recipe),
+                    &Ingredients::from_input_string(ingredients),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        let StoreResponse::Expiring(report) = store.expiring(date("2026-10-18"), 3).await.unwrap()
+        else {
             todo!()
         };
+        insta::assert_snapshot!(report.to_string(), @r###"
+        expiring within 3 days:
+          milk, best before 2026-10-19 (tomorrow)
+          spinach, best before 2026-10-20 (in 2 days)
+        use them up in:
+          saag: milk, spinach
+          pancakes: milk
+        "###);
+    }
 
-        assert!(checklist.iter().any(|item| item.name() == &item_name));
+    #[tokio::test]
+    async fn test_meal_plan() {
+        use common::{
+            date::parse_date,
+            plan::{DateRange, Meal, MealSlot},
+        };
 
-        store.delete_checklist_item(&item_name).await.unwrap();
+        let store = inmem_sqlite_store().await;
+        let slot = |date: &str, meal| MealSlot::new(parse_date(date).unwrap(), meal);
+        let week = DateRange::week_of(parse_date("2026-10-19").unwrap());
 
-        let StoreResponse::Checklist(checklist) = store.checklist().await.unwrap() else {
+        for (recipe, ingredients) in [
+            ("noodles", "2 cloves garlic, 400g tofu"),
+            ("stir fry", "1 clove garlic, 200g tofu, 100g rice"),
+        ] {
+            store
+                .add_recipe(
+                    &Recipe::new(recipe),
+                    &Ingredients::from_input_string(ingredients),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+        for (date, meal, recipe) in [
+            ("2026-10-20", Meal::Dinner, "noodles"),
+            ("2026-10-21", Meal::Lunch, "stir fry"),
+            ("2026-10-23", Meal::Dinner, "noodles"),
+            ("2026-10-27", Meal::Dinner, "stir fry"),
+        ] {
+            store
+                .add_planned_meal(slot(date, meal), &Recipe::new(recipe))
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            store
+                .add_planned_meal(slot("2026-10-22", Meal::Dinner), &Recipe::new("soup"))
+                .await,
+            Err(StoreError::RecipeNotFound(_))
+        ));
+
+        // Moving onto a planned meal is refused, but swapping isn't.
+        assert!(matches!(
+            store
+                .move_meal(
+                    slot("2026-10-20", Meal::Dinner),
+                    slot("2026-10-21", Meal::Lunch)
+                )
+                .await,
+            Err(StoreError::MealSlotTaken(_))
+        ));
+        store
+            .swap_meals(
+                slot("2026-10-20", Meal::Dinner),
+                slot("2026-10-21", Meal::Lunch),
+            )
+            .await
+            .unwrap();
+        store
+            .move_meal(
+                slot("2026-10-23", Meal::Dinner),
+                slot("2026-10-24", Meal::Lunch),
+            )
+            .await
+            .unwrap();
+
+        let StoreResponse::MealPlan(plan) = store.meal_plan(week).await.unwrap() else {
             todo!()
         };
+        insta::assert_snapshot!(plan.to_string(), @r###"
+        plan for 2026-10-19 to 2026-10-25:
+        Monday 2026-10-19: nothing planned
+        Tuesday 2026-10-20:
+          dinner: stir fry
+        Wednesday 2026-10-21:
+          lunch: noodles
+        Thursday 2026-10-22: nothing planned
+        Friday 2026-10-23: nothing planned
+        Saturday 2026-10-24:
+          lunch: noodles
+        Sunday 2026-10-25: nothing planned
+        "###);
 
-        assert!(checklist.iter().all(|item| item.name() != &item_name));
+        let list = ListName::from("week");
+        store.add_list_from_plan(&list, week).await.unwrap();
+        let StoreResponse::List(list) = store.list(&list).await.unwrap() else {
+            todo!()
+        };
+        assert_eq!(list.plan(), Some(week));
+        // Noodles are planned twice, and share garlic and tofu with stir fry.
+        insta::assert_debug_snapshot!(
+            list.items().iter().map(ToString::to_string).collect::<Vec<_>>(),
+            @r###"
+        [
+            "garlic (5 clove)",
+            "tofu (1000 g)",
+            "rice (100 g)",
+        ]
+        "###
+        );
+
+        store
+            .delete_planned_meal(slot("2026-10-27", Meal::Dinner))
+            .await
+            .unwrap();
+        assert!(matches!(
+            store
+                .add_list_from_plan(
+                    &ListName::default(),
+                    DateRange::week_of(parse_date("2026-10-27").unwrap())
+                )
+                .await,
+            Err(StoreError::MealNotPlanned(_))
+        ));
     }
 
     #[tokio::test]
-    async fn test_delete_recipe() {
+    async fn test_meal_plan_calendar() {
+        use common::{
+            date::parse_date,
+            plan::{DateRange, Meal, MealSlot},
+        };
+
         let store = inmem_sqlite_store().await;
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("meal-plan.ics");
+        let slot = |date: &str, meal| MealSlot::new(parse_date(date).unwrap(), meal);
+        let week = DateRange::week_of(parse_date("2026-10-19").unwrap());
 
-        let ingredients =
-            Ingredients::from_iter(vec![Name::from("ingredient 1"), Name::from("ingredient 2")]);
+        for recipe in ["noodles", "stir fry"] {
+            store
+                .add_recipe(
+                    &Recipe::new(recipe),
+                    &Ingredients::from_input_string("2 cloves garlic, 400g tofu"),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+        for (date, meal, recipe) in [
+            ("2026-10-20", Meal::Dinner, "noodles"),
+            ("2026-10-21", Meal::Lunch, "stir fry"),
+            ("2026-10-27", Meal::Dinner, "stir fry"),
+        ] {
+            store
+                .add_planned_meal(slot(date, meal), &Recipe::new(recipe))
+                .await
+                .unwrap();
+        }
+        store.export_meal_plan(week, &path).await.unwrap();
+        let exported = std::fs::read_to_string(&path).unwrap();
+        assert!(exported.contains("DESCRIPTION:2 clove garlic\\n400 g tofu"));
 
-        let recipe = Recipe::new("test recipe");
-        store.add_recipe(&recipe, &ingredients).await.unwrap();
+        // Lunch is taken off the calendar and dinner moved to the next day.
+        let (before, after) = exported.split_once("BEGIN:VEVENT\r\nUID:20261021").unwrap();
+        let (_, after) = after.split_once("END:VEVENT\r\n").unwrap();
+        std::fs::write(
+            &path,
+            format!("{before}{after}").replace("DTSTART:20261020", "DTSTART:20261021"),
+        )
+        .unwrap();
+        store.import_meal_plan(&path).await.unwrap();
 
-        let StoreResponse::Recipes(recipes) = store.recipes().await.unwrap() else {
+        let StoreResponse::MealPlan(plan) = store
+            .meal_plan(DateRange::new(
+                week.start(),
+                parse_date("2026-10-27").unwrap(),
+            ))
+            .await
+            .unwrap()
+        else {
             todo!()
         };
-        assert_eq!(recipes.len(), 1);
-
-        let added_recipe = &recipes[0];
-        assert_eq!(added_recipe.as_str(), "test recipe");
+        insta::assert_snapshot!(
+            plan.meals().iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"),
+            @r###"
+        Wednesday 2026-10-21 dinner: noodles
+        Tuesday 2026-10-27 dinner: stir fry
+        "###
+        );
 
-        let StoreResponse::RecipeIngredients(Some(recipe_ingredients)) =
-            store.recipe_ingredients(&recipe).await.unwrap()
+        // Nothing changes when a recipe isn't found.
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .replace("noodles", "soup"),
+        )
+        .unwrap();
+        assert!(matches!(
+            store.import_meal_plan(&path).await,
+            Err(StoreError::RecipeNotFound(_))
+        ));
+        let StoreResponse::MealPlan(unchanged) = store
+            .meal_plan(DateRange::new(
+                week.start(),
+                parse_date("2026-10-27").unwrap(),
+            ))
+            .await
+            .unwrap()
         else {
             todo!()
         };
-        assert_eq!(recipe_ingredients, ingredients);
+        assert_eq!(unchanged, plan);
+    }
 
-        store.delete_recipe(&recipe).await.unwrap();
+    #[tokio::test]
+    async fn test_store_layout() {
+        use common::{
+            layout::{Aisle, StoreName},
+            section::Section,
+        };
 
-        let StoreResponse::Recipes(recipes) = store.recipes().await.unwrap() else {
+        let store = inmem_sqlite_store().await;
+        let shop = StoreName::from("Corner Shop");
+        let list = ListName::default();
+
+        for (item, section) in [("apples", "fresh"), ("bagels", "bakery"), ("eggs", "dairy")] {
+            let item = Name::from(item);
+            store
+                .add_item(&item, &Some(Section::from(section)))
+                .await
+                .unwrap();
+            store.add_list_item(&list, &item).await.unwrap();
+        }
+
+        store
+            .add_store(&shop, &[Aisle::from("produce"), Aisle::from("bread")])
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.add_store(&shop, &[]).await,
+            Err(StoreError::StoreExists(_))
+        ));
+        store
+            .set_section_aisle(&shop, &Section::from("fresh"), &Aisle::from("produce"))
+            .await
+            .unwrap();
+        store
+            .set_section_aisle(&shop, &Section::from("bakery"), &Aisle::from("bread"))
+            .await
+            .unwrap();
+        store
+            .set_item_aisle(&shop, &Name::from("eggs"), &Aisle::from("bread"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            store
+                .set_item_aisle(&shop, &Name::from("eggs"), &Aisle::from("dairy"))
+                .await,
+            Err(StoreError::AisleNotFound(_))
+        ));
+
+        // Walk the store the other way round
+        store
+            .set_store_aisles(&shop, &[Aisle::from("bread"), Aisle::from("produce")])
+            .await
+            .unwrap();
+
+        let StoreResponse::List(list) = store.list_for_store(&list, &shop).await.unwrap() else {
             todo!()
         };
-        assert_eq!(recipes.len(), 0);
+        insta::assert_debug_snapshot!(
+            list.groups()
+                .into_iter()
+                .map(|(aisle, items)| (
+                    aisle,
+                    items
+                        .iter()
+                        .map(|item| item.name().as_str())
+                        .collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            @r###"
+        [
+            (
+                Some(
+                    "bread",
+                ),
+                [
+                    "bagels",
+                    "eggs",
+                ],
+            ),
+            (
+                Some(
+                    "produce",
+                ),
+                [
+                    "apples",
+                ],
+            ),
+        ]
+        "###
+        );
 
-        let StoreResponse::RecipeIngredients(recipe_ingredients) =
-            store.recipe_ingredients(&recipe).await.unwrap()
+        // Dropping an aisle drops what was placed in it
+        let StoreResponse::StoreLayout(layout) = store
+            .set_store_aisles(&shop, &[Aisle::from("produce")])
+            .await
+            .unwrap()
         else {
             todo!()
         };
-        assert_eq!(recipe_ingredients, None);
+        assert_eq!(layout.section_aisles().len(), 1);
+        assert!(layout.item_aisles().is_empty());
+
+        store.delete_store(&shop).await.unwrap();
+        let StoreResponse::Stores(stores) = store.stores().await.unwrap() else {
+            todo!()
+        };
+        assert!(stores.is_empty());
     }
 
     #[tokio::test]
     async fn test_refresh_list() {
         let store = inmem_sqlite_store().await;
 
-        store.refresh_list().await.unwrap();
+        store.refresh_list(&ListName::default()).await.unwrap();
 
-        let StoreResponse::List(list) = store.list().await.unwrap() else {
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
             todo!()
         };
         assert_eq!(list.items().len(), 0);
 
         let item1 = Name::from("item 1");
         let item2 = Name::from("item 2");
-        store.add_list_item(&item1).await.unwrap();
-        store.add_list_item(&item2).await.unwrap();
+        store
+            .add_list_item(&ListName::default(), &item1)
+            .await
+            .unwrap();
+        store
+            .add_list_item(&ListName::default(), &item2)
+            .await
+            .unwrap();
 
-        let StoreResponse::List(list) = store.list().await.unwrap() else {
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
             todo!()
         };
         assert_eq!(list.items().len(), 2);
         assert!(list.items().iter().any(|item| item.name() == &item1));
         assert!(list.items().iter().any(|item| item.name() == &item2));
 
-        store.refresh_list().await.unwrap();
+        store.refresh_list(&ListName::default()).await.unwrap();
 
-        let StoreResponse::List(list) = store.list().await.unwrap() else {
+        let StoreResponse::List(list) = store.list(&ListName::default()).await.unwrap() else {
             todo!()
         };
         assert_eq!(list.items().len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_item_update() {
+        let store = inmem_sqlite_store().await;
+
+        let recipe = Recipe::new("test recipe");
+        let ingredients = Ingredients::from_input_string("2 eggs");
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
+
+        let eggs = Name::from("eggs");
+        store
+            .add_item(&eggs, &Some(common::section::Section::from("fresh")))
+            .await
+            .unwrap();
+
+        let StoreResponse::Item(item) = store.item(&eggs).await.unwrap() else {
+            todo!()
+        };
+        insta::assert_debug_snapshot!(item, @r###"
+        Item {
+            name: Name(
+                "eggs",
+            ),
+            section: Some(
+                Section(
+                    "fresh",
+                ),
+            ),
+            recipes: Some(
+                [
+                    Recipe(
+                        "test recipe",
+                    ),
+                ],
+            ),
+        }
+        "###);
+
+        let StoreResponse::UpdatedItem(item) = store
+            .update_item(
+                &eggs,
+                Some(Name::from("free-range eggs")),
+                Some(common::section::Section::from("dairy")),
+            )
+            .await
+            .unwrap()
+        else {
+            todo!()
+        };
+        assert_eq!(item.name().as_str(), "free-range eggs");
+        assert_eq!(item.section().unwrap().as_str(), "dairy");
+        assert_eq!(item.recipes().unwrap(), &[recipe]);
+
+        assert!(matches!(
+            store.item(&eggs).await,
+            Err(StoreError::ItemNotFound(_))
+        ));
+        // The new name's spelling variants find it too.
+        assert!(store.item(&Name::from("Free Range Egg")).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_items() {
         let store = inmem_sqlite_store().await;
@@ -858,7 +5404,7 @@ mod tests {
         let ingredients = Ingredients::from_iter(vec![Name::from("item 1"), Name::from("item 2")]);
         let recipe = Recipe::new("test recipe");
 
-        store.add_recipe(&recipe, &ingredients).await.unwrap();
+        store.add_recipe(&recipe, &ingredients, None).await.unwrap();
 
         let items = store.items().await.unwrap();
 
@@ -1,12 +1,20 @@
 use common::{
-    commands::{Add, ApiCommand, Delete, Read, Update},
-    export::ExportError,
+    calendar::{parse_meal_plan, CalendarError, MealCalendar},
+    commands::{Add, ApiCommand, Available, Delete, Read, Update},
+    date::Date,
+    export::{ExportError, ICalendarSerializable},
     fetcher::{FetchError, Fetcher},
+    fuzzy::{self, closest, MatchKind, SearchMatch},
+    ingredient::{Ingredient, Measure},
     item::{Item, Name},
     items::Items,
-    list::List,
+    layout::{Aisle, StoreLayout, StoreName},
+    list::{List, ListName},
     load::LoadError,
-    recipes::{Ingredients, Recipe},
+    pantry::{ExpiryReport, PantryItem},
+    plan::{DateRange, MealPlan, MealSlot, PlannedMeal},
+    query::Query,
+    recipes::{Ingredients, Recipe, RecipeCoverage, Servings},
     section::Section,
 };
 use futures::FutureExt;
@@ -18,12 +26,27 @@ use tokio::sync::{
 use tracing::warn;
 use url::Url;
 
-use std::{error::Error, fmt::Debug, fmt::Display, str::FromStr};
+use std::{
+    error::Error,
+    fmt::Debug,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use crate::sqlite::{connection::DbUri, SqliteStore};
 
+// How many recipes to suggest when one isn't found.
+const RECIPE_SUGGESTIONS: usize = 3;
+
 #[derive(Error, Debug)]
 pub enum StoreError {
+    #[error("aisle not found: {0}")]
+    AisleNotFound(String),
+
+    #[error("calendar error: {0}")]
+    CalendarError(#[from] CalendarError),
+
     #[error("SQLite database connection error: {0}")]
     ConnectionError(#[from] diesel::ConnectionError),
 
@@ -42,15 +65,54 @@ pub enum StoreError {
     #[error("fetch error: {0}")]
     FetchError(#[from] FetchError),
 
+    #[error("ingredient not found in recipe: {0}")]
+    IngredientNotFound(String),
+
+    #[error("already an item or alias: {0}")]
+    AliasExists(String),
+
+    #[error("alias not found: {0}")]
+    AliasNotFound(String),
+
+    #[error("item already exists: {0}")]
+    ItemExists(String),
+
+    #[error("item not found: {0}")]
+    ItemNotFound(String),
+
+    #[error("'{alias}' is an alias for '{item}': name the item itself, or remove the alias with 'delete alias'")]
+    ItemIsAlias { alias: String, item: String },
+
+    #[error("list is archived: {0}")]
+    ListArchived(String),
+
+    #[error("list already exists: {0}")]
+    ListExists(String),
+
+    #[error("item not on list: {0}")]
+    ListItemNotFound(String),
+
+    #[error("list not found: {0}")]
+    ListNotFound(String),
+
     #[error("JoinError: {0}")]
     JoinError(#[from] tokio::task::JoinError),
 
     #[error("load error: {0}")]
     LoadError(#[from] LoadError),
 
+    #[error("nothing planned for {0}")]
+    MealNotPlanned(String),
+
+    #[error("already planned: {0} (swap the meals instead)")]
+    MealSlotTaken(String),
+
     #[error("migration error: {0}")]
     MigrationError(#[from] Box<dyn Error + Send + Sync>),
 
+    #[error("not in the pantry: {0}")]
+    PantryItemNotFound(String),
+
     #[error("Parse store type error: {0}")]
     ParseStoreType(String),
 
@@ -60,8 +122,63 @@ pub enum StoreError {
     #[error("ingredients not found for: {0}")]
     RecipeIngredients(String),
 
+    #[error("recipe already exists: {0}")]
+    RecipeExists(String),
+
+    #[error("recipe not found: {0}")]
+    RecipeNotFound(String),
+
+    #[error("servings unknown for: {0} (add the recipe again with --servings)")]
+    RecipeServings(String),
+
+    #[error("store already exists: {0}")]
+    StoreExists(String),
+
+    #[error("store not found: {0}")]
+    StoreNotFound(String),
+
+    #[error("no section suggested for: {0}")]
+    SuggestionNotFound(String),
+
+    #[error("section already exists: {0}")]
+    SectionExists(String),
+
+    #[error(
+        "section not found: {section}{}",
+        .suggestion.as_ref().map(|s| format!(", did you mean '{s}'?")).unwrap_or_default()
+    )]
+    SectionNotFound {
+        section: String,
+        suggestion: Option<String>,
+    },
+
     #[error("ingredients not found for: {0}")]
-    SendError(#[from] SendError<(ApiCommand, Sender<Result<StoreResponse, StoreError>>)>),
+    SendError(#[from] Box<SendError<StoreSendWithReply>>),
+
+    #[error("unexpected response when reading {0}")]
+    UnexpectedResponse(&'static str),
+}
+
+type StoreSendWithReply = (ApiCommand, Sender<Result<StoreResponse, StoreError>>);
+
+impl StoreError {
+    /// A missing section, suggesting the closest of `sections` if any is
+    /// close enough to be what was meant.
+    pub(crate) fn section_not_found<'a>(
+        section: &Section,
+        sections: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        Self::SectionNotFound {
+            section: section.to_string(),
+            suggestion: closest(section.as_str(), sections).map(ToString::to_string),
+        }
+    }
+}
+
+impl From<SendError<StoreSendWithReply>> for StoreError {
+    fn from(error: SendError<StoreSendWithReply>) -> Self {
+        Self::SendError(Box::new(error))
+    }
 }
 
 #[derive(Debug)]
@@ -181,23 +298,126 @@ impl StoreDispatch {
 pub enum StoreResponse {
     AddedChecklistItem(Name),
     AddedItem(Name),
+    AddedItemAlias {
+        item: Name,
+        alias: Name,
+    },
+    AddedList(ListName),
     AddedListItem(Name),
     AddedListRecipe(Recipe),
+    /// A list made from the meal plan, with the recipes added to it.
+    AddedListFromPlan {
+        list: ListName,
+        range: DateRange,
+        recipes: Vec<Recipe>,
+    },
+    /// What's in the pantry of an item after adding to it.
+    AddedPantryItem(PantryItem),
     AddedRecipe(Recipe),
+    AddedSection(Section),
+    AddedStore(StoreName),
+    ArchivedList(ListName),
+    AuditedPantryItem(PantryItem),
     Checklist(Vec<Item>),
-    DeletedRecipe(Recipe),
+    CheckedListItem(Name),
+    ClearedCheckedListItems(Vec<Name>),
+    ClearedChecklist,
+    ClearedList(ListName),
+    /// An item used from the pantry, along with what's left of it.
+    ConsumedPantryItem {
+        item: Name,
+        left: Option<PantryItem>,
+    },
+    /// A deleted recipe, along with which of its ingredients were kept
+    /// because something else still uses them and which were removed, and
+    /// the meals it was planned for, which are now unplanned.
+    DeletedRecipe {
+        recipe: Recipe,
+        kept: Vec<Name>,
+        removed: Vec<Name>,
+        unplanned: Vec<MealSlot>,
+    },
     DeletedChecklistItem(Name),
+    DeletedItem(Name),
+    DeletedItemAlias(Name),
+    DeletedList(ListName),
+    DeletedListItem(Name),
+    DeletedPantryItem(Name),
+    DeletedPlannedMeal(PlannedMeal),
+    DeletedSection(Section),
+    DeletedStore(StoreName),
+    /// Groups of items that look like the same item, oldest first.
+    DuplicateItems(Vec<Vec<Name>>),
+    Expiring(ExpiryReport),
     Exported(Vec<Item>, List),
+    ExportedMealPlan {
+        plan: MealPlan,
+        path: PathBuf,
+    },
     FetchedRecipe((Recipe, Ingredients)),
     ImportToSqlite,
+    /// The plan read from a calendar, over the days it replaced.
+    ImportedMealPlan(MealPlan),
+    Item(Item),
+    /// Aliases paired with the items they stand for.
+    ItemAliases(Vec<(Name, Name)>),
     ItemAlreadyAdded(Name),
     Items(Items),
     List(List),
+    /// Items merged away, each paired with the item it was merged into.
+    MergedDuplicates(Vec<(Name, Name)>),
+    MergedItems {
+        item: Name,
+        into: Name,
+    },
+    MergedSection {
+        section: Section,
+        into: Section,
+    },
+    Lists {
+        active: Vec<ListName>,
+        archived: Vec<ListName>,
+    },
+    MealPlan(MealPlan),
+    /// A planned meal in its new slot, along with the slot it moved from.
+    MovedMeal {
+        meal: PlannedMeal,
+        from: MealSlot,
+    },
     NothingReturned(ApiCommand),
+    Pantry(Vec<PantryItem>),
+    PlannedMeal(PlannedMeal),
     Recipes(Vec<Recipe>),
+    /// Recipes ranked by how many of their ingredients are at hand.
+    RecipeCoverage(Vec<RecipeCoverage>),
     RecipeIngredients(Option<Ingredients>),
+    /// A recipe that wasn't found, along with those with similar names.
+    RecipeNotFound {
+        recipe: Recipe,
+        suggestions: Vec<Recipe>,
+    },
     RefreshList,
+    RenamedList {
+        list: ListName,
+        new_name: ListName,
+    },
+    RenamedSection {
+        section: Section,
+        new_name: Section,
+    },
+    /// Items given the sections suggested for them, or corrections.
+    ReviewedSuggestions(Vec<(Name, Section)>),
+    /// Matches for a search, best first.
+    SearchResults(Vec<SearchMatch>),
+    SectionSuggestions(Vec<(Name, Section)>),
     Sections(Vec<Section>),
+    StoreLayout(StoreLayout),
+    Stores(Vec<StoreName>),
+    /// What's planned for each of two swapped slots now.
+    SwappedMeals(Vec<PlannedMeal>),
+    UncheckedListItem(Name),
+    UpdatedItem(Item),
+    UpdatedRecipe(Recipe, Ingredients),
 }
 
 pub(crate) trait Storage: Send + Sync + 'static {
@@ -206,9 +426,13 @@ pub(crate) trait Storage: Send + Sync + 'static {
             ApiCommand::Add(cmd) => self.add(cmd).await,
             ApiCommand::Delete(cmd) => self.delete(cmd).await,
             ApiCommand::Export => self.export().await,
+            ApiCommand::ExportMealPlan { range, path } => self.export_meal_plan(range, &path).await,
             ApiCommand::FetchRecipe(url) => self.fetch_recipe(url).await,
+            ApiCommand::FullTextSearch(query) => self.full_text_search(&query).await,
             ApiCommand::ImportFromJson => self.import_from_json().await,
+            ApiCommand::ImportMealPlan(path) => self.import_meal_plan(&path).await,
             ApiCommand::Read(cmd) => self.read(cmd).await,
+            ApiCommand::Search(query) => self.search(&query).await,
             ApiCommand::Update(cmd) => self.update(cmd).await,
         }
     }
@@ -216,13 +440,42 @@ pub(crate) trait Storage: Send + Sync + 'static {
     async fn add(&self, cmd: Add) -> Result<StoreResponse, StoreError> {
         match cmd {
             Add::ChecklistItem(name) => self.add_checklist_item(&name).await,
-            Add::Item { name, section } => self.add_item(&name, &section).await,
-            Add::ListItem(name) => self.add_list_item(&name).await,
-            Add::ListRecipe(name) => self.add_list_recipe(&name).await,
+            Add::Item {
+                name,
+                section,
+                create_section,
+            } => {
+                if let (Some(section), false) = (&section, create_section) {
+                    self.existing_section(section).await?;
+                }
+                self.add_item(&name, &section).await
+            }
+            Add::List(list) => self.add_list(&list).await,
+            Add::CopyList { list, new_name } => self.copy_list(&list, &new_name).await,
+            Add::ListItem { list, item } => self.add_list_item(&list, &item).await,
+            Add::ListRecipe {
+                list,
+                recipe,
+                servings,
+            } => self.add_list_recipe(&list, &recipe, servings).await,
+            Add::ListFromPlan { list, range } => self.add_list_from_plan(&list, range).await,
+            Add::PantryItem {
+                item,
+                measure,
+                best_before,
+            } => {
+                self.add_pantry_item(&item, measure.as_ref(), best_before)
+                    .await
+            }
+            Add::PlannedMeal { slot, recipe } => self.add_planned_meal(slot, &recipe).await,
             Add::Recipe {
                 recipe,
                 ingredients,
-            } => self.add_recipe(&recipe, &ingredients).await,
+                servings,
+            } => self.add_recipe(&recipe, &ingredients, servings).await,
+            Add::ItemAlias { item, alias } => self.add_item_alias(&item, &alias).await,
+            Add::Section(section) => self.add_section(&section).await,
+            Add::Store { store, aisles } => self.add_store(&store, &aisles).await,
         }
     }
 
@@ -230,46 +483,173 @@ pub(crate) trait Storage: Send + Sync + 'static {
         match cmd {
             Read::All => Ok(StoreResponse::Items(self.items().await?)),
             Read::Checklist => self.checklist().await,
-            Read::Item(_name) => todo!(),
-            Read::List => self.list().await,
-            Read::ListRecipes => todo!(),
-            Read::Recipe(recipe) => self.recipe_ingredients(&recipe).await,
+            Read::CookableRecipes(available) => self.cookable_recipes(&available).await,
+            Read::DuplicateItems => self.duplicate_items().await,
+            Read::Expiring { today, within_days } => self.expiring(today, within_days).await,
+            Read::Item(name) => self.item(&name).await,
+            Read::ItemAliases => self.item_aliases().await,
+            Read::List(list) => self.list(&list).await,
+            Read::ListForStore { list, store } => self.list_for_store(&list, &store).await,
+            Read::ListRecipes(list) => self.list_recipes(&list).await,
+            Read::RemainingListItems(list) => match self.list(&list).await? {
+                StoreResponse::List(list) => Ok(StoreResponse::List(list.remaining())),
+                response => Ok(response),
+            },
+            Read::Lists => self.lists().await,
+            Read::MealPlan(range) => self.meal_plan(range).await,
+            Read::Pantry => self.pantry().await,
+            Read::Recipe { recipe, servings } => {
+                match self.scaled_recipe_ingredients(&recipe, servings).await? {
+                    StoreResponse::RecipeIngredients(None) => self.missing_recipe(recipe).await,
+                    response => Ok(response),
+                }
+            }
             Read::Recipes => self.recipes().await,
+            Read::SectionSuggestions => self.section_suggestions().await,
             Read::Sections => self.sections().await,
+            Read::Store(store) => self.store_layout(&store).await,
+            Read::Stores => self.stores().await,
         }
     }
 
     async fn update(&self, cmd: Update) -> Result<StoreResponse, StoreError> {
         match cmd {
-            Update::Item(_name) => todo!(),
-            Update::RefreshList => self.refresh_list().await,
-            Update::Recipe(_name) => todo!(),
+            Update::AddIngredient { recipe, ingredient } => {
+                self.add_recipe_ingredient(&recipe, &ingredient).await
+            }
+            Update::DeleteIngredient { recipe, ingredient } => {
+                self.delete_recipe_ingredient(&recipe, &ingredient).await
+            }
+            Update::EditIngredient {
+                recipe,
+                ingredient,
+                replacement,
+            } => {
+                self.edit_recipe_ingredient(&recipe, &ingredient, &replacement)
+                    .await
+            }
+            Update::Item {
+                name,
+                new_name,
+                section,
+            } => self.update_item(&name, new_name, section).await,
+            Update::MergeItems { item, into } => self.merge_items(&item, &into).await,
+            Update::MergeDuplicates => self.merge_duplicates().await,
+            Update::ConsumePantryItem { item, measure } => {
+                self.consume_pantry_item(&item, measure.as_ref()).await
+            }
+            Update::AuditPantryItem {
+                item,
+                measure,
+                best_before,
+            } => {
+                self.audit_pantry_item(&item, measure.as_ref(), best_before)
+                    .await
+            }
+            Update::MoveMeal { from, to } => self.move_meal(from, to).await,
+            Update::SwapMeals(slot, other) => self.swap_meals(slot, other).await,
+            Update::ArchiveList(list) => self.archive_list(&list).await,
+            Update::CheckListItem {
+                list,
+                item,
+                checked,
+            } => self.check_list_item(&list, &item, checked).await,
+            Update::RefreshList(list) => self.refresh_list(&list).await,
+            Update::RenameList { list, new_name } => self.rename_list(&list, &new_name).await,
+            Update::RenameRecipe { recipe, new_name } => {
+                self.rename_recipe(&recipe, &new_name).await
+            }
+            Update::MergeSection { section, into } => self.merge_section(&section, &into).await,
+            Update::RenameSection { section, new_name } => {
+                self.rename_section(&section, &new_name).await
+            }
+            Update::SectionOrder(order) => self.order_sections(&order).await,
+            Update::ConfirmSuggestions => self.confirm_suggestions().await,
+            Update::ReviewSuggestion { item, section } => {
+                if let Some(section) = &section {
+                    self.existing_section(section).await?;
+                }
+                self.review_suggestion(&item, section.as_ref()).await
+            }
+            Update::StoreAisles { store, aisles } => self.set_store_aisles(&store, &aisles).await,
+            Update::SectionAisle {
+                store,
+                section,
+                aisle,
+            } => self.set_section_aisle(&store, &section, &aisle).await,
+            Update::ItemAisle { store, item, aisle } => {
+                self.set_item_aisle(&store, &item, &aisle).await
+            }
         }
     }
 
     async fn delete(&self, cmd: Delete) -> Result<StoreResponse, StoreError> {
         match cmd {
             Delete::ChecklistItem(name) => self.delete_checklist_item(&name).await,
-            Delete::ClearChecklist => todo!(),
-            Delete::ClearList => todo!(),
-            Delete::Item(_name) => todo!(),
-            Delete::ListItem(_name) => todo!(),
+            Delete::ClearChecklist => self.clear_checklist().await,
+            Delete::ClearList(list) => self.clear_list(&list).await,
+            Delete::ClearCheckedListItems(list) => self.clear_checked_list_items(&list).await,
+            Delete::Item(name) => self.delete_item(&name).await,
+            Delete::ItemAlias(alias) => self.delete_item_alias(&alias).await,
+            Delete::List(list) => self.delete_list(&list).await,
+            Delete::ListItem { list, item } => self.delete_list_item(&list, &item).await,
+            Delete::PantryItem(item) => self.delete_pantry_item(&item).await,
+            Delete::PlannedMeal(slot) => self.delete_planned_meal(slot).await,
             Delete::Recipe(recipe) => self.delete_recipe(&recipe).await,
+            Delete::Section(section) => self.delete_section(&section).await,
+            Delete::Store(store) => self.delete_store(&store).await,
         }
     }
 
     async fn export(&self) -> Result<StoreResponse, StoreError>;
 
+    /// Writes the meals planned over `range` to an iCalendar file, with the
+    /// ingredients of each meal's recipe.
+    async fn export_meal_plan(
+        &self,
+        range: DateRange,
+        path: &Path,
+    ) -> Result<StoreResponse, StoreError> {
+        let StoreResponse::MealPlan(plan) = self.meal_plan(range).await? else {
+            return Err(StoreError::UnexpectedResponse("the meal plan"));
+        };
+
+        let mut meals = Vec::new();
+        for meal in plan.meals() {
+            let StoreResponse::RecipeIngredients(ingredients) =
+                self.recipe_ingredients(meal.recipe()).await?
+            else {
+                return Err(StoreError::UnexpectedResponse("recipe ingredients"));
+            };
+            meals.push((meal.clone(), ingredients.unwrap_or_default()));
+        }
+        MealCalendar::new(range, meals).serialize_to_icalendar_and_write(path)?;
+
+        Ok(StoreResponse::ExportedMealPlan {
+            plan,
+            path: path.to_path_buf(),
+        })
+    }
+
     async fn fetch_recipe(&self, url: Url) -> Result<StoreResponse, StoreError> {
-        let fetcher = Fetcher::from(url);
-        let (recipe, ingredients) = fetcher.fetch_recipe().await?;
+        let fetcher = Fetcher::try_from(url)?;
+        let (recipe, ingredients, servings) = fetcher.fetch_recipe().await?;
 
-        self.add_recipe(&recipe, &ingredients).await?;
+        self.add_recipe(&recipe, &ingredients, servings).await?;
         Ok(StoreResponse::FetchedRecipe((recipe, ingredients)))
     }
 
     async fn import_from_json(&self) -> Result<StoreResponse, StoreError>;
 
+    /// Reads the meals back from an iCalendar file, replacing the plan over
+    /// the days it covers.
+    async fn import_meal_plan(&self, path: &Path) -> Result<StoreResponse, StoreError> {
+        let plan = parse_meal_plan(&std::fs::read_to_string(path)?)?;
+        self.replace_meal_plan(&plan).await?;
+
+        Ok(StoreResponse::ImportedMealPlan(plan))
+    }
+
     // Create
     async fn add_item(
         &self,
@@ -279,20 +659,118 @@ pub(crate) trait Storage: Send + Sync + 'static {
 
     async fn add_checklist_item(&self, item: &Name) -> Result<StoreResponse, StoreError>;
 
-    async fn add_list_item(&self, item: &Name) -> Result<StoreResponse, StoreError>;
+    /// Makes `alias` resolve to `item` wherever items are added or looked up.
+    async fn add_item_alias(&self, item: &Name, alias: &Name) -> Result<StoreResponse, StoreError>;
 
-    async fn add_list_recipe(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError>;
+    async fn add_list(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
+    async fn add_list_item(
+        &self,
+        list: &ListName,
+        item: &Name,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn add_list_recipe(
+        &self,
+        list: &ListName,
+        recipe: &Recipe,
+        servings: Option<Servings>,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Adds the recipe of every meal planned over `range` to a list, so a
+    /// recipe planned twice is added twice and ingredients shared between
+    /// recipes are summed, then notes the list was made from the plan.
+    async fn add_list_from_plan(
+        &self,
+        list: &ListName,
+        range: DateRange,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Adds `measure` of an item to the pantry, keeping the earlier of its
+    /// best-before dates.
+    async fn add_pantry_item(
+        &self,
+        item: &Name,
+        measure: Option<&Measure>,
+        best_before: Option<Date>,
+    ) -> Result<StoreResponse, StoreError>;
 
     async fn add_recipe(
         &self,
         recipe: &Recipe,
         ingredients: &Ingredients,
+        servings: Option<Servings>,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Plans `recipe` for a meal, replacing whatever was planned for it.
+    async fn add_planned_meal(
+        &self,
+        slot: MealSlot,
+        recipe: &Recipe,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn add_section(&self, section: &Section) -> Result<StoreResponse, StoreError>;
+
+    async fn add_store(
+        &self,
+        store: &StoreName,
+        aisles: &[Aisle],
     ) -> Result<StoreResponse, StoreError>;
 
     // Read
     async fn checklist(&self) -> Result<StoreResponse, StoreError>;
 
-    async fn list(&self) -> Result<StoreResponse, StoreError>;
+    /// Recipes using any of the `available` items, ranked by how many of
+    /// their ingredients are available and then by how few are missing.
+    async fn cookable_recipes(&self, available: &Available) -> Result<StoreResponse, StoreError>;
+
+    /// An item along with its section and the recipes that use it.
+    async fn item(&self, item: &Name) -> Result<StoreResponse, StoreError>;
+
+    async fn item_aliases(&self) -> Result<StoreResponse, StoreError>;
+
+    /// Items whose names only differ in spelling, such as "egg" and "eggs".
+    async fn duplicate_items(&self) -> Result<StoreResponse, StoreError>;
+
+    /// Recipes matched on their names, ingredients or preparation notes,
+    /// followed by items, each best first.
+    async fn full_text_search(&self, query: &Query) -> Result<StoreResponse, StoreError>;
+
+    /// Creates `new_name` holding the items and recipes of `list`.
+    async fn copy_list(
+        &self,
+        list: &ListName,
+        new_name: &ListName,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn list(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
+    async fn list_recipes(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
+    async fn lists(&self) -> Result<StoreResponse, StoreError>;
+
+    async fn meal_plan(&self, range: DateRange) -> Result<StoreResponse, StoreError>;
+
+    async fn pantry(&self) -> Result<StoreResponse, StoreError>;
+
+    /// Pantry items best before `within_days` from `today`, along with the
+    /// recipes that would use them up.
+    async fn expiring(&self, today: Date, within_days: u32) -> Result<StoreResponse, StoreError>;
+
+    /// A list ordered by the aisles of `store`.
+    async fn list_for_store(
+        &self,
+        list: &ListName,
+        store: &StoreName,
+    ) -> Result<StoreResponse, StoreError> {
+        let StoreResponse::StoreLayout(layout) = self.store_layout(store).await? else {
+            return Err(StoreError::UnexpectedResponse("a store's layout"));
+        };
+        match self.list(list).await? {
+            StoreResponse::List(list) => Ok(StoreResponse::List(list.with_layout(layout))),
+            response => Ok(response),
+        }
+    }
 
     async fn items(&self) -> Result<Items, StoreError>;
 
@@ -300,13 +778,268 @@ pub(crate) trait Storage: Send + Sync + 'static {
 
     async fn recipe_ingredients(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError>;
 
+    async fn recipe_servings(&self, recipe: &Recipe) -> Result<Option<Servings>, StoreError>;
+
+    /// A recipe's ingredients, with quantities scaled from the recipe's own
+    /// servings to `servings` when given.
+    async fn scaled_recipe_ingredients(
+        &self,
+        recipe: &Recipe,
+        servings: Option<Servings>,
+    ) -> Result<StoreResponse, StoreError> {
+        let response = self.recipe_ingredients(recipe).await?;
+        let (Some(target), StoreResponse::RecipeIngredients(Some(ingredients))) =
+            (servings, &response)
+        else {
+            return Ok(response);
+        };
+
+        let Some(servings) = self.recipe_servings(recipe).await? else {
+            return Err(StoreError::RecipeServings(recipe.to_string()));
+        };
+
+        Ok(StoreResponse::RecipeIngredients(Some(
+            ingredients.clone().scale(servings.scale_factor(target)),
+        )))
+    }
+
+    /// Suggests recipes with names like that of a recipe that wasn't found.
+    async fn missing_recipe(&self, recipe: Recipe) -> Result<StoreResponse, StoreError> {
+        let StoreResponse::Recipes(recipes) = self.recipes().await? else {
+            return Err(StoreError::UnexpectedResponse("recipes"));
+        };
+        let suggestions = fuzzy::search(
+            recipe.as_str(),
+            recipes
+                .iter()
+                .map(|recipe| (MatchKind::Recipe, recipe.as_str())),
+        )
+        .into_iter()
+        .take(RECIPE_SUGGESTIONS)
+        .map(|found| Recipe::new(found.name()))
+        .collect();
+        Ok(StoreResponse::RecipeNotFound {
+            recipe,
+            suggestions,
+        })
+    }
+
+    /// Items, recipes and sections with names matching `query`, best first.
+    async fn search(&self, query: &str) -> Result<StoreResponse, StoreError> {
+        let items = self.items().await?;
+        let StoreResponse::Recipes(recipes) = self.recipes().await? else {
+            return Err(StoreError::UnexpectedResponse("recipes"));
+        };
+        let StoreResponse::Sections(sections) = self.sections().await? else {
+            return Err(StoreError::UnexpectedResponse("sections"));
+        };
+        let candidates = items
+            .collection_iter()
+            .map(|item| (MatchKind::Item, item.name().as_str()))
+            .chain(
+                recipes
+                    .iter()
+                    .map(|recipe| (MatchKind::Recipe, recipe.as_str())),
+            )
+            .chain(
+                sections
+                    .iter()
+                    .map(|section| (MatchKind::Section, section.as_str())),
+            );
+        Ok(StoreResponse::SearchResults(fuzzy::search(
+            query, candidates,
+        )))
+    }
+
     async fn sections(&self) -> Result<StoreResponse, StoreError>;
 
+    /// Sections suggested for items when their recipes were added.
+    async fn section_suggestions(&self) -> Result<StoreResponse, StoreError>;
+
+    /// Checks `section` exists, suggesting the closest existing section if
+    /// it doesn't.
+    async fn existing_section(&self, section: &Section) -> Result<(), StoreError> {
+        let StoreResponse::Sections(sections) = self.sections().await? else {
+            return Err(StoreError::UnexpectedResponse("sections"));
+        };
+        if sections.contains(section) {
+            return Ok(());
+        }
+        Err(StoreError::section_not_found(
+            section,
+            sections.iter().map(Section::as_str),
+        ))
+    }
+
+    async fn store_layout(&self, store: &StoreName) -> Result<StoreResponse, StoreError>;
+
+    async fn stores(&self) -> Result<StoreResponse, StoreError>;
+
     // Update
-    async fn refresh_list(&self) -> Result<StoreResponse, StoreError>;
+    async fn add_recipe_ingredient(
+        &self,
+        recipe: &Recipe,
+        ingredient: &Ingredient,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_recipe_ingredient(
+        &self,
+        recipe: &Recipe,
+        ingredient: &Name,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn edit_recipe_ingredient(
+        &self,
+        recipe: &Recipe,
+        ingredient: &Name,
+        replacement: &Ingredient,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Moves a planned meal to a slot that has nothing planned for it.
+    async fn move_meal(&self, from: MealSlot, to: MealSlot) -> Result<StoreResponse, StoreError>;
+
+    /// Swaps what's planned for two slots, moving a meal if one is empty.
+    async fn swap_meals(
+        &self,
+        slot: MealSlot,
+        other: MealSlot,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Replaces everything planned over the plan's range with its meals,
+    /// changing nothing if any of their recipes aren't found.
+    async fn replace_meal_plan(&self, plan: &MealPlan) -> Result<(), StoreError>;
+
+    async fn archive_list(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
+    /// Checks an item off a list, or puts it back when `checked` is false.
+    async fn check_list_item(
+        &self,
+        list: &ListName,
+        item: &Name,
+        checked: bool,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Empties the list, including any checked items, but keeps its recipes.
+    async fn refresh_list(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
+    async fn rename_list(
+        &self,
+        list: &ListName,
+        new_name: &ListName,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn rename_recipe(
+        &self,
+        recipe: &Recipe,
+        new_name: &Recipe,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn merge_section(
+        &self,
+        section: &Section,
+        into: &Section,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn rename_section(
+        &self,
+        section: &Section,
+        new_name: &Section,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn confirm_suggestions(&self) -> Result<StoreResponse, StoreError>;
+
+    /// Gives `item` the section suggested for it, or `section` if given.
+    async fn review_suggestion(
+        &self,
+        item: &Name,
+        section: Option<&Section>,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Puts sections in the given order, ahead of any sections not named.
+    async fn order_sections(&self, order: &[Section]) -> Result<StoreResponse, StoreError>;
+
+    /// Replaces a store's aisles, keeping what's placed in the aisles that
+    /// remain.
+    async fn set_store_aisles(
+        &self,
+        store: &StoreName,
+        aisles: &[Aisle],
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn set_section_aisle(
+        &self,
+        store: &StoreName,
+        section: &Section,
+        aisle: &Aisle,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn set_item_aisle(
+        &self,
+        store: &StoreName,
+        item: &Name,
+        aisle: &Aisle,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn update_item(
+        &self,
+        item: &Name,
+        new_name: Option<Name>,
+        section: Option<Section>,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn merge_items(&self, item: &Name, into: &Name) -> Result<StoreResponse, StoreError>;
+
+    async fn merge_duplicates(&self) -> Result<StoreResponse, StoreError>;
+
+    /// Takes `measure` of an item out of the pantry, or all of it when no
+    /// amount is given.
+    async fn consume_pantry_item(
+        &self,
+        item: &Name,
+        measure: Option<&Measure>,
+    ) -> Result<StoreResponse, StoreError>;
+
+    /// Replaces what the pantry holds of an item with `measure`, removing
+    /// it when the amount is zero.
+    async fn audit_pantry_item(
+        &self,
+        item: &Name,
+        measure: Option<&Measure>,
+        best_before: Option<Date>,
+    ) -> Result<StoreResponse, StoreError>;
 
     // Delete
+    async fn clear_checklist(&self) -> Result<StoreResponse, StoreError>;
+
+    /// Empties the list, including the recipes added to it.
+    async fn clear_list(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
+    /// Removes only the items that have been checked off the list.
+    async fn clear_checked_list_items(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
     async fn delete_checklist_item(&self, item: &Name) -> Result<StoreResponse, StoreError>;
 
+    /// Removes an item from the library, the list, the checklist, the pantry
+    /// and every recipe that uses it.
+    async fn delete_item(&self, item: &Name) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_item_alias(&self, alias: &Name) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_list(&self, list: &ListName) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_list_item(
+        &self,
+        list: &ListName,
+        item: &Name,
+    ) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_pantry_item(&self, item: &Name) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_planned_meal(&self, slot: MealSlot) -> Result<StoreResponse, StoreError>;
+
     async fn delete_recipe(&self, recipe: &Recipe) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_section(&self, section: &Section) -> Result<StoreResponse, StoreError>;
+
+    async fn delete_store(&self, store: &StoreName) -> Result<StoreResponse, StoreError>;
 }